@@ -0,0 +1,496 @@
+//! Common module of 1C configuration with wrappers of AddIn methods and
+//! properties, generated from `AddInDescription`s, so that BSL code calls
+//! typed functions with descriptions instead of the AddIn object directly
+
+use std::{fmt::Write, io, path::Path};
+
+use crate::{
+    description::{
+        AddInDescription, DescribeAddIn, MethodDescription, ParamDescription,
+        PropDescription, ValueType,
+    },
+    ffi::string_utils::from_os_string,
+    interface::ParamValue,
+};
+
+/// Name of the first parameter of wrappers, the AddIn object
+const COMPONENT: &str = "Компонента";
+
+/// Builder of BSL common module for AddIns of the library, e.g. called from a
+/// test or a binary of the AddIn crate:
+/// ```ignore
+/// BslModule::new("ОбщийМакет.МояКомпонента", "MyLib")
+///     .add_in::<MyAddIn>()
+///     .write("src/CommonModules/МояКомпонента/Ext/Module.bsl")?;
+/// ```
+pub struct BslModule {
+    location: String,
+    library: String,
+    add_ins: Vec<AddInDescription>,
+}
+
+impl BslModule {
+    /// # Arguments
+    /// * `location` - location of the library, passed to
+    ///   `ПодключитьВнешнююКомпоненту`, e.g. `ОбщийМакет.МояКомпонента`
+    /// * `library` - name of the library, so that objects are created with
+    ///   `Новый("AddIn.<library>.<AddIn>")`
+    pub fn new(location: &str, library: &str) -> Self {
+        Self {
+            location: location.to_owned(),
+            library: library.to_owned(),
+            add_ins: Vec::new(),
+        }
+    }
+
+    /// Adds wrappers of the AddIn, derived with `#[derive(AddIn)]`
+    pub fn add_in<T: DescribeAddIn>(self) -> Self {
+        self.description(T::description())
+    }
+
+    /// Adds wrappers of the AddIn by its description
+    pub fn description(mut self, description: AddInDescription) -> Self {
+        self.add_ins.push(description);
+        self
+    }
+
+    /// Text of the module
+    pub fn render(&self) -> String {
+        let mut module = format!(
+            "// Модуль сформирован по описанию внешней компоненты {}, \
+             изменения в нем будут потеряны\n\n\
+             #Область ПрограммныйИнтерфейс\n\n",
+            self.library
+        );
+        for add_in in &self.add_ins {
+            self.render_add_in(&mut module, add_in);
+        }
+        let library = bsl_string(&self.library);
+        let library_text = self.library.replace('"', "\"\"");
+        let _ = write!(
+            module,
+            "#КонецОбласти\n\n\
+             #Область СлужебныеПроцедурыИФункции\n\n\
+             Процедура ПодключитьКомпоненту()\n\t\n\
+             \tЕсли Не ПодключитьВнешнююКомпоненту({}, {library}, \
+             ТипВнешнейКомпоненты.Native) Тогда\n\
+             \t\tВызватьИсключение НСтр(\"ru = 'Не удалось подключить \
+             внешнюю компоненту {library_text}'; en = 'Unable to attach \
+             add-in {library_text}'\");\n\
+             \tКонецЕсли;\n\t\n\
+             КонецПроцедуры\n\n\
+             #КонецОбласти\n",
+            bsl_string(&self.location),
+        );
+        module
+    }
+
+    /// Writes the module in UTF-8 with BOM, as 1C stores `Module.bsl` files
+    /// of configuration dumps
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let module = format!("\u{feff}{}", self.render());
+        std::fs::write(path, module)
+    }
+
+    fn render_add_in(&self, module: &mut String, add_in: &AddInDescription) {
+        let name = add_in.name;
+        let doc = Doc::parse(add_in.doc);
+        let _ = writeln!(module, "#Область {name}\n");
+
+        let summary = if doc.summary.is_empty() {
+            vec![format!("Создает объект внешней компоненты {name}")]
+        } else {
+            doc.summary
+        };
+        write_comment(
+            module,
+            &summary,
+            &[],
+            Some(format!("ВнешняяКомпонента - объект {name}")),
+        );
+        let _ = writeln!(
+            module,
+            "Функция {name}_Создать() Экспорт\n\t\n\
+             \tПодключитьКомпоненту();\n\
+             \tВозврат Новый({});\n\t\n\
+             КонецФункции\n",
+            bsl_string(&format!("AddIn.{}.{name}", self.library)),
+        );
+
+        for prop in &add_in.props {
+            render_prop(module, name, prop);
+        }
+        for method in &add_in.methods {
+            render_method(module, name, method);
+        }
+        module.push_str("#КонецОбласти\n\n");
+    }
+}
+
+fn render_prop(module: &mut String, add_in: &str, prop: &PropDescription) {
+    let doc = Doc::parse(prop.doc);
+    let name = prop.name_ru;
+    let ty = type_name(prop.ty);
+    let component = component_param(add_in);
+    if prop.readable {
+        let mut summary = doc.summary.clone();
+        if summary.is_empty() {
+            summary.push(format!("Возвращает значение свойства {name}"));
+        }
+        write_comment(
+            module,
+            &summary,
+            std::slice::from_ref(&component),
+            Some(ty.to_owned()),
+        );
+        let _ = writeln!(
+            module,
+            "Функция {add_in}_{name}({COMPONENT}) Экспорт\n\t\n\
+             \tВозврат {COMPONENT}.{name};\n\t\n\
+             КонецФункции\n"
+        );
+    }
+    if prop.writable {
+        let mut summary =
+            vec![format!("Устанавливает значение свойства {name}")];
+        summary.extend(doc.summary);
+        let value = format!("Значение - {ty} - новое значение свойства");
+        write_comment(module, &summary, &[component, value], None);
+        let _ = writeln!(
+            module,
+            "Процедура {add_in}_Установить{name}({COMPONENT}, Знач Значение) Экспорт\n\t\n\
+             \t{COMPONENT}.{name} = Значение;\n\t\n\
+             КонецПроцедуры\n"
+        );
+    }
+}
+
+fn render_method(
+    module: &mut String,
+    add_in: &str,
+    method: &MethodDescription,
+) {
+    let doc = Doc::parse(method.doc);
+    let name = method.name_ru;
+    let names = param_names(&method.params);
+
+    let mut params_doc = vec![component_param(add_in)];
+    let mut params = vec![COMPONENT.to_owned()];
+    // arguments of the call, parameters or expressions of their defaults
+    let mut args = names.clone();
+    // indices of parameters with defaults, that can't be written in BSL
+    let mut omittable = vec![];
+    for (index, (param, bsl_name)) in
+        method.params.iter().zip(&names).enumerate()
+    {
+        let description = param.name.and_then(|name| doc.argument(name));
+        let mut line = format!("{bsl_name} - {}", type_name(param.ty));
+        if let Some(description) = description {
+            let _ = write!(line, " - {description}");
+        }
+        params_doc.push(line);
+
+        let mut bsl_param = if param.by_ref {
+            bsl_name.clone()
+        } else {
+            format!("Знач {bsl_name}")
+        };
+        match &param.default {
+            Some(default) => {
+                match (bsl_literal(default), bsl_expression(default)) {
+                    (Some(literal), _) => {
+                        let _ = write!(bsl_param, " = {literal}");
+                    }
+                    (None, Some(expression)) => {
+                        bsl_param.push_str(" = Неопределено");
+                        args[index] = format!(
+                        "?({bsl_name} = Неопределено, {expression}, {bsl_name})"
+                    );
+                    }
+                    (None, None) => {
+                        bsl_param.push_str(" = Неопределено");
+                        omittable.push(index);
+                    }
+                }
+            }
+            None if param.optional => bsl_param.push_str(" = Неопределено"),
+            None => {}
+        }
+        params.push(bsl_param);
+    }
+
+    let returns = method.returns.map(|ty| {
+        if doc.returns.is_empty() {
+            type_name(ty).to_owned()
+        } else {
+            format!("{} - {}", type_name(ty), doc.returns.join(" "))
+        }
+    });
+    write_comment(module, &doc.summary, &params_doc, returns);
+
+    let (keyword, end, call) = match method.returns {
+        Some(_) => ("Функция", "КонецФункции", "Возврат "),
+        None => ("Процедура", "КонецПроцедуры", ""),
+    };
+    let _ = writeln!(
+        module,
+        "{keyword} {add_in}_{name}({}) Экспорт\n\t\n\
+         {}\t\n\
+         {end}\n",
+        params.join(", "),
+        method_call(&format!("{call}{COMPONENT}.{name}"), &args, &omittable),
+    );
+}
+
+/// Number of parameters, left out of the call, for which a branch is written
+/// for every combination of them
+const MAX_OMITTABLE: usize = 3;
+
+/// Body of a method wrapper. Defaults, that have no BSL literal, are passed
+/// with expressions, while parameters, which defaults can't be written in BSL
+/// at all (e.g. infinite numbers), are left out of the call, when they are
+/// `Неопределено`, so that AddIn gets its own default values. Only first
+/// `MAX_OMITTABLE` of them are left out, the rest are passed as they are
+fn method_call(callee: &str, args: &[String], omittable: &[usize]) -> String {
+    let call = |omitted: &[usize]| {
+        let mut args: Vec<&str> = args
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| match omitted.contains(&index) {
+                true => "",
+                false => arg.as_str(),
+            })
+            .collect();
+        while args.last() == Some(&"") {
+            args.pop();
+        }
+        format!("{callee}({});", args.join(", "))
+    };
+    let omittable = &omittable[..omittable.len().min(MAX_OMITTABLE)];
+    if omittable.is_empty() {
+        return format!("\t{}\n", call(&[]));
+    }
+
+    // a branch for every combination of passed parameters
+    let mut body = String::new();
+    for mask in 1..1usize << omittable.len() {
+        let omitted: Vec<usize> = omittable
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, index)| *index)
+            .collect();
+        let condition = omittable
+            .iter()
+            .map(|index| match omitted.contains(index) {
+                true => format!("{} = Неопределено", args[*index]),
+                false => format!("{} <> Неопределено", args[*index]),
+            })
+            .collect::<Vec<_>>()
+            .join(" И ");
+        let keyword = if mask == 1 {
+            "Если"
+        } else {
+            "ИначеЕсли"
+        };
+        let _ = writeln!(
+            body,
+            "\t{keyword} {condition} Тогда\n\t\t{}",
+            call(&omitted)
+        );
+    }
+    let _ = writeln!(body, "\tИначе\n\t\t{}\n\tКонецЕсли;", call(&[]));
+    body
+}
+
+/// Writes comment in the format of 1C standards: description, parameters and
+/// returned value
+fn write_comment(
+    module: &mut String,
+    summary: &[String],
+    params: &[String],
+    returns: Option<String>,
+) {
+    let mut sections: Vec<Vec<String>> = vec![];
+    if !summary.is_empty() {
+        sections
+            .push(summary.iter().map(|line| format!("// {line}")).collect());
+    }
+    if !params.is_empty() {
+        let mut lines = vec!["// Параметры:".to_owned()];
+        lines.extend(params.iter().map(|line| format!("//  {line}")));
+        sections.push(lines);
+    }
+    if let Some(returns) = returns {
+        sections.push(vec![
+            "// Возвращаемое значение:".to_owned(),
+            format!("//  {returns}"),
+        ]);
+    }
+    for section in sections {
+        for line in section {
+            let _ = writeln!(module, "{line}");
+        }
+        module.push_str("//\n");
+    }
+}
+
+fn component_param(add_in: &str) -> String {
+    format!(
+        "{COMPONENT} - ВнешняяКомпонента - объект, созданный функцией {add_in}_Создать"
+    )
+}
+
+/// BSL names of parameters: Rust names in `CamelCase` or `ПараметрN`,
+/// repeated names, e.g. of variadic argument, are numbered
+fn param_names(params: &[ParamDescription]) -> Vec<String> {
+    let names: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| match param.name {
+            Some(name) => camel_case(name),
+            None => format!("Параметр{}", index + 1),
+        })
+        .collect();
+    let mut numbers = std::collections::HashMap::new();
+    names
+        .iter()
+        .map(|name| {
+            if names.iter().filter(|other| *other == name).count() == 1 {
+                return name.clone();
+            }
+            let number = numbers.entry(name).or_insert(0);
+            *number += 1;
+            format!("{name}{number}")
+        })
+        .collect()
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first.to_uppercase().chain(chars).collect::<String>()
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn type_name(ty: ValueType) -> &'static str {
+    match ty {
+        ValueType::Bool => "Булево",
+        ValueType::Number => "Число",
+        ValueType::String => "Строка",
+        ValueType::Date => "Дата",
+        ValueType::Blob => "ДвоичныеДанные",
+        ValueType::Any => "Произвольный",
+    }
+}
+
+/// BSL string literal, line breaks are continued with `|`
+fn bsl_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\"").replace('\n', "\n|"))
+}
+
+/// BSL literal of default value, `None` for values, that can't be written as
+/// literal, e.g. binary data or infinite number
+fn bsl_literal(value: &ParamValue) -> Option<String> {
+    match value {
+        ParamValue::Empty => Some("Неопределено".to_owned()),
+        ParamValue::Bool(value) => {
+            Some(if *value { "Истина" } else { "Ложь" }.to_owned())
+        }
+        ParamValue::I32(value) => Some(value.to_string()),
+        ParamValue::F64(value) if value.is_finite() => Some(value.to_string()),
+        ParamValue::String(value) => Some(bsl_string(&from_os_string(value))),
+        ParamValue::Date(tm) => Some(format!(
+            "'{:04}{:02}{:02}{:02}{:02}{:02}'",
+            tm.year + 1900,
+            tm.mon + 1,
+            tm.mday,
+            tm.hour,
+            tm.min,
+            tm.sec
+        )),
+        _ => None,
+    }
+}
+
+/// Expression of the value, that has no literal in BSL
+fn bsl_expression(value: &ParamValue) -> Option<String> {
+    let data = match value {
+        ParamValue::Blob(data) => data.as_slice(),
+        ParamValue::PlatformBlob(data) => data,
+        _ => return None,
+    };
+    let hex = data.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02X}");
+        hex
+    });
+    Some(format!("ПолучитьДвоичныеДанныеИзHexСтроки(\"{hex}\")"))
+}
+
+/// Rust doc comment, split into the sections of the repository style:
+/// description, `# Arguments` with "* `name` - description" lines and
+/// `# Returns`
+#[derive(Default)]
+struct Doc {
+    summary: Vec<String>,
+    arguments: Vec<(String, String)>,
+    returns: Vec<String>,
+}
+
+impl Doc {
+    fn parse(doc: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut section = "";
+        for line in doc.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(header) = line.strip_prefix("# ") {
+                section = header.trim();
+                continue;
+            }
+            match section {
+                "" => parsed.summary.push(line.to_owned()),
+                "Arguments" => {
+                    let item = line
+                        .strip_prefix("* `")
+                        .or_else(|| line.strip_prefix("- `"))
+                        .and_then(|item| item.split_once('`'));
+                    match (item, parsed.arguments.last_mut()) {
+                        (Some((name, description)), _) => {
+                            let description = description
+                                .trim_start()
+                                .trim_start_matches(['-', ':'])
+                                .trim();
+                            parsed.arguments.push((
+                                name.to_owned(),
+                                description.to_owned(),
+                            ));
+                        }
+                        (None, Some((_, description))) => {
+                            description.push(' ');
+                            description.push_str(line);
+                        }
+                        (None, None) => {}
+                    }
+                }
+                "Returns" => parsed.returns.push(line.to_owned()),
+                _ => {}
+            }
+        }
+        parsed
+    }
+
+    fn argument(&self, name: &str) -> Option<&str> {
+        self.arguments
+            .iter()
+            .find(|(argument, _)| argument == name)
+            .map(|(_, description)| description.as_str())
+            .filter(|description| !description.is_empty())
+    }
+}
@@ -0,0 +1,397 @@
+//! Conversions between `ParamValue` and Rust types, for the code that works
+//! with parameters directly, e.g. manual `AddInWrapper` implementations or
+//! `raw` functions
+
+use std::fmt::Display;
+
+use crate::{
+    date_time::DateConversionError,
+    ffi::{
+        memory_manager::PlatformBlob,
+        provided_types::Tm,
+        string_utils::{from_os_string, os_string},
+    },
+    interface::{ParamValue, U16String},
+};
+
+/// Error of conversion of `ParamValue` into Rust type
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValueConversionError {
+    /// Value is of the variant, that can't be converted to the type
+    UnexpectedVariant {
+        /// Name of the expected variant
+        expected: &'static str,
+        /// Name of the actual variant
+        actual: &'static str,
+    },
+    /// Number isn't an integer or doesn't fit into the type
+    OutOfRange {
+        /// Name of the type
+        expected: &'static str,
+        /// Value of the number
+        value: f64,
+    },
+    /// Value is `Date`, but it can't be converted to the type
+    InvalidDate(DateConversionError),
+}
+
+impl Display for ParamValueConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedVariant { expected, actual } => {
+                write!(f, "expected {expected} value, got {actual}")
+            }
+            Self::OutOfRange { expected, value } => {
+                write!(f, "number {value} can't be represented as {expected}")
+            }
+            Self::InvalidDate(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ParamValueConversionError {}
+
+impl ParamValue {
+    /// Name of the variant, used in conversion errors
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Empty => "Empty",
+            Self::Bool(_) => "Bool",
+            Self::I32(_) => "I32",
+            Self::F64(_) => "F64",
+            Self::Date(_) => "Date",
+            Self::String(_) => "String",
+            Self::Blob(_) => "Blob",
+            Self::PlatformBlob(_) => "PlatformBlob",
+        }
+    }
+
+    /// Checks if value is `Empty`, i.e. `Undefined` in 1C
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Returns value of `Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `I32`, or of `F64` if it's an integer in range of `i32`
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_i64().and_then(|val| val.try_into().ok())
+    }
+
+    /// Returns value of `I32`, or of `F64` if it's an integer in range of `i64`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::I32(val) => Some(i64::from(*val)),
+            Self::F64(val) if is_integer_in_range(*val, i64::MIN, i64::MAX) => {
+                Some(*val as i64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns value of `F64` or `I32`, as 1C has a single number type
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::I32(val) => Some(f64::from(*val)),
+            Self::F64(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `String`, converted from UTF-16
+    pub fn as_string(&self) -> Option<String> {
+        self.as_utf16().map(from_os_string)
+    }
+
+    /// Returns value of `String` as is, in UTF-16
+    pub fn as_utf16(&self) -> Option<&[u16]> {
+        match self {
+            Self::String(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `Blob` or `PlatformBlob`
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match self {
+            Self::Blob(val) => Some(val),
+            Self::PlatformBlob(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `Date`
+    pub fn as_date(&self) -> Option<&Tm> {
+        match self {
+            Self::Date(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns value of `Date`, converted to `chrono::NaiveDateTime`, if
+    /// it's a valid date
+    #[cfg(feature = "chrono")]
+    pub fn as_date_time(&self) -> Option<chrono::NaiveDateTime> {
+        self.as_date().and_then(|val| val.try_into().ok())
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParamValueConversionError {
+        ParamValueConversionError::UnexpectedVariant {
+            expected,
+            actual: self.variant_name(),
+        }
+    }
+}
+
+/// `f64` can be converted to integer type without loss
+fn is_integer_in_range<T: Into<i128>>(value: f64, min: T, max: T) -> bool {
+    value.fract() == 0.0
+        && value >= min.into() as f64
+        && value < (max.into() + 1) as f64
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        Self::F64(value)
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        Self::String(os_string(&value))
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        Self::String(os_string(value))
+    }
+}
+
+impl From<Vec<u8>> for ParamValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<&[u8]> for ParamValue {
+    fn from(value: &[u8]) -> Self {
+        Self::Blob(value.to_vec())
+    }
+}
+
+impl From<PlatformBlob> for ParamValue {
+    fn from(value: PlatformBlob) -> Self {
+        Self::PlatformBlob(value)
+    }
+}
+
+impl From<Tm> for ParamValue {
+    fn from(value: Tm) -> Self {
+        Self::Date(value)
+    }
+}
+
+/// `None` is passed to 1C as `Undefined`
+impl<T: Into<ParamValue>> From<Option<T>> for ParamValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Empty, Into::into)
+    }
+}
+
+/// Integers that always fit into `i32`
+macro_rules! from_small_int {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for ParamValue {
+            fn from(value: $ty) -> Self {
+                Self::I32(i32::from(value))
+            }
+        }
+    )*};
+}
+
+/// Integers that may not fit into `i32`, they are passed as `F64` then
+macro_rules! from_large_int {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for ParamValue {
+            fn from(value: $ty) -> Self {
+                match i32::try_from(value) {
+                    Ok(value) => Self::I32(value),
+                    Err(_) => Self::F64(value as f64),
+                }
+            }
+        }
+    )*};
+}
+
+from_small_int!(i8, i16, i32, u8, u16);
+from_large_int!(i64, isize, u32, u64, usize);
+
+macro_rules! try_from_int {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<ParamValue> for $ty {
+            type Error = ParamValueConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                let out_of_range = |value: f64| {
+                    ParamValueConversionError::OutOfRange {
+                        expected: stringify!($ty),
+                        value,
+                    }
+                };
+                match value {
+                    ParamValue::I32(val) => {
+                        <$ty>::try_from(val).map_err(|_| out_of_range(val.into()))
+                    }
+                    ParamValue::F64(val)
+                        if is_integer_in_range(val, <$ty>::MIN, <$ty>::MAX) =>
+                    {
+                        Ok(val as $ty)
+                    }
+                    ParamValue::F64(val) => Err(out_of_range(val)),
+                    other => Err(other.unexpected("number")),
+                }
+            }
+        }
+    )*};
+}
+
+try_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+macro_rules! try_from_variant {
+    ($ty:ty, $expected:literal, $pattern:pat => $value:expr) => {
+        impl TryFrom<ParamValue> for $ty {
+            type Error = ParamValueConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    $pattern => Ok($value),
+                    other => Err(other.unexpected($expected)),
+                }
+            }
+        }
+    };
+}
+
+try_from_variant!(bool, "Bool", ParamValue::Bool(val) => val);
+try_from_variant!(String, "String", ParamValue::String(val) => from_os_string(&val));
+try_from_variant!(U16String, "String", ParamValue::String(val) => U16String::from_vec(val));
+try_from_variant!(Tm, "Date", ParamValue::Date(val) => val);
+
+/// 1C has a single number type, so `I32` is a valid `f64` as well
+impl TryFrom<ParamValue> for f64 {
+    type Error = ParamValueConversionError;
+
+    fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+        value.as_f64().ok_or_else(|| value.unexpected("number"))
+    }
+}
+
+/// `PlatformBlob` is copied, as it's the same `BinaryData` for 1C
+impl TryFrom<ParamValue> for Vec<u8> {
+    type Error = ParamValueConversionError;
+
+    fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+        match value {
+            ParamValue::Blob(val) => Ok(val),
+            ParamValue::PlatformBlob(val) => Ok(val.to_vec()),
+            other => Err(other.unexpected("Blob")),
+        }
+    }
+}
+
+/// `Undefined` from 1C is converted to `None`
+macro_rules! try_from_option {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<ParamValue> for Option<$ty> {
+            type Error = ParamValueConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    ParamValue::Empty => Ok(None),
+                    other => <$ty>::try_from(other).map(Some),
+                }
+            }
+        }
+    )*};
+}
+
+try_from_option!(
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64,
+    f64,
+    String,
+    U16String,
+    Vec<u8>,
+    Tm
+);
+
+/// Date and time types, enabled with features. `InvalidDate` is returned for
+/// dates that can't be represented by the type, and 1C empty date
+/// `0001-01-01` is converted to `None` for `Option`, same as `Empty`
+macro_rules! date_conversions {
+    ($($(#[$attr:meta])* $ty:ty),*) => {$(
+        $(#[$attr])*
+        impl From<$ty> for ParamValue {
+            fn from(value: $ty) -> Self {
+                Self::Date(value.into())
+            }
+        }
+
+        $(#[$attr])*
+        impl TryFrom<ParamValue> for $ty {
+            type Error = ParamValueConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    ParamValue::Date(val) => <$ty>::try_from(val)
+                        .map_err(ParamValueConversionError::InvalidDate),
+                    other => Err(other.unexpected("Date")),
+                }
+            }
+        }
+
+        $(#[$attr])*
+        impl TryFrom<ParamValue> for Option<$ty> {
+            type Error = ParamValueConversionError;
+
+            fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+                match value {
+                    ParamValue::Empty => Ok(None),
+                    ParamValue::Date(val) if val.is_empty_date() => Ok(None),
+                    other => <$ty>::try_from(other).map(Some),
+                }
+            }
+        }
+    )*};
+}
+
+date_conversions!(
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDateTime,
+    #[cfg(feature = "chrono")]
+    chrono::DateTime<chrono::FixedOffset>,
+    #[cfg(feature = "time")]
+    time::PrimitiveDateTime,
+    #[cfg(feature = "time")]
+    time::OffsetDateTime
+);
@@ -0,0 +1,99 @@
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Timelike,
+};
+
+use super::DateConversionError;
+use crate::ffi::provided_types::Tm;
+
+/// `chrono` represents leap second as 59th second with extra nanoseconds
+const LEAP_SECOND_NANOS: u32 = 1_000_000_000;
+
+impl TryFrom<&Tm> for NaiveDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        let fields = value.fields()?;
+        let date = NaiveDate::from_ymd_opt(
+            fields.year,
+            fields.month as u32,
+            fields.day as u32,
+        )
+        .ok_or_else(|| fields.invalid_date())?;
+
+        let (hour, min) = (fields.hour as u32, fields.min as u32);
+        let date_time = match fields.sec {
+            60 => date.and_hms_nano_opt(hour, min, 59, LEAP_SECOND_NANOS),
+            sec => date.and_hms_opt(hour, min, sec as u32),
+        };
+        date_time.ok_or_else(|| fields.invalid_date())
+    }
+}
+
+impl TryFrom<Tm> for NaiveDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<&NaiveDateTime> for Tm {
+    fn from(value: &NaiveDateTime) -> Self {
+        let leap_second = value.nanosecond() >= LEAP_SECOND_NANOS;
+        Tm::from_fields(
+            value.year(),
+            value.month() as i32,
+            value.day() as i32,
+            value.hour() as i32,
+            value.minute() as i32,
+            value.second() as i32 + i32::from(leap_second),
+        )
+    }
+}
+
+impl From<NaiveDateTime> for Tm {
+    fn from(value: NaiveDateTime) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Offset is taken from `gmtoff`, so on windows it is always UTC
+impl TryFrom<&Tm> for DateTime<FixedOffset> {
+    type Error = DateConversionError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        let offset = value.utc_offset();
+        let offset = i32::try_from(offset)
+            .ok()
+            .and_then(FixedOffset::east_opt)
+            .ok_or(DateConversionError::InvalidOffset(offset))?;
+        let local = NaiveDateTime::try_from(value)?;
+        local
+            .and_local_timezone(offset)
+            .single()
+            .ok_or(DateConversionError::InvalidOffset(value.utc_offset()))
+    }
+}
+
+impl TryFrom<Tm> for DateTime<FixedOffset> {
+    type Error = DateConversionError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// Offset is stored in `gmtoff`, so on windows it is lost
+impl From<&DateTime<FixedOffset>> for Tm {
+    fn from(value: &DateTime<FixedOffset>) -> Self {
+        let mut tm = Tm::from(value.naive_local());
+        tm.set_utc_offset(value.offset().local_minus_utc().into());
+        tm
+    }
+}
+
+impl From<DateTime<FixedOffset>> for Tm {
+    fn from(value: DateTime<FixedOffset>) -> Self {
+        Self::from(&value)
+    }
+}
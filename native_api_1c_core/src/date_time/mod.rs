@@ -0,0 +1,205 @@
+//! Conversions of `Tm` into date and time types of `chrono` and `time` crates,
+//! enabled with features of the same names. Conversions into Rust types are
+//! fallible, as `Tm` from 1C is not guaranteed to hold a valid date. UTC offset
+//! is taken from `gmtoff` on unix, it already accounts for daylight saving
+//! time, so `isdst` is not used. On windows `Tm` has no offset, so it is
+//! always treated as UTC.
+//!
+//! 1C has no `null` date, instead `0001-01-01T00:00:00` is used as empty date.
+//! It is converted as any other date, see `Tm::empty_date` and
+//! `Tm::is_empty_date` to handle it explicitly.
+
+use std::{ffi::c_int, fmt::Display};
+
+use crate::ffi::provided_types::Tm;
+
+#[cfg(feature = "chrono")]
+mod chrono_impl;
+#[cfg(feature = "time")]
+mod time_impl;
+
+/// Error of conversion of `Tm` into date and time type
+#[derive(Clone, Debug, PartialEq)]
+pub enum DateConversionError {
+    /// Field of `Tm` is out of its range, e.g. 13th month
+    InvalidField {
+        /// Name of the field
+        field: &'static str,
+        /// Value of the field
+        value: c_int,
+    },
+    /// Date doesn't exist, e.g. February 30th, or can't be represented by
+    /// the type
+    InvalidDate {
+        /// Year
+        year: c_int,
+        /// Month, starting from 1
+        month: c_int,
+        /// Day of the month
+        day: c_int,
+    },
+    /// UTC offset in seconds is out of range
+    InvalidOffset(i64),
+}
+
+impl Display for DateConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidField { field, value } => {
+                write!(f, "field `{field}` of date is out of range: {value}")
+            }
+            Self::InvalidDate { year, month, day } => {
+                write!(f, "invalid date {year:04}-{month:02}-{day:02}")
+            }
+            Self::InvalidOffset(offset) => {
+                write!(f, "UTC offset of {offset} seconds is out of range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateConversionError {}
+
+/// Fields of `Tm` in calendar form, checked to be in their ranges
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) struct DateFields {
+    pub year: c_int,
+    pub month: c_int,
+    pub day: c_int,
+    pub hour: c_int,
+    pub min: c_int,
+    pub sec: c_int,
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl DateFields {
+    pub fn invalid_date(&self) -> DateConversionError {
+        DateConversionError::InvalidDate {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+}
+
+impl Tm {
+    /// Creates `Tm` from calendar fields, computing day of the week and day
+    /// of the year. `month` starts from 1, `sec` can be 60 for leap second
+    pub fn from_fields(
+        year: c_int,
+        month: c_int,
+        day: c_int,
+        hour: c_int,
+        min: c_int,
+        sec: c_int,
+    ) -> Self {
+        let days = days_from_civil(year, month, day);
+        let year_start = days_from_civil(year, 1, 1);
+        Self {
+            sec,
+            min,
+            hour,
+            mday: day,
+            mon: month - 1,
+            year: year - 1900,
+            wday: (days + 4).rem_euclid(7) as c_int,
+            yday: (days - year_start) as c_int,
+            ..Default::default()
+        }
+    }
+
+    /// Empty date of 1C, `0001-01-01T00:00:00`
+    pub fn empty_date() -> Self {
+        Self::from_fields(1, 1, 1, 0, 0, 0)
+    }
+
+    /// Checks if it's the empty date of 1C, `0001-01-01T00:00:00`
+    pub fn is_empty_date(&self) -> bool {
+        self.year == 1 - 1900
+            && self.mon == 0
+            && self.mday == 1
+            && self.hour == 0
+            && self.min == 0
+            && self.sec == 0
+    }
+
+    /// UTC offset in seconds, always 0 on windows
+    // `c_long` is `i32` on some targets
+    #[allow(clippy::useless_conversion)]
+    pub fn utc_offset(&self) -> i64 {
+        #[cfg(target_family = "unix")]
+        return i64::from(self.gmtoff);
+        #[cfg(target_family = "windows")]
+        return 0;
+    }
+
+    /// Sets UTC offset in seconds, ignored on windows
+    pub fn set_utc_offset(&mut self, offset: i64) {
+        #[cfg(target_family = "unix")]
+        {
+            self.gmtoff = offset as std::ffi::c_long;
+        }
+        #[cfg(target_family = "windows")]
+        let _ = offset;
+    }
+
+    /// Calendar fields, checked to be in their ranges
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn fields(&self) -> Result<DateFields, DateConversionError> {
+        let check = |field, value: c_int, min, max| {
+            if (min..=max).contains(&value) {
+                Ok(value)
+            } else {
+                Err(DateConversionError::InvalidField { field, value })
+            }
+        };
+        let fields = DateFields {
+            year: self.year.checked_add(1900).ok_or(
+                DateConversionError::InvalidField {
+                    field: "year",
+                    value: self.year,
+                },
+            )?,
+            month: check("mon", self.mon, 0, 11)? + 1,
+            day: check("mday", self.mday, 1, 31)?,
+            hour: check("hour", self.hour, 0, 23)?,
+            min: check("min", self.min, 0, 59)?,
+            sec: check("sec", self.sec, 0, 60)?,
+        };
+        if fields.day > days_in_month(fields.year, fields.month) {
+            return Err(fields.invalid_date());
+        }
+        Ok(fields)
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "serde"))]
+pub(crate) fn is_leap_year(year: c_int) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "serde"))]
+pub(crate) fn days_in_month(year: c_int, month: c_int) -> c_int {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01 for the given date of proleptic Gregorian
+/// calendar, see http://howardhinnant.github.io/date_algorithms.html
+pub(crate) fn days_from_civil(year: c_int, month: c_int, day: c_int) -> i64 {
+    let year = i64::from(if month <= 2 { year - 1 } else { year });
+    let month = i64::from(month);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(day)
+            - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
@@ -0,0 +1,90 @@
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::DateConversionError;
+use crate::ffi::provided_types::Tm;
+
+impl TryFrom<&Tm> for PrimitiveDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        let fields = value.fields()?;
+        let month = Month::try_from(fields.month as u8)
+            .map_err(|_| fields.invalid_date())?;
+        let date =
+            Date::from_calendar_date(fields.year, month, fields.day as u8)
+                .map_err(|_| fields.invalid_date())?;
+        // `time` has no leap seconds, so it is truncated to the 59th second
+        let time = Time::from_hms(
+            fields.hour as u8,
+            fields.min as u8,
+            fields.sec.min(59) as u8,
+        )
+        .map_err(|_| fields.invalid_date())?;
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+}
+
+impl TryFrom<Tm> for PrimitiveDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<&PrimitiveDateTime> for Tm {
+    fn from(value: &PrimitiveDateTime) -> Self {
+        Tm::from_fields(
+            value.year(),
+            value.month() as i32,
+            value.day().into(),
+            value.hour().into(),
+            value.minute().into(),
+            value.second().into(),
+        )
+    }
+}
+
+impl From<PrimitiveDateTime> for Tm {
+    fn from(value: PrimitiveDateTime) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Offset is taken from `gmtoff`, so on windows it is always UTC
+impl TryFrom<&Tm> for OffsetDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: &Tm) -> Result<Self, Self::Error> {
+        let offset = value.utc_offset();
+        let offset = i32::try_from(offset)
+            .ok()
+            .and_then(|offset| UtcOffset::from_whole_seconds(offset).ok())
+            .ok_or(DateConversionError::InvalidOffset(offset))?;
+        Ok(PrimitiveDateTime::try_from(value)?.assume_offset(offset))
+    }
+}
+
+impl TryFrom<Tm> for OffsetDateTime {
+    type Error = DateConversionError;
+
+    fn try_from(value: Tm) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// Offset is stored in `gmtoff`, so on windows it is lost
+impl From<&OffsetDateTime> for Tm {
+    fn from(value: &OffsetDateTime) -> Self {
+        let mut tm =
+            Tm::from(PrimitiveDateTime::new(value.date(), value.time()));
+        tm.set_utc_offset(value.offset().whole_seconds().into());
+        tm
+    }
+}
+
+impl From<OffsetDateTime> for Tm {
+    fn from(value: OffsetDateTime) -> Self {
+        Self::from(&value)
+    }
+}
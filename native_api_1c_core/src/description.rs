@@ -0,0 +1,72 @@
+use crate::interface::ParamValue;
+
+/// Type of value, passed between 1C and AddIn, as 1C sees it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    Bool,
+    /// Integer or float number, also handles
+    Number,
+    /// String, also JSON
+    String,
+    Date,
+    /// `BinaryData`
+    Blob,
+    /// Value of any type, e.g. of `raw` function
+    Any,
+}
+
+/// Parameter of AddIn method
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamDescription {
+    /// Name of Rust argument, if it's known
+    pub name: Option<&'static str>,
+    pub ty: ValueType,
+    /// Default value, set with `#[arg(default = ...)]`
+    pub default: Option<ParamValue>,
+    /// Parameter may be omitted, e.g. values of variadic argument
+    pub optional: bool,
+    /// Value is written back to 1C, `as_out` or `as_inout`
+    pub by_ref: bool,
+}
+
+/// Method of AddIn
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodDescription {
+    pub name: &'static str,
+    pub name_ru: &'static str,
+    /// Doc comment of the method
+    pub doc: &'static str,
+    /// Parameters as seen by 1C, variadic argument takes up to `max` of them
+    pub params: Vec<ParamDescription>,
+    /// Type of returned value, `None` for procedures
+    pub returns: Option<ValueType>,
+}
+
+/// Property of AddIn
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropDescription {
+    pub name: &'static str,
+    pub name_ru: &'static str,
+    /// Doc comment of the property
+    pub doc: &'static str,
+    pub ty: ValueType,
+    pub readable: bool,
+    pub writable: bool,
+}
+
+/// Description of AddIn, as it's seen by 1C, e.g. to generate BSL module for
+/// it with `bsl::BslModule`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddInDescription {
+    /// Name of the AddIn, returned by `register_extension_as`
+    pub name: &'static str,
+    /// Doc comment of the AddIn
+    pub doc: &'static str,
+    pub props: Vec<PropDescription>,
+    pub methods: Vec<MethodDescription>,
+}
+
+/// AddIn, that can describe itself, implemented by `#[derive(AddIn)]`
+pub trait DescribeAddIn {
+    fn description() -> AddInDescription;
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use crate::{
+    ffi::connection::Connection,
+    interface::{AddInWrapper, AddInWrapperResult, ParamValue, ParamValues},
+};
+
+/// Body of a method, that returns a value
+pub type Function =
+    Box<dyn FnMut(&mut ParamValues) -> AddInWrapperResult<ParamValue>>;
+/// Body of a method, that doesn't return a value
+pub type Procedure = Box<dyn FnMut(&mut ParamValues) -> AddInWrapperResult<()>>;
+/// Getter of a property, property is readable if it's set
+pub type Getter = Box<dyn Fn() -> AddInWrapperResult<ParamValue>>;
+/// Setter of a property, property is writable if it's set
+pub type Setter = Box<dyn FnMut(ParamValue) -> AddInWrapperResult<()>>;
+
+enum MethodBody {
+    Function(Function),
+    Procedure(Procedure),
+}
+
+struct DynamicMethod {
+    names: [Vec<u16>; 2],
+    n_params: usize,
+    defaults: HashMap<usize, ParamValue>,
+    body: MethodBody,
+}
+
+struct DynamicProp {
+    names: [Vec<u16>; 2],
+    getter: Option<Getter>,
+    setter: Option<Setter>,
+}
+
+/// AddIn, which methods and properties are defined at runtime, e.g. loaded
+/// from a configuration file, when they can't be described with
+/// `#[derive(AddIn)]`. Created with `DynamicAddIn::builder`:
+/// ```
+/// use native_api_1c_core::{dynamic::DynamicAddIn, interface::ParamValue};
+///
+/// let add_in = DynamicAddIn::builder("Plugins")
+///     .method("Sum", "Сумма", 2, [(1, ParamValue::I32(1))], |params| {
+///         let a = params[0].as_i32().ok_or(())?;
+///         let b = params[1].as_i32().ok_or(())?;
+///         Ok(ParamValue::I32(a + b))
+///     })
+///     .property("Version", "Версия", Some(Box::new(|| Ok("1.0".into()))), None)
+///     .build();
+/// ```
+pub struct DynamicAddIn {
+    /// Name with null terminator, as returned by `register_extension_as`
+    name: Vec<u16>,
+    connection: Option<&'static Connection>,
+    methods: Vec<DynamicMethod>,
+    props: Vec<DynamicProp>,
+    /// Indices of methods by both names
+    method_indices: HashMap<Vec<u16>, usize>,
+    /// Indices of properties by both names
+    prop_indices: HashMap<Vec<u16>, usize>,
+}
+
+/// Builder of `DynamicAddIn`, methods and properties are numbered in the
+/// order they are added
+pub struct DynamicAddInBuilder {
+    name: String,
+    methods: Vec<DynamicMethod>,
+    props: Vec<DynamicProp>,
+}
+
+impl DynamicAddIn {
+    /// Starts building AddIn, registered in 1C with `name`
+    pub fn builder(name: &str) -> DynamicAddInBuilder {
+        DynamicAddInBuilder {
+            name: name.to_owned(),
+            methods: Vec::new(),
+            props: Vec::new(),
+        }
+    }
+
+    /// Connection to 1C, available after the AddIn is initialized
+    pub fn connection(&self) -> Option<&'static Connection> {
+        self.connection
+    }
+}
+
+impl DynamicAddInBuilder {
+    /// Adds method, that returns a value
+    /// # Arguments
+    /// * `name` - name of the method in English
+    /// * `name_ru` - name of the method in Russian
+    /// * `n_params` - number of parameters
+    /// * `defaults` - default values of parameters by their indices
+    /// * `function` - body of the method
+    pub fn method<F>(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        n_params: usize,
+        defaults: impl IntoIterator<Item = (usize, ParamValue)>,
+        function: F,
+    ) -> Self
+    where
+        F: FnMut(&mut ParamValues) -> AddInWrapperResult<ParamValue> + 'static,
+    {
+        self.methods.push(DynamicMethod {
+            names: names(name, name_ru),
+            n_params,
+            defaults: defaults.into_iter().collect(),
+            body: MethodBody::Function(Box::new(function)),
+        });
+        self
+    }
+
+    /// Adds method, that doesn't return a value, arguments are the same as
+    /// for `method`
+    pub fn procedure<F>(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        n_params: usize,
+        defaults: impl IntoIterator<Item = (usize, ParamValue)>,
+        procedure: F,
+    ) -> Self
+    where
+        F: FnMut(&mut ParamValues) -> AddInWrapperResult<()> + 'static,
+    {
+        self.methods.push(DynamicMethod {
+            names: names(name, name_ru),
+            n_params,
+            defaults: defaults.into_iter().collect(),
+            body: MethodBody::Procedure(Box::new(procedure)),
+        });
+        self
+    }
+
+    /// Adds property, readable if `getter` is set and writable if `setter`
+    /// is set
+    pub fn property(
+        mut self,
+        name: &str,
+        name_ru: &str,
+        getter: Option<Getter>,
+        setter: Option<Setter>,
+    ) -> Self {
+        self.props.push(DynamicProp {
+            names: names(name, name_ru),
+            getter,
+            setter,
+        });
+        self
+    }
+
+    /// Builds lookup tables of methods and properties. If several of them
+    /// share a name, the first one is found by it
+    pub fn build(self) -> DynamicAddIn {
+        let method_indices = indices(self.methods.iter().map(|m| &m.names));
+        let prop_indices = indices(self.props.iter().map(|p| &p.names));
+        DynamicAddIn {
+            name: self.name.encode_utf16().chain(Some(0)).collect(),
+            connection: None,
+            methods: self.methods,
+            props: self.props,
+            method_indices,
+            prop_indices,
+        }
+    }
+}
+
+fn names(name: &str, name_ru: &str) -> [Vec<u16>; 2] {
+    [
+        name.encode_utf16().collect(),
+        name_ru.encode_utf16().collect(),
+    ]
+}
+
+fn indices<'a>(
+    names: impl Iterator<Item = &'a [Vec<u16>; 2]>,
+) -> HashMap<Vec<u16>, usize> {
+    let mut indices = HashMap::new();
+    for (index, names) in names.enumerate() {
+        for name in names {
+            indices.entry(name.clone()).or_insert(index);
+        }
+    }
+    indices
+}
+
+/// Name, passed by 1C, without null terminator
+fn trim_nul(name: &[u16]) -> &[u16] {
+    name.strip_suffix(&[0]).unwrap_or(name)
+}
+
+/// Name with null terminator, English for alias 0 and Russian otherwise
+fn alias_name(names: &[Vec<u16>; 2], alias: usize) -> Vec<u16> {
+    let name = if alias == 0 { &names[0] } else { &names[1] };
+    name.iter().copied().chain(Some(0)).collect()
+}
+
+impl AddInWrapper for DynamicAddIn {
+    fn init(&mut self, interface: &'static Connection) -> bool {
+        self.connection = Some(interface);
+        true
+    }
+
+    fn done(&mut self) {}
+
+    fn register_extension_as(&mut self) -> &[u16] {
+        &self.name
+    }
+
+    fn get_n_props(&self) -> usize {
+        self.props.len()
+    }
+
+    fn find_prop(&self, name: &[u16]) -> Option<usize> {
+        self.prop_indices.get(trim_nul(name)).copied()
+    }
+
+    fn get_prop_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        Some(alias_name(&self.props.get(num)?.names, alias))
+    }
+
+    fn get_prop_val(&self, num: usize) -> AddInWrapperResult<ParamValue> {
+        let getter = self.props.get(num).and_then(|p| p.getter.as_ref());
+        getter.ok_or(())?()
+    }
+
+    fn set_prop_val(
+        &mut self,
+        num: usize,
+        val: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        let setter = self.props.get_mut(num).and_then(|p| p.setter.as_mut());
+        setter.ok_or(())?(val)
+    }
+
+    fn is_prop_readable(&self, num: usize) -> bool {
+        self.props.get(num).is_some_and(|p| p.getter.is_some())
+    }
+
+    fn is_prop_writable(&self, num: usize) -> bool {
+        self.props.get(num).is_some_and(|p| p.setter.is_some())
+    }
+
+    fn get_n_methods(&self) -> usize {
+        self.methods.len()
+    }
+
+    fn find_method(&self, name: &[u16]) -> Option<usize> {
+        self.method_indices.get(trim_nul(name)).copied()
+    }
+
+    fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+        Some(alias_name(&self.methods.get(num)?.names, alias))
+    }
+
+    fn get_n_params(&self, num: usize) -> usize {
+        self.methods.get(num).map_or(0, |m| m.n_params)
+    }
+
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        param_num: usize,
+    ) -> Option<ParamValue> {
+        self.methods
+            .get(method_num)?
+            .defaults
+            .get(&param_num)
+            .cloned()
+    }
+
+    fn has_ret_val(&self, method_num: usize) -> bool {
+        self.methods
+            .get(method_num)
+            .is_some_and(|m| matches!(m.body, MethodBody::Function(_)))
+    }
+
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        match &mut self.methods.get_mut(method_num).ok_or(())?.body {
+            MethodBody::Function(function) => function(params).map(|_| ()),
+            MethodBody::Procedure(procedure) => procedure(params),
+        }
+    }
+
+    fn call_as_func(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        match &mut self.methods.get_mut(method_num).ok_or(())?.body {
+            MethodBody::Function(function) => function(params),
+            MethodBody::Procedure(_) => Err(()),
+        }
+    }
+
+    fn set_locale(&mut self, _loc: &[u16]) {}
+
+    fn set_user_interface_language_code(&mut self, _lang: &[u16]) {}
+}
@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use crate::{
+    ffi::connection::{Connection, MessageCode},
+    localization::{Message, MessageCatalog, UiLanguage},
+};
+
+/// Destination of an error, returned by AddIn method or property setter
+pub struct ErrorReport<'a> {
+    /// Connection to 1C platform, nothing is reported if it's `None`
+    pub connection: Option<&'a Connection>,
+    /// Source of the error, e.g. name of the method or property
+    pub source: &'a str,
+    /// Interface language of the AddIn, see `AddInWrapper::ui_language` and
+    /// `UiLanguage::with_current`
+    pub language: Option<&'a UiLanguage>,
+    /// Catalog, that translates `Message` errors, they are reported as their
+    /// keys if it's `None`
+    pub catalog: Option<&'a MessageCatalog>,
+}
+
+impl ErrorReport<'_> {
+    /// Reports the text to 1C platform, so that it's shown to the user as
+    /// exception text
+    pub fn text(&self, text: &str) {
+        if let Some(connection) = self.connection {
+            connection.add_error(MessageCode::Fail, self.source, text);
+        }
+    }
+}
+
+/// Reference to an error, returned by AddIn method or property setter, that
+/// lets `#[derive(AddIn)]` choose how the error is reported by its type.
+/// Generated code calls `report` on `&&&ErrorRef`, so that `ReportMessage` is
+/// used for `Message`, `ReportDisplay` for other errors, that implement
+/// `Display`, and `ReportOpaque` for the rest, e.g. `()`
+pub struct ErrorRef<'a, E>(pub &'a E);
+
+/// Reports `Message` with its text from the catalog in the interface language
+pub trait ReportMessage {
+    fn report(&self, report: &ErrorReport);
+}
+
+impl ReportMessage for &&ErrorRef<'_, Message> {
+    fn report(&self, report: &ErrorReport) {
+        match report.catalog {
+            Some(catalog) => {
+                // default language of the catalog, until 1C sets one
+                let unknown = UiLanguage::new();
+                let language = report.language.unwrap_or(&unknown);
+                report.text(&catalog.text(language, self.0))
+            }
+            None => report.text(self.0.key()),
+        }
+    }
+}
+
+/// Errors of JSON conversion are translated, if the catalog has their
+/// messages, see `JsonError::message`, and are reported in English otherwise
+#[cfg(feature = "serde")]
+impl ReportMessage for &&ErrorRef<'_, crate::json::JsonError> {
+    fn report(&self, report: &ErrorReport) {
+        let message = self.0.message();
+        let unknown = UiLanguage::new();
+        let language = report.language.unwrap_or(&unknown);
+        match report.catalog {
+            Some(catalog) if catalog.get(language, message.key()).is_some() => {
+                report.text(&catalog.text(language, &message))
+            }
+            _ => report.text(&self.0.to_string()),
+        }
+    }
+}
+
+/// Reports error with its `Display` text
+pub trait ReportDisplay {
+    fn report(&self, report: &ErrorReport);
+}
+
+impl<E: Display> ReportDisplay for &ErrorRef<'_, E> {
+    fn report(&self, report: &ErrorReport) {
+        report.text(&self.0.to_string())
+    }
+}
+
+/// Error without text, 1C only gets a failed operation
+pub trait ReportOpaque {
+    fn report(&self, report: &ErrorReport);
+}
+
+impl<E> ReportOpaque for ErrorRef<'_, E> {
+    fn report(&self, _: &ErrorReport) {}
+}
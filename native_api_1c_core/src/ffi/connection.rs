@@ -1,6 +1,6 @@
 use std::ffi::{c_long, c_ushort};
 
-use super::{provided_types::TVariant, string_utils::os_string_nil};
+use super::{provided_types::TVariant, string_utils::os_string};
 
 /// Message codes that can be used in `Connection::add_error` method
 /// to specify message type.
@@ -89,8 +89,8 @@ impl Connection {
         description: &str,
     ) -> bool {
         unsafe {
-            let source_wstr = os_string_nil(source);
-            let description_wstr = os_string_nil(description);
+            let source_wstr = wstr(source);
+            let description_wstr = wstr(description);
             (self.vptr1.add_error)(
                 self,
                 code as u16,
@@ -110,9 +110,9 @@ impl Connection {
     /// `bool` - operation success status
     pub fn external_event(&self, caller: &str, name: &str, data: &str) -> bool {
         unsafe {
-            let mut caller_wstr = os_string_nil(caller);
-            let mut name_wstr = os_string_nil(name);
-            let mut data_wstr = os_string_nil(data);
+            let mut caller_wstr = wstr(caller);
+            let mut name_wstr = wstr(name);
+            let mut data_wstr = wstr(data);
             (self.vptr1.external_event)(
                 self,
                 caller_wstr.as_mut_ptr(),
@@ -136,3 +136,9 @@ impl Connection {
         unsafe { (self.vptr1.get_event_buffer_depth)(self) }
     }
 }
+
+/// String, passed to 1C, with null terminator on every platform, unlike
+/// `os_string_nil`, which keeps it only on Windows
+fn wstr(s: &str) -> Vec<u16> {
+    os_string(s).into_iter().chain(Some(0)).collect()
+}
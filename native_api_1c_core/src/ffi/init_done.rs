@@ -10,28 +10,33 @@ pub struct InitDoneBaseVTable<T: AddInWrapper> {
     dtor: usize,
     #[cfg(target_family = "unix")]
     dtor2: usize,
-    init: unsafe extern "system" fn(&mut This<T>, &'static Connection) -> bool,
+    init: unsafe extern "system" fn(&mut This<T>, *const Connection) -> bool,
     set_mem_manager:
-        unsafe extern "system" fn(&mut This<T>, &'static MemoryManager) -> bool,
+        unsafe extern "system" fn(&mut This<T>, *const MemoryManager) -> bool,
     get_info: unsafe extern "system" fn(&mut This<T>) -> c_long,
     done: unsafe extern "system" fn(&mut This<T>),
 }
 
 unsafe extern "system" fn init<T: AddInWrapper>(
     this: &mut This<T>,
-    interface: &'static Connection,
+    interface: *const Connection,
 ) -> bool {
     let component = this.get_component();
-    component.addin.init(interface)
+    match interface.as_ref() {
+        Some(interface) => component.addin.init(interface),
+        None => false,
+    }
 }
 
+/// Hosts of Native API 1.0 may not call it or pass null, then the AddIn in
+/// `ProtocolMode::V1` allocates memory itself
 unsafe extern "system" fn set_mem_manager<T: AddInWrapper>(
     this: &mut This<T>,
-    mem: &'static MemoryManager,
+    mem: *const MemoryManager,
 ) -> bool {
     let component = this.get_component();
-    component.memory_manager_ptr = Some(mem);
-    true
+    component.memory_manager_ptr = mem.as_ref();
+    component.memory_manager().is_some()
 }
 
 unsafe extern "system" fn get_info<T: AddInWrapper>(
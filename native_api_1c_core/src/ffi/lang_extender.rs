@@ -1,7 +1,13 @@
-use super::{get_str, offset, provided_types::TVariant};
-use crate::interface::{AddInWrapper, ParamValue, ParamValues};
+use super::{
+    get_str,
+    memory_manager::{LocalBlocks, MemoryManager, PlatformString},
+    offset,
+    provided_types::{TVariant, VariantType},
+    Component, LocalDefault,
+};
+use crate::interface::{AddInWrapper, ParamValue, ParamValues, ProtocolMode};
 use std::{
-    ffi::c_long,
+    ffi::{c_long, c_void},
     ptr::{self},
     slice::from_raw_parts_mut,
 };
@@ -57,21 +63,19 @@ unsafe extern "system" fn register_extension_as<T: AddInWrapper>(
     name: *mut *mut u16,
 ) -> bool {
     let component = this.get_component();
-    let Some(allocator) = component.memory_manager_ptr else {
+    let Some(allocator) = component.memory_manager() else {
         return false;
     };
 
     let extension_name = component.addin.register_extension_as();
 
-    let Ok(ptr) = allocator.alloc_str(extension_name.len()) else {
+    let Ok(extension_name) =
+        PlatformString::from_slice_in(allocator, extension_name)
+    else {
         return false;
     };
-    ptr::copy_nonoverlapping(
-        extension_name.as_ptr(),
-        ptr.as_ptr(),
-        extension_name.len(),
-    );
-    *name = ptr.as_ptr();
+    *name = extension_name.into_raw().0;
+    component.hand_over((*name).cast());
 
     true
 }
@@ -101,7 +105,7 @@ unsafe extern "system" fn get_prop_name<T: AddInWrapper>(
     alias: c_long,
 ) -> *const u16 {
     let component = this.get_component();
-    let Some(allocator) = component.memory_manager_ptr else {
+    let Some(allocator) = component.memory_manager() else {
         return ptr::null();
     };
     let Some(prop_name) =
@@ -109,12 +113,14 @@ unsafe extern "system" fn get_prop_name<T: AddInWrapper>(
     else {
         return ptr::null();
     };
-    let Ok(ptr) = allocator.alloc_str(prop_name.len()) else {
+    let Ok(prop_name) = PlatformString::from_slice_in(allocator, &prop_name)
+    else {
         return ptr::null();
     };
-    ptr::copy_nonoverlapping(prop_name.as_ptr(), ptr.as_ptr(), prop_name.len());
 
-    ptr.as_ptr()
+    let prop_name = prop_name.into_raw().0;
+    component.hand_over(prop_name.cast());
+    prop_name
 }
 
 unsafe extern "system" fn get_prop_val<T: AddInWrapper>(
@@ -123,16 +129,16 @@ unsafe extern "system" fn get_prop_val<T: AddInWrapper>(
     val: &mut TVariant,
 ) -> bool {
     let component = this.get_component();
-    let Some(mem_mngr) = component.memory_manager_ptr else {
+    let Some(mem_mngr) = component.memory_manager() else {
         return false;
     };
 
-    let prop_val_result = component.addin.get_prop_val(num as usize);
+    let _consumed = std::mem::take(&mut component.handed_over);
+    let _scope = mem_mngr.enter();
+    let prop_val_result =
+        component.with_language(|addin| addin.get_prop_val(num as usize));
     match prop_val_result {
-        Ok(prop_val) => {
-            val.update_from_return(mem_mngr, &prop_val);
-            true
-        }
+        Ok(prop_val) => write_result(component, val, mem_mngr, prop_val),
         Err(_) => false,
     }
 }
@@ -142,9 +148,10 @@ unsafe extern "system" fn set_prop_val<T: AddInWrapper>(
     num: c_long,
     val: &TVariant,
 ) -> bool {
-    this.get_component()
-        .addin
-        .set_prop_val(num as usize, val.into())
+    let component = this.get_component();
+    let _consumed = std::mem::take(&mut component.handed_over);
+    component
+        .with_language(|addin| addin.set_prop_val(num as usize, val.into()))
         .is_ok()
 }
 
@@ -188,7 +195,7 @@ unsafe extern "system" fn get_method_name<T: AddInWrapper>(
     alias: c_long,
 ) -> *const u16 {
     let component = this.get_component();
-    let Some(allocator) = component.memory_manager_ptr else {
+    let Some(allocator) = component.memory_manager() else {
         return ptr::null();
     };
     let Some(method_name) = component
@@ -197,17 +204,15 @@ unsafe extern "system" fn get_method_name<T: AddInWrapper>(
     else {
         return ptr::null();
     };
-    let Ok(ptr) = allocator.alloc_str(method_name.len()) else {
+    let Ok(method_name) =
+        PlatformString::from_slice_in(allocator, &method_name)
+    else {
         return ptr::null();
     };
 
-    ptr::copy_nonoverlapping(
-        method_name.as_ptr(),
-        ptr.as_ptr(),
-        method_name.len(),
-    );
-
-    ptr.as_ptr()
+    let method_name = method_name.into_raw().0;
+    component.hand_over(method_name.cast());
+    method_name
 }
 
 unsafe extern "system" fn get_n_params<T: AddInWrapper>(
@@ -225,20 +230,46 @@ unsafe extern "system" fn get_param_def_value<T: AddInWrapper>(
     val: &mut TVariant,
 ) -> bool {
     let component = this.get_component();
-    let Some(mem) = component.memory_manager_ptr else {
+    let Some(mem) = component.memory_manager() else {
         return false;
     };
 
     let def_value_result = component
         .addin
         .get_param_def_value(method_num as usize, param_num as usize);
-    match def_value_result {
-        Some(def_value) => {
-            val.update_from_return(mem, &def_value);
-            true
+    let Some(def_value) = def_value_result else {
+        return false;
+    };
+    if component.memory_manager_ptr.is_some() {
+        let mode = component.addin.protocol_mode();
+        return write_value(val, mem, def_value, mode);
+    }
+
+    let key = (method_num as usize, param_num as usize);
+    if let Some(cached) = component.local_defaults.get(&key) {
+        if cached.value == def_value {
+            // variant is plain data, its memory stays owned by the cache
+            *val = ptr::read(&cached.variant);
+            return true;
         }
-        None => false,
     }
+    let value = def_value.clone();
+    let mode = component.addin.protocol_mode();
+    if !write_value(val, mem, def_value, mode) {
+        return false;
+    }
+    let mut block = LocalBlocks::default();
+    block.push(owned_block(val));
+    let cached = LocalDefault {
+        value,
+        variant: ptr::read(val),
+        block,
+    };
+    // the host may still hold the previous value
+    if let Some(previous) = component.local_defaults.insert(key, cached) {
+        component.handed_over.append(previous.block);
+    }
+    true
 }
 
 unsafe extern "system" fn has_ret_val<T: AddInWrapper>(
@@ -256,24 +287,28 @@ unsafe extern "system" fn call_as_proc<T: AddInWrapper>(
     size_array: c_long,
 ) -> bool {
     let component = this.get_component();
-    let Some(mem_mngr) = component.memory_manager_ptr else {
+    let Some(mem_mngr) = component.memory_manager() else {
         return false;
     };
 
     let parameters_raw = from_raw_parts_mut(params, size_array as usize);
-    let mut parameters_values =
-        ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
+    let mut parameters_values = ParamValues::from_variants(parameters_raw);
 
-    let call_result = component
-        .addin
-        .call_as_proc(method_num as usize, &mut parameters_values);
+    let _consumed = std::mem::take(&mut component.handed_over);
+    let _scope = mem_mngr.enter();
+    let call_result = component.with_language(|addin| {
+        addin.call_as_proc(method_num as usize, &mut parameters_values)
+    });
 
     if call_result.is_err() {
         return false;
     }
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
+    for (i, param) in parameters_values.into_changed() {
+        let variant = &mut parameters_raw[i];
+        if !write_result(component, variant, mem_mngr, param) {
+            return false;
+        }
     }
 
     true
@@ -287,31 +322,83 @@ unsafe extern "system" fn call_as_func<T: AddInWrapper>(
     size_array: c_long,
 ) -> bool {
     let component = this.get_component();
-    let Some(mem_mngr) = component.memory_manager_ptr else {
+    let Some(mem_mngr) = component.memory_manager() else {
         return false;
     };
 
     let parameters_raw = from_raw_parts_mut(params, size_array as usize);
-    let mut parameters_values =
-        ParamValues::new(parameters_raw.iter().map(ParamValue::from).collect());
+    let mut parameters_values = ParamValues::from_variants(parameters_raw);
 
-    let call_result = component
-        .addin
-        .call_as_func(method_num as usize, &mut parameters_values);
+    let _consumed = std::mem::take(&mut component.handed_over);
+    let _scope = mem_mngr.enter();
+    let call_result = component.with_language(|addin| {
+        addin.call_as_func(method_num as usize, &mut parameters_values)
+    });
 
     let Ok(ret_val) = call_result else {
         return false;
     };
 
-    ret_value.update_from_return(mem_mngr, &ret_val);
+    if !write_result(component, ret_value, mem_mngr, ret_val) {
+        return false;
+    }
+
+    for (i, param) in parameters_values.into_changed() {
+        let variant = &mut parameters_raw[i];
+        if !write_result(component, variant, mem_mngr, param) {
+            return false;
+        }
+    }
+
+    true
+}
 
-    for (i, param) in parameters_values.iter().enumerate() {
-        parameters_raw[i].update_from_return(mem_mngr, param);
+/// Writes value into the variant, provided by the host, in the format of its
+/// protocol, `false` if memory could not be allocated
+fn write_value(
+    variant: &mut TVariant,
+    mem_mngr: &'static MemoryManager,
+    value: ParamValue,
+    mode: ProtocolMode,
+) -> bool {
+    match (mode, value) {
+        (ProtocolMode::V1, ParamValue::String(val)) => {
+            // memory of the previous value belongs to the host
+            variant.vt = VariantType::Empty;
+            unsafe { variant.update_to_pstr(mem_mngr, &val) }.is_ok()
+        }
+        (_, value) => variant.update_platform_owned(mem_mngr, value).is_ok(),
     }
+}
 
+/// Writes result of the call with `write_value`. Memory, allocated by the
+/// AddIn itself, is kept until the next call, by which the host has consumed
+/// the result
+fn write_result<T: AddInWrapper>(
+    component: &mut Component<T>,
+    variant: &mut TVariant,
+    mem_mngr: &'static MemoryManager,
+    value: ParamValue,
+) -> bool {
+    let mode = component.addin.protocol_mode();
+    if !write_value(variant, mem_mngr, value, mode) {
+        return false;
+    }
+    component.hand_over(owned_block(variant));
     true
 }
 
+/// Memory of string or blob, written into the variant
+fn owned_block(variant: &TVariant) -> *mut c_void {
+    match variant.vt {
+        VariantType::WStr => unsafe { variant.value.data_str.ptr.cast() },
+        VariantType::Blob | VariantType::PStr => unsafe {
+            variant.value.data_blob.ptr.cast()
+        },
+        _ => ptr::null_mut(),
+    }
+}
+
 impl<T: AddInWrapper> Default for LanguageExtenderBaseVTable<T> {
     fn default() -> Self {
         Self {
@@ -29,5 +29,6 @@ unsafe extern "system" fn set_locale<T: AddInWrapper>(
 ) {
     let component = this.get_component();
     let loc = get_str(loc);
+    component.language.set_locale(loc);
     component.addin.set_locale(loc)
 }
@@ -1,6 +1,12 @@
 use std::{
+    alloc::{alloc, dealloc, Layout},
+    cell::Cell,
     ffi::{c_ulong, c_void},
+    fmt::{Debug, Display},
+    ops::{Deref, DerefMut},
     ptr::{self, NonNull},
+    slice::{from_raw_parts, from_raw_parts_mut},
+    thread::{self, ThreadId},
 };
 
 /// VTable for MemoryManager object, derived from Native API interface. See original
@@ -25,8 +31,113 @@ pub struct MemoryManager {
     vptr: &'static MemoryManagerVTable,
 }
 
+/// MemoryManager, implemented by the AddIn itself for hosts of Native API
+/// 1.0, that don't provide one. It keeps no state: size of each block is
+/// stored in front of it, so memory may be freed after the component is
+/// destroyed, e.g. by `PlatformBox`, kept in a static
+pub(crate) static LOCAL_MEMORY_MANAGER: MemoryManager = MemoryManager {
+    vptr: &LOCAL_VTABLE,
+};
+
+/// Size of the header, that stores size of the block, and the alignment of
+/// the memory, returned by `LOCAL_MEMORY_MANAGER`
+const LOCAL_HEADER: usize = 16;
+
+static LOCAL_VTABLE: MemoryManagerVTable = MemoryManagerVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    alloc_memory: local_alloc_memory,
+    free_memory: local_free_memory,
+};
+
+fn local_layout(size: usize) -> Option<Layout> {
+    let size = size.checked_add(LOCAL_HEADER)?;
+    Layout::from_size_align(size, LOCAL_HEADER).ok()
+}
+
+unsafe extern "system" fn local_alloc_memory(
+    _: &MemoryManager,
+    ptr: *mut *mut c_void,
+    size: c_ulong,
+) -> bool {
+    let Some(layout) = local_layout(size as usize) else {
+        return false;
+    };
+    let block = alloc(layout);
+    if block.is_null() {
+        return false;
+    }
+    block.cast::<usize>().write(size as usize);
+    *ptr = block.add(LOCAL_HEADER).cast();
+    true
+}
+
+unsafe extern "system" fn local_free_memory(
+    _: &MemoryManager,
+    ptr: *mut *mut c_void,
+) {
+    let data = (*ptr).cast::<u8>();
+    if !data.is_null() {
+        let block = data.sub(LOCAL_HEADER);
+        let size = block.cast::<usize>().read();
+        if let Some(layout) = local_layout(size) {
+            dealloc(block, layout);
+        }
+    }
+    *ptr = ptr::null_mut();
+}
+
+/// Blocks of `LOCAL_MEMORY_MANAGER`, handed over to the host, which doesn't
+/// free them, freed on drop
+#[derive(Default)]
+pub(crate) struct LocalBlocks(Vec<NonNull<c_void>>);
+
+impl LocalBlocks {
+    pub fn push(&mut self, block: *mut c_void) {
+        self.0.extend(NonNull::new(block));
+    }
+
+    pub fn append(&mut self, mut other: Self) {
+        self.0.append(&mut other.0);
+    }
+}
+
+impl Drop for LocalBlocks {
+    fn drop(&mut self) {
+        for block in self.0.drain(..) {
+            LOCAL_MEMORY_MANAGER.free_memory(&mut block.as_ptr());
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct AllocationError;
 
+impl Display for AllocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unable to allocate memory with 1C memory manager")
+    }
+}
+
+impl std::error::Error for AllocationError {}
+
+thread_local! {
+    static CURRENT: Cell<Option<&'static MemoryManager>> =
+        const { Cell::new(None) };
+}
+
+/// Makes MemoryManager available with `MemoryManager::current` until dropped
+pub(crate) struct MemoryManagerScope {
+    previous: Option<&'static MemoryManager>,
+}
+
+impl Drop for MemoryManagerScope {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
 impl MemoryManager {
     /// Safe wrapper around `alloc_memory` method of the MemoryManager object
     /// to allocate memory for byte array
@@ -38,17 +149,7 @@ impl MemoryManager {
         &self,
         size: usize,
     ) -> Result<NonNull<u8>, AllocationError> {
-        let mut ptr = ptr::null_mut::<c_void>();
-        unsafe {
-            if (self.vptr.alloc_memory)(self, &mut ptr, size as c_ulong * 2) {
-                match NonNull::new(ptr as *mut u8) {
-                    Some(ptr) => Ok(ptr),
-                    None => Err(AllocationError),
-                }
-            } else {
-                Err(AllocationError)
-            }
-        }
+        self.alloc(size)
     }
 
     /// Safe wrapper around `alloc_memory` method of the MemoryManager object
@@ -61,13 +162,19 @@ impl MemoryManager {
         &self,
         size: usize,
     ) -> Result<NonNull<u16>, AllocationError> {
+        self.alloc(size)
+    }
+
+    /// Allocates memory for `len` values of `T`
+    fn alloc<T>(&self, len: usize) -> Result<NonNull<T>, AllocationError> {
+        let size = len
+            .checked_mul(size_of::<T>())
+            .and_then(|size| c_ulong::try_from(size).ok())
+            .ok_or(AllocationError)?;
         let mut ptr = ptr::null_mut::<c_void>();
         unsafe {
-            if (self.vptr.alloc_memory)(self, &mut ptr, size as c_ulong * 2) {
-                match NonNull::new(ptr as *mut u16) {
-                    Some(ptr) => Ok(ptr),
-                    None => Err(AllocationError),
-                }
+            if (self.vptr.alloc_memory)(self, &mut ptr, size) {
+                NonNull::new(ptr.cast::<T>()).ok_or(AllocationError)
             } else {
                 Err(AllocationError)
             }
@@ -79,4 +186,207 @@ impl MemoryManager {
             (self.vptr.free_memory)(self, ptr);
         }
     }
+
+    /// MemoryManager of the component, which method is being called on the
+    /// current thread, `None` outside of calls from 1C
+    pub fn current() -> Option<&'static MemoryManager> {
+        CURRENT.with(|current| current.get())
+    }
+
+    /// Makes `self` current for the duration of the call from 1C
+    pub(crate) fn enter(&'static self) -> MemoryManagerScope {
+        let previous = CURRENT.with(|current| current.replace(Some(self)));
+        MemoryManagerScope { previous }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+}
+
+/// Types of values, that can be stored in `PlatformBox`: plain data, for
+/// which zeroed memory is a valid value
+pub trait PlatformData: Copy + private::Sealed {}
+
+impl PlatformData for u8 {}
+impl PlatformData for u16 {}
+
+/// Array, allocated with 1C MemoryManager, so it can be passed to 1C without
+/// copying. Memory is freed on drop, unless ownership is transferred to 1C
+/// with `into_raw`, e.g. by returning `PlatformBlob` from the method.
+///
+/// Interfaces of 1C may only be used on the thread, that calls the AddIn, so
+/// memory is only freed if the box is dropped on the thread, that allocated
+/// it, and is leaked otherwise
+pub struct PlatformBox<T: PlatformData> {
+    /// `None` for empty array, as 1C may return null for empty allocation
+    ptr: Option<NonNull<T>>,
+    len: usize,
+    mem: &'static MemoryManager,
+    /// Thread, on which `mem` may be used to free the memory
+    thread: ThreadId,
+}
+
+// SAFETY: the box exclusively owns plain data, like `Box<[T]>`, and
+// MemoryManager is only used by `drop` on the thread, that allocated the
+// memory, so the box can be moved to and read from other threads
+unsafe impl<T: PlatformData + Send> Send for PlatformBox<T> {}
+unsafe impl<T: PlatformData + Sync> Sync for PlatformBox<T> {}
+
+/// Byte array, passed to 1C as `BinaryData`
+pub type PlatformBlob = PlatformBox<u8>;
+/// UTF-16 string, passed to 1C as `String`
+pub type PlatformString = PlatformBox<u16>;
+
+impl<T: PlatformData> PlatformBox<T> {
+    /// Allocates zeroed array of `len` values with the current MemoryManager,
+    /// so it's only available inside of methods, called by 1C
+    pub fn new(len: usize) -> Result<Self, AllocationError> {
+        let mem = MemoryManager::current().ok_or(AllocationError)?;
+        Self::new_in(mem, len)
+    }
+
+    /// Allocates zeroed array of `len` values with the given MemoryManager
+    pub fn new_in(
+        mem: &'static MemoryManager,
+        len: usize,
+    ) -> Result<Self, AllocationError> {
+        let mut boxed = Self::alloc_in(mem, len)?;
+        if let Some(ptr) = boxed.ptr {
+            unsafe { ptr::write_bytes(ptr.as_ptr(), 0, len) };
+        }
+        boxed.len = len;
+        Ok(boxed)
+    }
+
+    /// Allocates array with the current MemoryManager and copies `data` into it
+    pub fn from_slice(data: &[T]) -> Result<Self, AllocationError> {
+        let mem = MemoryManager::current().ok_or(AllocationError)?;
+        Self::from_slice_in(mem, data)
+    }
+
+    /// Allocates array with the given MemoryManager and copies `data` into it
+    pub fn from_slice_in(
+        mem: &'static MemoryManager,
+        data: &[T],
+    ) -> Result<Self, AllocationError> {
+        let mut boxed = Self::alloc_in(mem, data.len())?;
+        if let Some(ptr) = boxed.ptr {
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    data.as_ptr(),
+                    ptr.as_ptr(),
+                    data.len(),
+                )
+            };
+        }
+        boxed.len = data.len();
+        Ok(boxed)
+    }
+
+    /// Takes ownership of the memory, allocated with `mem`, e.g. the one
+    /// previously returned by `into_raw`
+    /// # Safety
+    /// `ptr` must be null or point to `len` values, allocated with `mem` and
+    /// not owned by anything else
+    pub unsafe fn from_raw(
+        mem: &'static MemoryManager,
+        ptr: *mut T,
+        len: usize,
+    ) -> Self {
+        let ptr = NonNull::new(ptr);
+        let len = if ptr.is_some() { len } else { 0 };
+        let thread = thread::current().id();
+        Self {
+            ptr,
+            len,
+            mem,
+            thread,
+        }
+    }
+
+    /// Transfers ownership of the memory to the caller, pointer is null for
+    /// empty array
+    pub fn into_raw(self) -> (*mut T, usize) {
+        let raw = match self.ptr {
+            Some(ptr) => (ptr.as_ptr(), self.len),
+            None => (ptr::null_mut(), 0),
+        };
+        std::mem::forget(self);
+        raw
+    }
+
+    /// Allocates memory for `len` values, leaving it uninitialized, so the
+    /// length is 0 until it's written
+    fn alloc_in(
+        mem: &'static MemoryManager,
+        len: usize,
+    ) -> Result<Self, AllocationError> {
+        let ptr = if len == 0 {
+            None
+        } else {
+            Some(mem.alloc::<T>(len)?)
+        };
+        Ok(Self {
+            ptr,
+            len: 0,
+            mem,
+            thread: thread::current().id(),
+        })
+    }
+}
+
+impl<T: PlatformData> Drop for PlatformBox<T> {
+    fn drop(&mut self) {
+        if thread::current().id() != self.thread {
+            // MemoryManager can't be used here, so the memory is leaked
+            return;
+        }
+        if let Some(ptr) = self.ptr {
+            self.mem.free_memory(&mut ptr.as_ptr().cast::<c_void>());
+        }
+    }
+}
+
+impl<T: PlatformData> Deref for PlatformBox<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        match self.ptr {
+            Some(ptr) => unsafe { from_raw_parts(ptr.as_ptr(), self.len) },
+            None => &[],
+        }
+    }
+}
+
+impl<T: PlatformData> DerefMut for PlatformBox<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self.ptr {
+            Some(ptr) => unsafe { from_raw_parts_mut(ptr.as_ptr(), self.len) },
+            None => &mut [],
+        }
+    }
+}
+
+impl<T: PlatformData> AsRef<[T]> for PlatformBox<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: PlatformData> AsMut<[T]> for PlatformBox<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: PlatformData> Debug for PlatformBox<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlatformBox")
+            .field("len", &self.len)
+            .finish()
+    }
 }
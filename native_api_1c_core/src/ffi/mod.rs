@@ -3,16 +3,24 @@
 //! submodules according to what C++ class they originate from
 //!
 use std::{
+    collections::HashMap,
     ffi::{c_long, c_void},
     ptr,
 };
 
-use crate::interface::AddInWrapper;
+use crate::{
+    interface::{AddInWrapper, ParamValue, ProtocolMode},
+    localization::UiLanguage,
+};
 
 use self::{
-    connection::Connection, init_done::InitDoneBaseVTable,
-    lang_extender::LanguageExtenderBaseVTable, locale_base::LocaleBaseVTable,
-    memory_manager::MemoryManager, string_utils::get_str,
+    connection::Connection,
+    init_done::InitDoneBaseVTable,
+    lang_extender::LanguageExtenderBaseVTable,
+    locale_base::LocaleBaseVTable,
+    memory_manager::{LocalBlocks, MemoryManager, LOCAL_MEMORY_MANAGER},
+    provided_types::TVariant,
+    string_utils::get_str,
     user_lang_base::UserLanguageBaseVTable,
 };
 
@@ -61,8 +69,8 @@ mod offset {
     pub const USER_LANG: usize = 3;
 }
 
-impl<'a, const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
-    unsafe fn get_component(&mut self) -> &'a mut Component<T> {
+impl<const OFFSET: usize, T: AddInWrapper> This<OFFSET, T> {
+    unsafe fn get_component<'a>(&mut self) -> &'a mut Component<T> {
         let new_ptr = (self as *mut This<OFFSET, T> as *mut c_void)
             .sub(OFFSET * std::mem::size_of::<usize>());
         &mut *(new_ptr as *mut Component<T>)
@@ -79,11 +87,54 @@ struct Component<T: AddInWrapper> {
     destroy: unsafe extern "system" fn(*mut *mut Component<T>),
     memory_manager_ptr: Option<&'static MemoryManager>,
     addin: T,
-    
+
     // Дополнительные поля - добавляем в конец, чтобы не нарушить совместимость
     connection_ptr: Option<&'static Connection>,
-    locale: Option<String>,
-    user_interface_language_code: Option<String>,
+    /// Language, passed by 1C, available to the AddIn during its calls with
+    /// `UiLanguage::with_current`
+    language: UiLanguage,
+    /// Memory of values, returned to the host without MemoryManager, freed
+    /// when the next call, that returns a value, ends
+    handed_over: LocalBlocks,
+    /// Default values of parameters, returned to the host without
+    /// MemoryManager, by method and parameter. The host may pass them back,
+    /// so they are reused while the value is the same
+    local_defaults: HashMap<(usize, usize), LocalDefault>,
+}
+
+/// Default value of a parameter, allocated by the AddIn
+struct LocalDefault {
+    value: ParamValue,
+    variant: TVariant,
+    block: LocalBlocks,
+}
+
+impl<T: AddInWrapper> Component<T> {
+    /// MemoryManager of the host, or, for hosts of Native API 1.0 that don't
+    /// provide one, the AddIn's own
+    fn memory_manager(&self) -> Option<&'static MemoryManager> {
+        match self.memory_manager_ptr {
+            Some(mem) => Some(mem),
+            None if self.addin.protocol_mode() == ProtocolMode::V1 => {
+                Some(&LOCAL_MEMORY_MANAGER)
+            }
+            None => None,
+        }
+    }
+
+    /// Calls the AddIn with the language of the component being current
+    fn with_language<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        let _scope = self.language.enter();
+        f(&mut self.addin)
+    }
+
+    /// Memory of `block` will be freed after the next call, if it's
+    /// allocated by the AddIn itself
+    fn hand_over(&mut self, block: *mut c_void) {
+        if self.memory_manager_ptr.is_none() {
+            self.handed_over.push(block);
+        }
+    }
 }
 
 unsafe extern "system" fn destroy<T: AddInWrapper>(
@@ -110,8 +161,9 @@ pub unsafe fn create_component<T: AddInWrapper>(
         addin,
 
         connection_ptr: Default::default(),
-        locale: Default::default(),
-        user_interface_language_code: Default::default(),
+        language: Default::default(),
+        handed_over: Default::default(),
+        local_defaults: Default::default(),
     });
 
     *component = Box::into_raw(c) as *mut c_void;
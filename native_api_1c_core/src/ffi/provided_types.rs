@@ -1,14 +1,12 @@
-use std::{
-    ffi::{c_int, c_void},
-    ptr,
-    slice::from_raw_parts,
-};
-
-use chrono::{Datelike, Timelike};
+use std::{ffi::c_int, slice::from_raw_parts};
 
 use crate::interface::ParamValue;
 
-use super::memory_manager::{AllocationError, MemoryManager};
+use super::string_utils::{from_os_string, os_string};
+
+use super::memory_manager::{
+    AllocationError, MemoryManager, PlatformBlob, PlatformString,
+};
 
 /// Type representing 1C date and time values
 /// # Fields
@@ -42,68 +40,6 @@ pub struct Tm {
     pub zone: std::ffi::c_char,
 }
 
-impl From<&Tm> for chrono::NaiveDateTime {
-    fn from(value: &Tm) -> Self {
-        let default_time = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap();
-
-        let from_result = chrono::NaiveDate::from_ymd_opt(
-            1900 + value.year,
-            (1 + value.mon) as u32,
-            (value.mday) as u32,
-        )
-        .ok_or(());
-        if from_result.is_err() {
-            return default_time;
-        }
-
-        let from_result = from_result.unwrap().and_hms_opt(
-            value.hour as u32,
-            value.min as u32,
-            value.sec as u32,
-        );
-        if from_result.is_none() {
-            return default_time;
-        }
-
-        from_result.unwrap()
-    }
-}
-
-impl From<Tm> for chrono::NaiveDateTime {
-    fn from(value: Tm) -> Self {
-        Self::from(&value)
-    }
-}
-
-impl From<&chrono::NaiveDateTime> for Tm {
-    fn from(value: &chrono::NaiveDateTime) -> Self {
-        Self {
-            sec: value.time().second() as c_int,
-            min: value.time().minute() as c_int,
-            hour: value.time().hour() as c_int,
-            mday: value.date().day() as c_int,
-            mon: value.date().month0() as c_int,
-            year: value.date().year() as c_int - 1900,
-            wday: value.date().weekday().num_days_from_sunday() as c_int,
-            yday: value.ordinal0() as c_int,
-            isdst: 0,
-            #[cfg(target_family = "unix")]
-            gmtoff: 0,
-            #[cfg(target_family = "unix")]
-            zone: 0,
-        }
-    }
-}
-
-impl From<chrono::NaiveDateTime> for Tm {
-    fn from(value: chrono::NaiveDateTime) -> Self {
-        Self::from(&value)
-    }
-}
-
 #[cfg(target_family = "unix")]
 impl PartialEq for Tm {
     fn eq(&self, other: &Self) -> bool {
@@ -142,7 +78,7 @@ impl PartialEq for Tm {
 /// `variant` - pointer to the TVariant object
 /// `result` - pointer to the result of the operation
 pub struct ReturnValue<'a> {
-    pub mem: &'a MemoryManager,
+    pub mem: &'static MemoryManager,
     pub variant: &'a mut TVariant,
     pub result: &'a mut bool,
 }
@@ -151,7 +87,7 @@ pub struct ReturnValue<'a> {
 impl<'a> ReturnValue<'a> {
     /// Creates a new ReturnValue object
     pub fn new(
-        mem: &'a MemoryManager,
+        mem: &'static MemoryManager,
         variant: &'a mut TVariant,
         result: &'a mut bool,
     ) -> Self {
@@ -193,34 +129,28 @@ impl<'a> ReturnValue<'a> {
 
     /// Sets the value of the ReturnValue object to UTF-16 `&[u16]`
     pub fn set_str(self, val: &[u16]) {
-        let Ok(ptr) = self.mem.alloc_str(val.len()) else {
+        let Ok(data) = PlatformString::from_slice_in(self.mem, val) else {
             *self.result = false;
             return;
         };
-
-        unsafe {
-            ptr::copy_nonoverlapping(val.as_ptr(), ptr.as_ptr(), val.len())
-        };
+        let (ptr, len) = data.into_raw();
 
         self.variant.vt = VariantType::WStr;
-        self.variant.value.data_str.ptr = ptr.as_ptr();
-        self.variant.value.data_str.len = val.len() as u32;
+        self.variant.value.data_str.ptr = ptr;
+        self.variant.value.data_str.len = len as u32;
     }
 
     /// Sets the value of the ReturnValue object to blob `&[u8]`
     pub fn set_blob(self, val: &[u8]) {
-        let Ok(ptr) = self.mem.alloc_blob(val.len()) else {
+        let Ok(data) = PlatformBlob::from_slice_in(self.mem, val) else {
             *self.result = false;
             return;
         };
-
-        unsafe {
-            ptr::copy_nonoverlapping(val.as_ptr(), ptr.as_ptr(), val.len())
-        };
+        let (ptr, len) = data.into_raw();
 
         self.variant.vt = VariantType::Blob;
-        self.variant.value.data_blob.ptr = ptr.as_ptr();
-        self.variant.value.data_blob.len = val.len() as u32;
+        self.variant.value.data_blob.ptr = ptr;
+        self.variant.value.data_blob.len = len as u32;
     }
 }
 
@@ -233,20 +163,17 @@ impl<'a> From<&'a TVariant> for ParamValue {
                 VariantType::Int32 => Self::I32(param.value.i32),
                 VariantType::Double => Self::F64(param.value.f64),
                 VariantType::Time => Self::Date(param.value.tm),
-                VariantType::WStr => Self::String(
-                    from_raw_parts(
-                        param.value.data_str.ptr,
-                        param.value.data_str.len as usize,
-                    )
-                    .into(),
-                ),
-                VariantType::Blob => Self::Blob(
-                    from_raw_parts(
-                        param.value.data_blob.ptr,
-                        param.value.data_blob.len as usize,
-                    )
-                    .into(),
-                ),
+                VariantType::WStr => {
+                    Self::String(param.as_str().unwrap_or_default().into())
+                }
+                VariantType::Blob => {
+                    Self::Blob(param.as_blob().unwrap_or_default().into())
+                }
+                VariantType::PStr => {
+                    Self::String(os_string(&String::from_utf8_lossy(
+                        param.as_pstr().unwrap_or_default(),
+                    )))
+                }
                 _ => Self::Empty,
             }
         }
@@ -345,27 +272,85 @@ impl Default for TVariant {
 }
 
 impl TVariant {
+    /// Borrows string value, `None` if variant is not a string
+    pub fn as_str(&self) -> Option<&[u16]> {
+        if self.vt != VariantType::WStr {
+            return None;
+        }
+        unsafe {
+            let data_str = self.value.data_str;
+            if data_str.ptr.is_null() || data_str.len == 0 {
+                return Some(&[]);
+            }
+            Some(from_raw_parts(data_str.ptr, data_str.len as usize))
+        }
+    }
+
+    /// Borrows blob value, `None` if variant is not a blob
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        if self.vt != VariantType::Blob {
+            return None;
+        }
+        unsafe {
+            let data_blob = self.value.data_blob;
+            if data_blob.ptr.is_null() || data_blob.len == 0 {
+                return Some(&[]);
+            }
+            Some(from_raw_parts(data_blob.ptr, data_blob.len as usize))
+        }
+    }
+
+    /// Borrows bytes of `PStr` string of Native API 1.0, without null
+    /// terminator, `None` if variant is not such string
+    pub fn as_pstr(&self) -> Option<&[u8]> {
+        if self.vt != VariantType::PStr {
+            return None;
+        }
+        // `PStr` has the same layout as blob: `char*` and length in bytes
+        unsafe {
+            let data_pstr = self.value.data_blob;
+            if data_pstr.ptr.is_null() || data_pstr.len == 0 {
+                return Some(&[]);
+            }
+            Some(from_raw_parts(data_pstr.ptr, data_pstr.len as usize))
+        }
+    }
+
+    /// Sets `PStr` string of Native API 1.0, encoded in null-terminated UTF-8
     /// # Safety
     /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
     /// Function is safe as long as 1C platform provides valid pointers.
-    pub unsafe fn update_to_str(
+    pub unsafe fn update_to_pstr(
         &mut self,
-        mem_mngr: &MemoryManager,
+        mem_mngr: &'static MemoryManager,
         v: &[u16],
     ) -> Result<u32, AllocationError> {
-        let old_pointer = self.value.data_str.ptr;
-
-        let ptr = mem_mngr.alloc_str(v.len())?;
-        ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len());
-
-        self.value.data_str.ptr = ptr.as_ptr();
-        self.value.data_str.len = v.len() as u32;
-
-        mem_mngr.free_memory(&mut old_pointer.cast::<c_void>());
-
-        self.vt = VariantType::WStr;
+        let text = from_os_string(v);
+        let mut bytes = Vec::with_capacity(text.len() + 1);
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.push(0);
+        let data = PlatformBlob::from_slice_in(mem_mngr, &bytes)?;
+
+        drop(self.take_owned(mem_mngr));
+        let (ptr, _) = data.into_raw();
+        self.value.data_blob = DataBlob {
+            ptr,
+            len: text.len() as u32,
+        };
+        self.vt = VariantType::PStr;
+        Ok(self.value.data_blob.len)
+    }
 
-        Ok(self.value.data_str.len)
+    /// # Safety
+    /// This function is unsafe because it manipulates pointers, provided by the 1C platform.
+    /// Function is safe as long as 1C platform provides valid pointers.
+    pub unsafe fn update_to_str(
+        &mut self,
+        mem_mngr: &'static MemoryManager,
+        v: &[u16],
+    ) -> Result<u32, AllocationError> {
+        let data = PlatformString::from_slice_in(mem_mngr, v)?;
+        Ok(self.set_str(mem_mngr, data))
     }
 
     /// # Safety
@@ -373,22 +358,67 @@ impl TVariant {
     /// Function is safe as long as 1C platform provides valid pointers.
     pub unsafe fn update_to_blob(
         &mut self,
-        mem_mngr: &MemoryManager,
+        mem_mngr: &'static MemoryManager,
         v: &[u8],
     ) -> Result<u32, AllocationError> {
-        let old_pointer = self.value.data_blob.ptr;
-
-        let ptr = mem_mngr.alloc_blob(v.len())?;
-        ptr::copy_nonoverlapping(v.as_ptr(), ptr.as_ptr(), v.len());
-
-        self.value.data_blob.ptr = ptr.as_ptr();
-        self.value.data_blob.len = v.len() as u32;
+        let data = PlatformBlob::from_slice_in(mem_mngr, v)?;
+        Ok(self.set_blob(mem_mngr, data))
+    }
 
-        mem_mngr.free_memory(&mut old_pointer.cast::<c_void>());
+    /// Passes ownership of the string to the variant, freeing its old value
+    unsafe fn set_str(
+        &mut self,
+        mem_mngr: &'static MemoryManager,
+        data: PlatformString,
+    ) -> u32 {
+        drop(self.take_owned(mem_mngr));
+        let (ptr, len) = data.into_raw();
+        self.value.data_str = DataStr {
+            ptr,
+            len: len as u32,
+        };
+        self.vt = VariantType::WStr;
+        self.value.data_str.len
+    }
 
+    /// Passes ownership of the blob to the variant, freeing its old value
+    unsafe fn set_blob(
+        &mut self,
+        mem_mngr: &'static MemoryManager,
+        data: PlatformBlob,
+    ) -> u32 {
+        drop(self.take_owned(mem_mngr));
+        let (ptr, len) = data.into_raw();
+        self.value.data_blob = DataBlob {
+            ptr,
+            len: len as u32,
+        };
         self.vt = VariantType::Blob;
+        self.value.data_blob.len
+    }
 
-        Ok(self.value.data_blob.len)
+    /// Memory of string or blob, held by the variant, other types don't own
+    /// any memory
+    unsafe fn take_owned(
+        &mut self,
+        mem_mngr: &'static MemoryManager,
+    ) -> Option<PlatformBlob> {
+        let (ptr, len) = match self.vt {
+            VariantType::WStr => (
+                self.value.data_str.ptr.cast::<u8>(),
+                self.value.data_str.len as usize * 2,
+            ),
+            VariantType::Blob => {
+                (self.value.data_blob.ptr, self.value.data_blob.len as usize)
+            }
+            VariantType::PStr => (
+                self.value.data_blob.ptr,
+                self.value.data_blob.len as usize + 1,
+            ),
+            _ => return None,
+        };
+        self.vt = VariantType::Empty;
+        Some(PlatformBlob::from_raw(mem_mngr, ptr, len))
     }
 
     pub fn update_to_bool(&mut self, v: bool) {
@@ -411,23 +441,56 @@ impl TVariant {
         self.vt = VariantType::Time;
     }
 
+    /// Sets the value of the variant, owned by the AddIn: string or blob it
+    /// held before is freed, other types don't own any memory
     pub fn update_from_return(
         &mut self,
-        mem_mngr: &MemoryManager,
+        mem_mngr: &'static MemoryManager,
         value: &ParamValue,
-    ) {
+    ) -> Result<(), AllocationError> {
         match value {
-            ParamValue::Empty => self.vt = VariantType::Empty,
-            ParamValue::Bool(v) => self.update_to_bool(*v),
-            ParamValue::I32(v) => self.update_to_i32(*v),
-            ParamValue::F64(v) => self.update_to_f64(*v),
-            ParamValue::Date(v) => self.update_to_date(*v),
             ParamValue::String(v) => {
-                let _ = unsafe { self.update_to_str(mem_mngr, v.as_slice()) };
+                unsafe { self.update_to_str(mem_mngr, v.as_slice()) }?;
             }
             ParamValue::Blob(v) => {
-                let _ = unsafe { self.update_to_blob(mem_mngr, v.as_slice()) };
+                unsafe { self.update_to_blob(mem_mngr, v.as_slice()) }?;
+            }
+            ParamValue::PlatformBlob(v) => {
+                unsafe { self.update_to_blob(mem_mngr, v) }?;
+            }
+            value => {
+                drop(unsafe { self.take_owned(mem_mngr) });
+                self.update_to_scalar(value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the value of the variant, provided by 1C, i.e. return value or
+    /// out parameter. Memory of its previous value belongs to 1C, so it's not
+    /// freed. `PlatformBlob` is passed to 1C without copying
+    pub fn update_platform_owned(
+        &mut self,
+        mem_mngr: &'static MemoryManager,
+        value: ParamValue,
+    ) -> Result<(), AllocationError> {
+        self.vt = VariantType::Empty;
+        match value {
+            ParamValue::PlatformBlob(v) => {
+                unsafe { self.set_blob(mem_mngr, v) };
+                Ok(())
             }
+            value => self.update_from_return(mem_mngr, &value),
+        }
+    }
+
+    fn update_to_scalar(&mut self, value: &ParamValue) {
+        match value {
+            ParamValue::Bool(v) => self.update_to_bool(*v),
+            ParamValue::I32(v) => self.update_to_i32(*v),
+            ParamValue::F64(v) => self.update_to_f64(*v),
+            ParamValue::Date(v) => self.update_to_date(*v),
+            _ => self.vt = VariantType::Empty,
         }
     }
 }
@@ -30,5 +30,6 @@ unsafe extern "system" fn set_user_interface_language_code<T: AddInWrapper>(
 ) {
     let component = this.get_component();
     let lang = get_str(lang);
+    component.language.set_code(lang);
     component.addin.set_user_interface_language_code(lang)
 }
@@ -0,0 +1,243 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard, TryLockError},
+};
+
+use crate::interface::{ParamValue, ParamValueConversionError};
+
+/// Generations are kept below 2^20, so that handle fits into 52 bits of
+/// mantissa and is passed through 1C number without loss
+const GENERATION_MASK: u32 = (1 << 20) - 1;
+
+/// Id of a value in `HandleRegistry`, passed to 1C as a number instead of an
+/// object, which Native API can't return. Handle of a removed value is stale
+/// and doesn't resolve, even if its slot is reused. Default handle never
+/// resolves
+pub struct Handle<T> {
+    index: u32,
+    generation: u32,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            _value: PhantomData,
+        }
+    }
+
+    /// Number, that represents the handle in 1C
+    pub fn to_bits(self) -> u64 {
+        (u64::from(self.generation) << 32) | u64::from(self.index)
+    }
+
+    /// Handle from the number, returned by `to_bits`
+    pub fn from_bits(bits: u64) -> Self {
+        Self::new(bits as u32, (bits >> 32) as u32)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state);
+    }
+}
+
+impl<T> Default for Handle<T> {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T> From<Handle<T>> for ParamValue {
+    fn from(handle: Handle<T>) -> Self {
+        ParamValue::from(handle.to_bits())
+    }
+}
+
+impl<T> TryFrom<ParamValue> for Handle<T> {
+    type Error = ParamValueConversionError;
+
+    fn try_from(value: ParamValue) -> Result<Self, Self::Error> {
+        u64::try_from(value).map(Self::from_bits)
+    }
+}
+
+/// Error of access to a value of `HandleRegistry`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandleError {
+    /// Handle doesn't refer to a stored value, e.g. the value was removed
+    Stale,
+    /// Value is in use, e.g. by AddIn method, that is called with it
+    Busy,
+}
+
+impl Display for HandleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandleError::Stale => write!(f, "handle is stale"),
+            HandleError::Busy => write!(f, "value of the handle is in use"),
+        }
+    }
+}
+
+impl std::error::Error for HandleError {}
+
+struct Slot<T> {
+    /// Generation of the current or the next value, starts with 1
+    generation: u32,
+    value: Option<Arc<Mutex<T>>>,
+}
+
+/// Storage of values, passed to 1C by `Handle`, e.g. open files or
+/// connections. Values are locked while AddIn method is called with them,
+/// so they are kept in `Arc<Mutex<T>>`, and the registry is `Send`, if
+/// values are
+pub struct HandleRegistry<T> {
+    slots: Vec<Slot<T>>,
+    /// Indices of vacant slots
+    vacant: Vec<u32>,
+}
+
+impl<T> Default for HandleRegistry<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+        }
+    }
+}
+
+impl<T> HandleRegistry<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores value and returns its handle
+    pub fn insert(&mut self, value: T) -> Handle<T> {
+        let value = Some(Arc::new(Mutex::new(value)));
+        match self.vacant.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = value;
+                Handle::new(index, slot.generation)
+            }
+            None => {
+                let index = self.slots.len() as u32;
+                self.slots.push(Slot {
+                    generation: 1,
+                    value,
+                });
+                Handle::new(index, 1)
+            }
+        }
+    }
+
+    /// Locks value, `Busy` if it's already locked, e.g. after `resolve`
+    pub fn get(
+        &self,
+        handle: Handle<T>,
+    ) -> Result<MutexGuard<'_, T>, HandleError> {
+        Self::lock(self.slot(handle)?)
+    }
+
+    /// Shares value, so it can be locked without borrowing the registry, used
+    /// by the derive to pass it to AddIn method. `Busy` if it's locked
+    pub fn resolve(
+        &self,
+        handle: Handle<T>,
+    ) -> Result<Arc<Mutex<T>>, HandleError> {
+        let value = self.slot(handle)?;
+        drop(Self::lock(value)?);
+        Ok(value.clone())
+    }
+
+    /// Locks value, returned by `resolve`, `Busy` if it's already locked.
+    /// Value stays available, even if other thread panicked with the lock
+    pub fn lock(value: &Mutex<T>) -> Result<MutexGuard<'_, T>, HandleError> {
+        match value.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+            Err(TryLockError::WouldBlock) => Err(HandleError::Busy),
+        }
+    }
+
+    /// Removes value, so its handle becomes stale. Value, that is shared
+    /// with `resolve` and is not released yet, is kept and `Busy` is returned
+    pub fn remove(&mut self, handle: Handle<T>) -> Result<T, HandleError> {
+        if Arc::strong_count(self.slot(handle)?) > 1 {
+            return Err(HandleError::Busy);
+        }
+        let slot = &mut self.slots[handle.index as usize];
+        let value = slot.value.take().ok_or(HandleError::Stale)?;
+        slot.generation = (slot.generation % GENERATION_MASK) + 1;
+        self.vacant.push(handle.index);
+        let value = Arc::into_inner(value).ok_or(HandleError::Busy)?;
+        Ok(value.into_inner().unwrap_or_else(|err| err.into_inner()))
+    }
+
+    /// Checks if handle refers to a stored value
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        self.slot(handle).is_ok()
+    }
+
+    /// Number of stored values
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.vacant.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all values, making all handles stale. Values in use are
+    /// dropped, when they are released. Called by the derive from `done` for
+    /// fields marked with `#[add_in_handles]`
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = (slot.generation % GENERATION_MASK) + 1;
+                self.vacant.push(index as u32);
+            }
+        }
+    }
+
+    fn slot(&self, handle: Handle<T>) -> Result<&Arc<Mutex<T>>, HandleError> {
+        let slot = self.slots.get(handle.index as usize);
+        match slot {
+            Some(slot) if slot.generation == handle.generation => {
+                slot.value.as_ref().ok_or(HandleError::Stale)
+            }
+            _ => Err(HandleError::Stale),
+        }
+    }
+}
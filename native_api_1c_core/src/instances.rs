@@ -0,0 +1,229 @@
+use std::{
+    collections::VecDeque,
+    ffi::{c_long, c_void},
+    fmt::Display,
+    ops::Deref,
+    ptr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+use crate::{
+    ffi::{create_component, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue},
+};
+
+static NEXT_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// AddIn, prepared by a method of another AddIn, e.g. a document returned by
+/// `Parser.Open(path)`. It waits for 1C to create an object of its class,
+/// since Native API can't pass it to 1C directly
+struct Pending<T> {
+    key: u64,
+    /// Key of `Children`, that prepared the AddIn
+    owner: u64,
+    add_in: T,
+}
+
+/// AddIns of a `factory` class of the library, that are prepared and wait
+/// for 1C to create their objects. It's declared by `extern_functions!` as
+/// a static, named by `factory` of `#[add_in_component]`, so each library
+/// keeps its own. The next object of the class, created with
+/// `New("AddIn.<Library>.<Class>")`, is the AddIn prepared first
+pub struct Factory<T> {
+    class: &'static str,
+    pending: Mutex<VecDeque<Pending<T>>>,
+}
+
+impl<T: AddInWrapper + Default + Send + 'static> Factory<T> {
+    pub const fn new(class: &'static str) -> Self {
+        Self {
+            class,
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Name of the class, which objects are created from prepared AddIns
+    pub fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Number of prepared AddIns, which objects are not created yet
+    pub fn pending_count(&self) -> usize {
+        self.pending().len()
+    }
+
+    /// Drops prepared AddIn, that is no longer needed, `false` if its object
+    /// is already created or it was cancelled
+    pub fn cancel(&self, instance: &PendingInstance) -> bool {
+        let cancelled = {
+            let mut pending = self.pending();
+            let index = pending.iter().position(|p| p.key == instance.key);
+            index.and_then(|index| pending.remove(index))
+        };
+        cancelled.is_some()
+    }
+
+    /// Creates component from the AddIn, prepared first, called by
+    /// `GetClassObject` of `extern_functions!`. If there is none, e.g. 1C
+    /// creates the object by itself, it's created from `T::default()`
+    /// # Safety
+    /// Same as for `create_component`
+    pub unsafe fn create(&self, component: *mut *mut c_void) -> c_long {
+        let prepared = self.pending().pop_front();
+        let add_in = prepared.map(|p| p.add_in).unwrap_or_default();
+        create_component(component, add_in)
+    }
+
+    fn pending(&self) -> MutexGuard<'_, VecDeque<Pending<T>>> {
+        // queue stays consistent, even if other thread panicked
+        self.pending.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Factory, which AddIns, prepared by `Children`, are dropped with them
+trait Discard: Sync {
+    /// Drops AddIns, prepared by `owner`
+    fn discard(&self, owner: u64);
+
+    fn count(&self, owner: u64) -> usize;
+}
+
+impl<T: AddInWrapper + Default + Send + 'static> Discard for Factory<T> {
+    fn discard(&self, owner: u64) {
+        // AddIns are dropped outside of the lock, they may prepare or cancel
+        // other ones
+        let dropped: VecDeque<Pending<T>> = {
+            let mut pending = self.pending();
+            let (dropped, kept) = std::mem::take(&mut *pending)
+                .into_iter()
+                .partition(|p| p.owner == owner);
+            *pending = kept;
+            dropped
+        };
+        drop(dropped);
+    }
+
+    fn count(&self, owner: u64) -> usize {
+        self.pending().iter().filter(|p| p.owner == owner).count()
+    }
+}
+
+/// Result of `Children::prepare`, returned to 1C as the name of the class,
+/// so that the object is created with `New("AddIn.<Library>." + Result)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingInstance {
+    class: &'static str,
+    key: u64,
+}
+
+impl PendingInstance {
+    /// Name of the class, which next object is the prepared AddIn
+    pub fn class(&self) -> &str {
+        self.class
+    }
+
+    /// Key of the prepared AddIn, unique within the library
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+}
+
+impl Display for PendingInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.class)
+    }
+}
+
+/// Dereferences to the name of the class, so that it can be returned from
+/// AddIn method as a string
+impl Deref for PendingInstance {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.class
+    }
+}
+
+impl From<PendingInstance> for ParamValue {
+    fn from(instance: PendingInstance) -> Self {
+        ParamValue::String(os_string(instance.class))
+    }
+}
+
+/// AddIns, prepared by a parent AddIn, which objects are not created yet.
+/// Kept as a field of the parent, so that they are dropped with it, e.g. when
+/// 1C destroys the parser before documents, opened by it, are created
+pub struct Children {
+    owner: u64,
+    /// Factories, used by `prepare`
+    factories: Mutex<Vec<&'static dyn Discard>>,
+}
+
+impl Children {
+    pub fn new() -> Self {
+        Self {
+            owner: NEXT_KEY.fetch_add(1, Ordering::Relaxed),
+            factories: Mutex::default(),
+        }
+    }
+
+    /// Prepares AddIn, that becomes the next object of the `factory` class,
+    /// created by 1C. AddIns can share state with their parent behind an
+    /// `Arc`
+    pub fn prepare<T: AddInWrapper + Default + Send + 'static>(
+        &self,
+        factory: &'static Factory<T>,
+        add_in: T,
+    ) -> PendingInstance {
+        let key = NEXT_KEY.fetch_add(1, Ordering::Relaxed);
+        let class = factory.class;
+        factory.pending().push_back(Pending {
+            key,
+            owner: self.owner,
+            add_in,
+        });
+
+        let mut factories = self.factories();
+        let factory: &'static dyn Discard = factory;
+        if !factories.iter().any(|f| ptr::addr_eq(*f, factory)) {
+            factories.push(factory);
+        }
+        PendingInstance { class, key }
+    }
+
+    /// Number of AddIns, prepared by this parent, which objects are not
+    /// created yet
+    pub fn pending_count(&self) -> usize {
+        self.factories().iter().map(|f| f.count(self.owner)).sum()
+    }
+
+    fn factories(&self) -> MutexGuard<'_, Vec<&'static dyn Discard>> {
+        self.factories.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Default for Children {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Children {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Children")
+            .field("owner", &self.owner)
+            .finish()
+    }
+}
+
+impl Drop for Children {
+    fn drop(&mut self) {
+        let factories = std::mem::take(&mut *self.factories());
+        for factory in factories {
+            factory.discard(self.owner);
+        }
+    }
+}
@@ -1,9 +1,27 @@
-use std::ops::{Index, IndexMut};
-
-use crate::ffi::{connection::Connection, provided_types::Tm};
+use std::{
+    ops::{Deref, Index, IndexMut},
+    sync::OnceLock,
+};
+
+use crate::{
+    description::MethodDescription,
+    ffi::{
+        connection::Connection,
+        provided_types::{TVariant, Tm},
+    },
+    localization::{MessageCatalog, UiLanguage},
+};
+
+pub use crate::{
+    conversions::ParamValueConversionError,
+    ffi::memory_manager::{
+        AllocationError, PlatformBlob, PlatformBox, PlatformString,
+    },
+    u16_str::{U16Str, U16String},
+};
 
 /// Represents 1C variant values for parameters in safe Rust code.
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub enum ParamValue {
     /// Empty value
     Empty,
@@ -19,6 +37,9 @@ pub enum ParamValue {
     String(Vec<u16>),
     /// Blob value
     Blob(Vec<u8>),
+    /// Blob value, already allocated with 1C MemoryManager. It's the same
+    /// `BinaryData` for 1C as `Blob`, but is passed without copying
+    PlatformBlob(PlatformBlob),
 }
 
 impl ParamValue {
@@ -47,8 +68,27 @@ impl ParamValue {
     }
 }
 
+/// `PlatformBlob` is cloned into `Blob`, as the copy is not passed to 1C
+impl Clone for ParamValue {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Empty => Self::Empty,
+            Self::Bool(val) => Self::Bool(*val),
+            Self::I32(val) => Self::I32(*val),
+            Self::F64(val) => Self::F64(*val),
+            Self::Date(val) => Self::Date(*val),
+            Self::String(val) => Self::String(val.clone()),
+            Self::Blob(val) => Self::Blob(val.clone()),
+            Self::PlatformBlob(val) => Self::Blob(val.to_vec()),
+        }
+    }
+}
+
 impl PartialEq for ParamValue {
     fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.as_blob(), other.as_blob()) {
+            return a == b;
+        }
         match (self, other) {
             (Self::Empty, Self::Empty) => true,
             (Self::Bool(a), Self::Bool(b)) => a == b,
@@ -56,7 +96,6 @@ impl PartialEq for ParamValue {
             (Self::F64(a), Self::F64(b)) => a == b,
             (Self::Date(a), Self::Date(b)) => a == b,
             (Self::String(a), Self::String(b)) => a == b,
-            (Self::Blob(a), Self::Blob(b)) => a == b,
             _ => false,
         }
     }
@@ -65,13 +104,66 @@ impl PartialEq for ParamValue {
 /// Represents 1C variant values for return values in safe Rust code.
 /// Only creator of the object can set the initial value, therefor has
 /// control over count of values.
+///
+/// Strings and blobs, passed by 1C, are borrowed from the platform buffer
+/// until they are accessed as `ParamValue`, so `get_str` and `get_blob` can
+/// read them without copying. Only parameters, that are accessed mutably,
+/// e.g. out parameters of the method, are written back to 1C
+#[derive(Clone)]
+pub struct ParamValues<'a> {
+    values: Vec<ParamSlot<'a>>,
+}
+
 #[derive(Clone)]
-pub struct ParamValues {
-    values: Vec<ParamValue>,
+enum ParamSlot<'a> {
+    Value(ParamValue),
+    /// Value, accessed mutably, so it has to be written back to 1C
+    Changed(ParamValue),
+    /// String or blob, borrowed from 1C, with its `ParamValue` created on
+    /// first access
+    Borrowed(BorrowedVariant<'a>, OnceLock<ParamValue>),
 }
 
-impl ParamValues {
+/// Parameter, passed by 1C, that is only read while the method is called
+#[derive(Clone, Copy)]
+struct BorrowedVariant<'a>(&'a TVariant);
+
+// SAFETY: the variant and the string or blob it points to are owned by 1C
+// for the duration of the call and are only read through `&TVariant`, so
+// they can be read from other threads within `'a`, like `&[u16]` or `&[u8]`
+unsafe impl Send for BorrowedVariant<'_> {}
+unsafe impl Sync for BorrowedVariant<'_> {}
+
+impl Deref for BorrowedVariant<'_> {
+    type Target = TVariant;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> ParamValues<'a> {
     pub fn new(values: Vec<ParamValue>) -> Self {
+        Self {
+            values: values.into_iter().map(ParamSlot::Value).collect(),
+        }
+    }
+
+    /// Wraps parameters, passed by 1C, borrowing strings and blobs
+    pub fn from_variants(variants: &'a [TVariant]) -> Self {
+        let values = variants
+            .iter()
+            .map(|variant| {
+                if variant.as_str().is_some() || variant.as_blob().is_some() {
+                    ParamSlot::Borrowed(
+                        BorrowedVariant(variant),
+                        OnceLock::new(),
+                    )
+                } else {
+                    ParamSlot::Value(ParamValue::from(variant))
+                }
+            })
+            .collect();
         Self { values }
     }
 
@@ -83,27 +175,134 @@ impl ParamValues {
         self.values.is_empty()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<ParamValue> {
-        self.values.iter()
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &ParamValue> + '_ {
+        (0..self.len()).map(|index| &self[index])
+    }
+
+    /// Returns string parameter without copying it, or `None` if parameter
+    /// at `index` is not a string
+    pub fn get_str(&self, index: usize) -> Option<&U16Str> {
+        match self.values.get(index)? {
+            ParamSlot::Value(value) | ParamSlot::Changed(value) => {
+                value.as_u16_str()
+            }
+            ParamSlot::Borrowed(variant, _) => {
+                variant.as_str().map(U16Str::from_slice)
+            }
+        }
+    }
+
+    /// Returns blob parameter without copying it, or `None` if parameter at
+    /// `index` is not a blob
+    pub fn get_blob(&self, index: usize) -> Option<&[u8]> {
+        match self.values.get(index)? {
+            ParamSlot::Value(value) | ParamSlot::Changed(value) => {
+                value.as_blob()
+            }
+            ParamSlot::Borrowed(variant, _) => variant.as_blob(),
+        }
+    }
+
+    /// Values, that have to be written back to 1C
+    pub(crate) fn into_changed(self) -> Vec<(usize, ParamValue)> {
+        self.values
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                ParamSlot::Changed(value) => Some((index, value)),
+                ParamSlot::Value(_) | ParamSlot::Borrowed(..) => None,
+            })
+            .collect()
     }
 }
 
-impl Index<usize> for ParamValues {
+impl Index<usize> for ParamValues<'_> {
     type Output = ParamValue;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.values[index]
+        match &self.values[index] {
+            ParamSlot::Value(value) | ParamSlot::Changed(value) => value,
+            ParamSlot::Borrowed(variant, value) => {
+                value.get_or_init(|| ParamValue::from(variant.0))
+            }
+        }
     }
 }
 
-impl IndexMut<usize> for ParamValues {
+impl IndexMut<usize> for ParamValues<'_> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.values[index]
+        let slot = &mut self.values[index];
+        let value = match std::mem::replace(
+            slot,
+            ParamSlot::Value(ParamValue::Empty),
+        ) {
+            ParamSlot::Value(value) | ParamSlot::Changed(value) => value,
+            ParamSlot::Borrowed(variant, value) => value
+                .into_inner()
+                .unwrap_or_else(|| ParamValue::from(variant.0)),
+        };
+        *slot = ParamSlot::Changed(value);
+        match slot {
+            ParamSlot::Changed(value) => value,
+            _ => unreachable!(),
+        }
     }
 }
 
 pub type AddInWrapperResult<T> = Result<T, ()>;
 
+/// Version of Native API, implemented by the host, that loads the AddIn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolMode {
+    /// Native API 1.0 of old platforms and some third-party hosts: strings
+    /// are returned as null-terminated UTF-8 `PStr`, and if host doesn't
+    /// provide MemoryManager, memory for returned values is allocated by the
+    /// AddIn and is freed after the next call to a property or method.
+    /// Default values of parameters, which the host may pass back, are kept
+    /// while they don't change. Interfaces of later versions, e.g.
+    /// `UserLanguageBase`, are not called by such hosts
+    V1,
+    /// Native API 2.0: strings are UTF-16 `WStr`, allocated with MemoryManager
+    /// of the host
+    V2,
+}
+
+impl ProtocolMode {
+    /// Mode for the version, returned by `AddInWrapper::get_info`
+    pub fn from_info(info: u16) -> Self {
+        if info < 2000 {
+            Self::V1
+        } else {
+            Self::V2
+        }
+    }
+}
+
+/// Conversion of values, returned by AddIn property setters, into `Result`,
+/// so that setter can either return nothing or `Result`, error of which is
+/// reported to 1C platform with `errors::ErrorReport`
+pub trait IntoSetterResult {
+    type Error;
+
+    fn into_setter_result(self) -> Result<(), Self::Error>;
+}
+
+impl IntoSetterResult for () {
+    type Error = std::convert::Infallible;
+
+    fn into_setter_result(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<E> IntoSetterResult for Result<(), E> {
+    type Error = E;
+
+    fn into_setter_result(self) -> Result<(), Self::Error> {
+        self
+    }
+}
+
 /// `AddInWrapper` trait is used to implement the 1C AddIn interface,
 /// and is used in FFI to get necessary information about the AddIn
 /// and call its methods.
@@ -124,14 +323,19 @@ pub trait AddInWrapper {
     fn init(&mut self, interface: &'static Connection) -> bool;
 
     /// Equivalent to `GetInfo` from Native API interface and is used to get Native API version used by AddIn, either
-    /// `1000` meaning 1.0 or `2000` meaning 2.0. It will be later removed to only
-    /// support 2.0 version.
+    /// `1000` meaning 1.0 or `2000` meaning 2.0. It also selects `ProtocolMode`
+    /// of the FFI layer, see `protocol_mode`
     /// # Returns
     /// `u16` - Native API version
     fn get_info(&self) -> u16 {
         2000
     }
 
+    /// Protocol, expected from the host, derived from `get_info` by default
+    fn protocol_mode(&self) -> ProtocolMode {
+        ProtocolMode::from_info(self.get_info())
+    }
+
     /// Equivalent to `Done` from Native API interface and is called when the AddIn is unloaded by 1C platform
     fn done(&mut self);
 
@@ -289,4 +493,97 @@ pub trait AddInWrapper {
     /// # Arguments
     /// * `lang` - language code in UTF-16, two letters
     fn set_user_interface_language_code(&mut self, lang: &[u16]);
+
+    /// Interface language, set with `set_user_interface_language_code`, if
+    /// the AddIn keeps it. Derived AddIns use it to choose localized names
+    /// of methods and properties
+    fn ui_language(&self) -> Option<&UiLanguage> {
+        None
+    }
+
+    /// Catalog, that translates `Message` errors of methods and properties
+    /// into `ui_language`, when derived AddIn reports them to 1C
+    fn message_catalog(&self) -> Option<&MessageCatalog> {
+        None
+    }
+}
+
+/// `AddInMethods` trait describes the part of `AddInWrapper` that is responsible
+/// for methods of the AddIn. It allows to keep methods table separately from the
+/// rest of the AddIn, e.g. `#[add_in_methods]` macro implements it for an `impl`
+/// block, while `#[derive(AddIn)]` takes care of properties.
+///
+/// See `AddInWrapper` for description of each method.
+#[allow(clippy::result_unit_err)]
+pub trait AddInMethods {
+    /// Lowercase names of the methods in both languages, which
+    /// `#[derive(AddIn)]` checks not to be used by properties
+    #[doc(hidden)]
+    const METHOD_NAMES: &'static [&'static str] = &[];
+
+    /// See `AddInWrapper::get_n_methods`
+    fn get_n_methods(&self) -> usize;
+
+    /// See `AddInWrapper::find_method`
+    fn find_method(&self, name: &[u16]) -> Option<usize>;
+
+    /// See `AddInWrapper::get_method_name`
+    fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>>;
+
+    /// See `AddInWrapper::get_n_params`
+    fn get_n_params(&self, num: usize) -> usize;
+
+    /// See `AddInWrapper::get_param_def_value`
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        param_num: usize,
+    ) -> Option<ParamValue>;
+
+    /// See `AddInWrapper::has_ret_val`
+    fn has_ret_val(&self, method_num: usize) -> bool;
+
+    /// See `AddInWrapper::call_as_proc`
+    fn call_as_proc(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()>;
+
+    /// See `AddInWrapper::call_as_func`
+    fn call_as_func(
+        &mut self,
+        method_num: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue>;
+
+    /// Descriptions of the methods, used by `DescribeAddIn` of derived AddIn
+    fn method_descriptions() -> Vec<MethodDescription>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+}
+
+/// Checks, that `names` contain `name`, in constant context, used to check
+/// names of `AddInMethods::METHOD_NAMES`
+#[doc(hidden)]
+pub const fn contains_name(names: &[&str], name: &str) -> bool {
+    let mut index = 0;
+    while index < names.len() {
+        let other = names[index].as_bytes();
+        let name = name.as_bytes();
+        if other.len() == name.len() {
+            let mut byte = 0;
+            while byte < name.len() && other[byte] == name[byte] {
+                byte += 1;
+            }
+            if byte == name.len() {
+                return true;
+            }
+        }
+        index += 1;
+    }
+    false
 }
@@ -0,0 +1,126 @@
+//! Transport of structured values, that can't be represented by platform types,
+//! as JSON strings in AddIn function parameters and return values
+
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    ffi::{
+        connection::{Connection, MessageCode},
+        string_utils::{from_os_string, os_string},
+    },
+    interface::ParamValue,
+    localization::Message,
+};
+
+/// Error of conversion between JSON string and Rust value
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonError {
+    /// Index of parameter, `None` for return value
+    pub param_index: Option<usize>,
+    /// Path to the value in JSON, that caused the error, e.g. `items[0].name`,
+    /// `.` for the root value
+    pub path: String,
+    /// Error message
+    pub message: String,
+}
+
+impl JsonError {
+    /// Message of `MessageCatalog`, that translates the error:
+    /// `invalid_json_param` with arguments `index`, `path` and `error`, or
+    /// `invalid_json_return` with `path` and `error`
+    pub fn message(&self) -> Message {
+        let message = match self.param_index {
+            Some(index) => {
+                Message::new("invalid_json_param").arg("index", index)
+            }
+            None => Message::new("invalid_json_return"),
+        };
+        message.arg("path", &self.path).arg("error", &self.message)
+    }
+
+    /// Reports the error to 1C platform with `Connection::add_error`, so that
+    /// it is shown to the user as exception text
+    /// # Arguments
+    /// * `connection` - connection to 1C platform, nothing is reported if it's `None`
+    /// * `source` - source of the error, e.g. name of the AddIn function
+    pub fn report(&self, connection: Option<&Connection>, source: &str) {
+        if let Some(connection) = connection {
+            connection.add_error(MessageCode::Fail, source, &self.to_string());
+        }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.param_index {
+            Some(index) => write!(f, "Invalid JSON in parameter {index}")?,
+            None => write!(f, "Unable to convert return value to JSON")?,
+        }
+        write!(f, " at `{}`: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Deserializes value of AddIn function parameter from JSON string
+/// # Arguments
+/// * `value` - parameter value, must be `ParamValue::String`
+/// * `param_index` - index of the parameter, used in error
+/// # Returns
+/// `Result<T, JsonError>` - deserialized value or error with path to invalid value
+pub fn from_param<T: DeserializeOwned>(
+    value: &ParamValue,
+    param_index: usize,
+) -> Result<T, JsonError> {
+    let ParamValue::String(json) = value else {
+        return Err(JsonError {
+            param_index: Some(param_index),
+            path: ".".to_string(),
+            message: "expected string with JSON".to_string(),
+        });
+    };
+    let json = from_os_string(json);
+
+    let deserializer = &mut serde_json::Deserializer::from_str(&json);
+    serde_path_to_error::deserialize(&mut *deserializer)
+        .map_err(|err| JsonError {
+            param_index: Some(param_index),
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
+        })
+        .and_then(|value| {
+            deserializer.end().map_err(|err| JsonError {
+                param_index: Some(param_index),
+                path: ".".to_string(),
+                message: err.to_string(),
+            })?;
+            Ok(value)
+        })
+}
+
+/// Serializes value to JSON string, passed to 1C as `ParamValue::String`
+/// # Arguments
+/// * `value` - value to serialize
+/// * `param_index` - index of the parameter for out parameters, `None` for return value
+/// # Returns
+/// `Result<ParamValue, JsonError>` - JSON string or error with path to invalid value
+pub fn to_param<T: Serialize + ?Sized>(
+    value: &T,
+    param_index: Option<usize>,
+) -> Result<ParamValue, JsonError> {
+    let mut json = Vec::new();
+    let serializer = &mut serde_json::Serializer::new(&mut json);
+    serde_path_to_error::serialize(value, serializer).map_err(|err| {
+        JsonError {
+            param_index,
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
+        }
+    })?;
+
+    // serde_json only produces valid UTF-8
+    let json = String::from_utf8_lossy(&json);
+    Ok(ParamValue::String(os_string(&json)))
+}
@@ -7,7 +7,35 @@
 //! While it is possible to use this crate to implement your Native API
 //! Component, it is intended to be used with native_api_1c crate.
 
+/// BSL common modules with wrappers of AddIns
+pub mod bsl;
+/// Conversions between `ParamValue` and Rust types
+mod conversions;
+/// Conversions of dates into `chrono` and `time` types
+pub mod date_time;
+/// Descriptions of AddIns, as they are seen by 1C
+pub mod description;
+/// AddIns with methods and properties, defined at runtime
+pub mod dynamic;
+/// Errors of AddIn methods and properties, reported to 1C
+pub mod errors;
 /// Module for implementations of Native API FFI
 pub mod ffi;
+/// Registry of values, passed to 1C by handles
+pub mod handles;
+/// AddIns, created by methods of other AddIns
+pub mod instances;
 /// Module for high level interface of Native API
 pub mod interface;
+/// Module for JSON transport of structured values
+#[cfg(feature = "serde")]
+pub mod json;
+/// Localization of AddIns for the interface language of 1C
+pub mod localization;
+/// Serialization of values, passed between 1C and AddIn
+#[cfg(feature = "serde")]
+mod serialization;
+/// State, shared by AddIns of the library
+pub mod shared;
+/// UTF-16 string types
+mod u16_str;
@@ -0,0 +1,307 @@
+use std::{
+    borrow::Cow, cell::Cell, collections::HashMap, fmt::Display,
+    marker::PhantomData, ptr,
+};
+
+use crate::ffi::{
+    connection::{Connection, MessageCode},
+    string_utils::from_os_string,
+};
+
+/// Interface language of 1C session, passed to AddIn with
+/// `SetUserInterfaceLanguageCode`, or language of the locale, passed with
+/// `SetLocale` by platforms, that don't call the former. `#[derive(AddIn)]`
+/// keeps it in the field, marked with `#[add_in_lang]`, and uses it to
+/// choose localized names
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UiLanguage {
+    /// Lowercase code without region, e.g. `ru` or `kk`
+    code: Option<String>,
+    /// Language of the locale, used until the interface language is set
+    locale: Option<String>,
+}
+
+impl UiLanguage {
+    pub const fn new() -> Self {
+        Self {
+            code: None,
+            locale: None,
+        }
+    }
+
+    /// Language with the code, e.g. `en` or `uk_UA`, region is ignored
+    pub fn from_code(code: &str) -> Self {
+        Self {
+            code: language_code(code),
+            locale: None,
+        }
+    }
+
+    /// Sets the code, passed by 1C, with or without null terminator
+    pub fn set_code(&mut self, code: &[u16]) {
+        self.code = language_code(&from_os_string(code));
+    }
+
+    /// Sets the locale, passed by 1C, e.g. `ru_RU`. It only selects the
+    /// language, if the interface language is not set
+    pub fn set_locale(&mut self, locale: &[u16]) {
+        self.locale = language_code(&from_os_string(locale));
+    }
+
+    /// Lowercase code of the language, `None` until 1C sets it
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref().or(self.locale.as_deref())
+    }
+
+    /// Checks if it's the language with the code, regardless of case
+    pub fn is(&self, code: &str) -> bool {
+        self.code()
+            .is_some_and(|own| own.eq_ignore_ascii_case(code))
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<*const UiLanguage> = const { Cell::new(ptr::null()) };
+}
+
+/// Makes language available with `UiLanguage::with_current` until dropped
+pub(crate) struct LanguageScope<'a> {
+    previous: *const UiLanguage,
+    language: PhantomData<&'a UiLanguage>,
+}
+
+impl Drop for LanguageScope<'_> {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+impl UiLanguage {
+    /// Calls `f` with the language, that 1C passed to the component, which
+    /// method is being called on the current thread. It's kept by the
+    /// component, whether or not the AddIn has `#[add_in_lang]` field, and is
+    /// `None` outside of calls from 1C
+    pub fn with_current<R>(f: impl FnOnce(Option<&UiLanguage>) -> R) -> R {
+        let current = CURRENT.with(|current| current.get());
+        // SAFETY: pointer is set by `enter` and is valid, until its scope,
+        // that borrows the language, is dropped
+        f(unsafe { current.as_ref() })
+    }
+
+    /// Makes `self` current for the duration of the call from 1C
+    pub(crate) fn enter(&self) -> LanguageScope<'_> {
+        let previous = CURRENT.with(|current| current.replace(self));
+        LanguageScope {
+            previous,
+            language: PhantomData,
+        }
+    }
+}
+
+/// Lowercase language from the code or locale, region is dropped
+fn language_code(code: &str) -> Option<String> {
+    let language = code.split(['_', '-', '.']).next().unwrap_or_default();
+    let language = language.trim().to_lowercase();
+    (!language.is_empty()).then_some(language)
+}
+
+/// Message of `MessageCatalog` with its arguments, e.g. an error of AddIn
+/// method, that is translated when it's shown to the user. It can be used as
+/// error type, then it's displayed as its key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    key: Cow<'static, str>,
+    args: Vec<(Cow<'static, str>, String)>,
+}
+
+impl Message {
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Adds argument, that replaces `{name}` in the text of the message
+    pub fn arg(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Display,
+    ) -> Self {
+        self.args.push((name.into(), value.to_string()));
+        self
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key)
+    }
+}
+
+impl std::error::Error for Message {}
+
+/// Error in messages, passed to `MessageCatalog`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogError {
+    /// Language of the invalid messages
+    pub language: String,
+    /// Number of the invalid line, starting with 1
+    pub line: usize,
+    /// Error message
+    pub message: String,
+}
+
+impl Display for CatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid messages for `{}` at line {}: {}",
+            self.language, self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// Texts of AddIn messages in several languages, e.g. of errors, reported to
+/// 1C. Texts are chosen by `UiLanguage` of the AddIn, falling back to the
+/// default language, and are usually embedded with `include_str!`:
+/// ```
+/// use native_api_1c_core::localization::{Message, MessageCatalog, UiLanguage};
+///
+/// let catalog = MessageCatalog::new("ru")
+///     .with_messages("ru", "not_found = Файл {path} не найден")?
+///     .with_messages("en", "not_found = File {path} not found")?;
+///
+/// let message = Message::new("not_found").arg("path", "data.xml");
+/// let text = catalog.text(&UiLanguage::from_code("en"), &message);
+/// assert_eq!(text, "File data.xml not found");
+/// # Ok::<(), native_api_1c_core::localization::CatalogError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MessageCatalog {
+    /// Language, used if the interface language has no message
+    default: String,
+    /// Texts by languages and keys
+    texts: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageCatalog {
+    /// Creates empty catalog, `default` language is used for messages, that
+    /// are missing in the interface language
+    pub fn new(default: &str) -> Self {
+        Self {
+            default: language_code(default).unwrap_or_default(),
+            texts: HashMap::new(),
+        }
+    }
+
+    /// Adds messages of the language, one `key = text` per line. Empty lines
+    /// and lines, starting with `#`, are skipped, `\n` in text is replaced
+    /// with a line break
+    pub fn with_messages(
+        mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<Self, CatalogError> {
+        let mut texts = HashMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| CatalogError {
+                language: language.to_owned(),
+                line: index + 1,
+                message: message.to_owned(),
+            };
+            let Some((key, text)) = line.split_once('=') else {
+                return Err(error("expected `key = text`"));
+            };
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(error("key is empty"));
+            }
+            if texts.contains_key(key) {
+                return Err(error(&format!("key `{key}` is repeated")));
+            }
+            texts.insert(key.to_owned(), text.trim().replace("\\n", "\n"));
+        }
+        self.add_texts(language, texts);
+        Ok(self)
+    }
+
+    /// Adds messages of the language from JSON object with texts by keys
+    #[cfg(feature = "serde")]
+    pub fn with_json(
+        mut self,
+        language: &str,
+        json: &str,
+    ) -> Result<Self, CatalogError> {
+        let texts: HashMap<String, String> = serde_json::from_str(json)
+            .map_err(|err| CatalogError {
+                language: language.to_owned(),
+                line: err.line(),
+                message: err.to_string(),
+            })?;
+        self.add_texts(language, texts);
+        Ok(self)
+    }
+
+    fn add_texts(&mut self, language: &str, texts: HashMap<String, String>) {
+        let language = language_code(language).unwrap_or_default();
+        self.texts.entry(language).or_default().extend(texts);
+    }
+
+    /// Text of the message in the language, or in the default one, without
+    /// arguments replaced
+    pub fn get(&self, language: &UiLanguage, key: &str) -> Option<&str> {
+        let text = |language: &str| {
+            self.texts.get(language)?.get(key).map(String::as_str)
+        };
+        language
+            .code()
+            .and_then(text)
+            .or_else(|| text(&self.default))
+    }
+
+    /// Text of the message in the language with its arguments. Key is
+    /// returned for messages, missing in the catalog
+    pub fn text(&self, language: &UiLanguage, message: &Message) -> String {
+        let Some(text) = self.get(language, &message.key) else {
+            return message.key.to_string();
+        };
+        message
+            .args
+            .iter()
+            .fold(text.to_owned(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Reports the message to 1C platform with `Connection::add_error`, so
+    /// that it is shown to the user as exception text
+    /// # Arguments
+    /// * `connection` - connection to 1C platform, nothing is reported if
+    ///   it's `None`
+    /// * `language` - language of the AddIn
+    /// * `source` - source of the error, e.g. name of the AddIn function
+    /// * `message` - message to report
+    pub fn report(
+        &self,
+        connection: Option<&Connection>,
+        language: &UiLanguage,
+        source: &str,
+        message: &Message,
+    ) {
+        if let Some(connection) = connection {
+            let text = self.text(language, message);
+            connection.add_error(MessageCode::Fail, source, &text);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! `Serialize` and `Deserialize` implementations for values passed between
+//! 1C and AddIn, so that calls can be recorded and replayed. Representation is
+//! stable and human readable:
+//! * `ParamValue` is tagged: `{"type": "I32", "value": 42}`, `{"type": "Empty"}`
+//! * strings are UTF-8, blobs are base64 strings
+//! * dates are ISO 8601 strings: `2024-02-29T12:30:15`, with UTC offset on
+//!   unix, if it's set: `2024-02-29T12:30:15+03:00`
+
+use std::ffi::c_int;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    date_time::days_in_month,
+    ffi::provided_types::Tm,
+    interface::{ParamValue, ParamValues},
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum ParamValueRepr {
+    Empty,
+    Bool(bool),
+    I32(i32),
+    F64(f64),
+    Date(Tm),
+    String(String),
+    Blob(String),
+}
+
+impl Serialize for ParamValue {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            ParamValue::Empty => ParamValueRepr::Empty,
+            ParamValue::Bool(val) => ParamValueRepr::Bool(*val),
+            ParamValue::I32(val) => ParamValueRepr::I32(*val),
+            ParamValue::F64(val) => ParamValueRepr::F64(*val),
+            ParamValue::Date(val) => ParamValueRepr::Date(*val),
+            ParamValue::String(val) => {
+                ParamValueRepr::String(String::from_utf16_lossy(val))
+            }
+            ParamValue::Blob(val) => ParamValueRepr::Blob(STANDARD.encode(val)),
+            ParamValue::PlatformBlob(val) => {
+                ParamValueRepr::Blob(STANDARD.encode(&**val))
+            }
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ParamValue {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = match ParamValueRepr::deserialize(deserializer)? {
+            ParamValueRepr::Empty => ParamValue::Empty,
+            ParamValueRepr::Bool(val) => ParamValue::Bool(val),
+            ParamValueRepr::I32(val) => ParamValue::I32(val),
+            ParamValueRepr::F64(val) => ParamValue::F64(val),
+            ParamValueRepr::Date(val) => ParamValue::Date(val),
+            ParamValueRepr::String(val) => {
+                ParamValue::String(val.encode_utf16().collect())
+            }
+            ParamValueRepr::Blob(val) => {
+                ParamValue::Blob(STANDARD.decode(val).map_err(|e| {
+                    D::Error::custom(format!("invalid blob: {e}"))
+                })?)
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl Serialize for ParamValues<'_> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for ParamValues<'_> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Vec::<ParamValue>::deserialize(deserializer).map(ParamValues::new)
+    }
+}
+
+impl Serialize for Tm {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let date_time = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year + 1900,
+            self.mon + 1,
+            self.mday,
+            self.hour,
+            self.min,
+            self.sec
+        );
+
+        #[cfg(target_family = "unix")]
+        if self.gmtoff != 0 {
+            let sign = if self.gmtoff < 0 { '-' } else { '+' };
+            let offset_min = self.gmtoff.abs() / 60;
+            return serializer.collect_str(&format_args!(
+                "{date_time}{sign}{:02}:{:02}",
+                offset_min / 60,
+                offset_min % 60
+            ));
+        }
+
+        serializer.collect_str(&date_time)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tm {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_iso_date_time(&value).ok_or_else(|| {
+            D::Error::custom(format!(
+                "invalid date `{value}`, expected `YYYY-MM-DDTHH:MM:SS` \
+                 with optional UTC offset"
+            ))
+        })
+    }
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS` with optional `Z` or `±HH:MM` offset
+fn parse_iso_date_time(value: &str) -> Option<Tm> {
+    let (date_time, offset) = split_offset(value)?;
+    if date_time.len() != 19 || !date_time.is_ascii() {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| -> Option<c_int> {
+        let part = &date_time[range];
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        part.parse().ok()
+    };
+    let separators = [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':')];
+    if separators
+        .iter()
+        .any(|(index, separator)| date_time.as_bytes()[*index] != *separator)
+    {
+        return None;
+    }
+
+    let year = number(0..4)?;
+    let month = number(5..7)?;
+    let day = number(8..10)?;
+    let hour = number(11..13)?;
+    let min = number(14..16)?;
+    let sec = number(17..19)?;
+    let valid = (1..=12).contains(&month)
+        && (1..=days_in_month(year, month)).contains(&day)
+        && hour < 24
+        && min < 60
+        && sec <= 60;
+    if !valid {
+        return None;
+    }
+
+    let mut tm = Tm::from_fields(year, month, day, hour, min, sec);
+    tm.set_utc_offset(offset);
+    Some(tm)
+}
+
+/// Splits date and time from UTC offset, returned in seconds
+fn split_offset(value: &str) -> Option<(&str, i64)> {
+    if let Some(date_time) = value.strip_suffix('Z') {
+        return Some((date_time, 0));
+    }
+    if value.len() < 25 || !value.is_char_boundary(19) {
+        return Some((value, 0));
+    }
+
+    let (date_time, offset) = value.split_at(19);
+    let sign = match offset.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = offset[1..].split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some((date_time, sign * (hours * 3600 + minutes * 60)))
+}
@@ -0,0 +1,130 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Debug,
+    ops::Deref,
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+/// Key, that values of the same type are shared by. Values of different keys
+/// don't collide, even if they have the same type, e.g. `Shared<Config>` of
+/// unrelated AddIns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SharedKey {
+    /// Value is shared by AddIns of one type, default of `#[add_in_shared]`
+    Owner(TypeId),
+    /// Value is shared by all AddIns, that use the same name, set with
+    /// `#[add_in_shared(key = "...")]`
+    Named(&'static str),
+}
+
+impl SharedKey {
+    /// Key of the values, shared by AddIns of type `O`
+    pub fn owner<O: Any>() -> Self {
+        Self::Owner(TypeId::of::<O>())
+    }
+
+    /// Key of the values, shared by AddIns, that use the same `name`
+    pub const fn named(name: &'static str) -> Self {
+        Self::Named(name)
+    }
+}
+
+type Slots = HashMap<(SharedKey, TypeId), Weak<dyn Any + Send + Sync>>;
+
+/// Values, shared by AddIns of the library, by their keys and types. Slots
+/// only keep weak references, so values are dropped with the last AddIn,
+/// that uses them
+fn slots() -> std::sync::MutexGuard<'static, Slots> {
+    static SLOTS: OnceLock<Mutex<Slots>> = OnceLock::new();
+    let slots = SLOTS.get_or_init(Default::default);
+    // map stays consistent, even if other thread panicked
+    slots.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Returns value of type `T`, shared by AddIns with the `key`, if some
+/// AddIn still holds it
+pub fn get<T: Any + Send + Sync>(key: SharedKey) -> Option<Arc<T>> {
+    let value = slots().get(&(key, TypeId::of::<T>()))?.upgrade()?;
+    value.downcast().ok()
+}
+
+/// Returns value of type `T`, shared by AddIns with the `key`, creating it
+/// with `init` if there is none. Value lives while any of the returned
+/// `Arc`s does, and is created again after that
+pub fn acquire<T: Any + Send + Sync>(
+    key: SharedKey,
+    init: impl FnOnce() -> T,
+) -> Arc<T> {
+    if let Some(value) = get(key) {
+        return value;
+    }
+    // `init` is called without lock, as it may acquire other values
+    let value = Arc::new(init());
+
+    let mut slots = slots();
+    let slot = slots
+        .entry((key, TypeId::of::<T>()))
+        .or_insert_with(|| Weak::<()>::new() as Weak<dyn Any + Send + Sync>);
+    // other thread may have created the value meanwhile
+    if let Some(existing) = slot.upgrade().and_then(|v| v.downcast().ok()) {
+        return existing;
+    }
+    let shared: Arc<dyn Any + Send + Sync> = value.clone();
+    *slot = Arc::downgrade(&shared);
+    value
+}
+
+/// Field of AddIn with value of type `T`, shared by AddIns of the library,
+/// e.g. cache or connection pool. It's empty until AddIn is initialized by
+/// 1C, `#[derive(AddIn)]` fills fields, marked with `#[add_in_shared]`, with
+/// values of `SharedKey::owner` of the AddIn or the key, set explicitly.
+/// Value is dropped when the last AddIn, that holds it, is destroyed
+pub struct Shared<T> {
+    value: Option<Arc<T>>,
+}
+
+impl<T: Any + Send + Sync> Shared<T> {
+    pub const fn new() -> Self {
+        Self { value: None }
+    }
+
+    /// Fills the field with the value, shared by the `key`, creating it
+    /// with `init` if there is none
+    pub fn acquire(&mut self, key: SharedKey, init: impl FnOnce() -> T) {
+        self.value = Some(acquire(key, init));
+    }
+
+    /// Shared value, `None` until the field is filled
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_deref()
+    }
+
+    /// `Arc` of the shared value, e.g. to pass it to other thread
+    pub fn arc(&self) -> Option<&Arc<T>> {
+        self.value.as_ref()
+    }
+}
+
+impl<T: Any + Send + Sync> Default for Shared<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Panics
+/// If the field is not filled yet, i.e. AddIn is not initialized by 1C
+impl<T: Any + Send + Sync> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+            .expect("shared value is available after AddIn is initialized")
+    }
+}
+
+impl<T: Debug> Debug for Shared<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Shared").field(&self.value).finish()
+    }
+}
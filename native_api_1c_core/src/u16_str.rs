@@ -0,0 +1,211 @@
+//! UTF-16 string types, that let strings be passed between 1C and AddIn
+//! without conversion to UTF-8 and back
+
+use std::{
+    borrow::Borrow,
+    char::{decode_utf16, DecodeUtf16Error, REPLACEMENT_CHARACTER},
+    fmt::{Debug, Display, Write},
+    ops::Deref,
+};
+
+use crate::interface::ParamValue;
+
+/// Borrowed UTF-16 string, e.g. string parameter, passed by 1C, that is
+/// read directly from the platform buffer. It's not validated, so it may
+/// contain unpaired surrogates, that are replaced when converting to `String`
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct U16Str([u16]);
+
+impl U16Str {
+    /// Wraps slice of UTF-16 code units
+    pub fn from_slice(slice: &[u16]) -> &Self {
+        // SAFETY: `U16Str` is `repr(transparent)` over `[u16]`
+        unsafe { &*(slice as *const [u16] as *const Self) }
+    }
+
+    /// UTF-16 code units of the string
+    pub fn as_slice(&self) -> &[u16] {
+        &self.0
+    }
+
+    /// Length in UTF-16 code units
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decodes characters of the string
+    pub fn chars(
+        &self,
+    ) -> impl Iterator<Item = Result<char, DecodeUtf16Error>> + '_ {
+        decode_utf16(self.0.iter().copied())
+    }
+
+    /// Converts to `String`, replacing invalid code units with `U+FFFD`
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(&self.0)
+    }
+
+    /// Converts to `String`, failing on invalid code units
+    pub fn to_string_checked(&self) -> Result<String, DecodeUtf16Error> {
+        self.chars().collect()
+    }
+
+    pub fn to_u16_string(&self) -> U16String {
+        U16String(self.0.to_vec())
+    }
+}
+
+impl Display for U16Str {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.chars() {
+            f.write_char(c.unwrap_or(REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for U16Str {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl AsRef<[u16]> for U16Str {
+    fn as_ref(&self) -> &[u16] {
+        &self.0
+    }
+}
+
+impl ToOwned for U16Str {
+    type Owned = U16String;
+
+    fn to_owned(&self) -> Self::Owned {
+        self.to_u16_string()
+    }
+}
+
+impl<'a> From<&'a [u16]> for &'a U16Str {
+    fn from(value: &'a [u16]) -> Self {
+        U16Str::from_slice(value)
+    }
+}
+
+/// Owned UTF-16 string, that is passed to 1C without conversion
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct U16String(Vec<u16>);
+
+impl U16String {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps vector of UTF-16 code units
+    pub fn from_vec(vec: Vec<u16>) -> Self {
+        Self(vec)
+    }
+
+    /// UTF-16 code units of the string
+    pub fn into_vec(self) -> Vec<u16> {
+        self.0
+    }
+
+    pub fn as_u16_str(&self) -> &U16Str {
+        U16Str::from_slice(&self.0)
+    }
+
+    /// Appends UTF-8 string, encoding it to UTF-16
+    pub fn push_str(&mut self, s: &str) {
+        self.0.extend(s.encode_utf16());
+    }
+
+    /// Appends UTF-16 string as is
+    pub fn push_u16_str(&mut self, s: &U16Str) {
+        self.0.extend_from_slice(s.as_slice());
+    }
+}
+
+impl Deref for U16String {
+    type Target = U16Str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_u16_str()
+    }
+}
+
+impl Borrow<U16Str> for U16String {
+    fn borrow(&self) -> &U16Str {
+        self.as_u16_str()
+    }
+}
+
+impl AsRef<U16Str> for U16String {
+    fn as_ref(&self) -> &U16Str {
+        self.as_u16_str()
+    }
+}
+
+impl Display for U16String {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_u16_str(), f)
+    }
+}
+
+impl Debug for U16String {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_u16_str(), f)
+    }
+}
+
+impl From<&str> for U16String {
+    fn from(value: &str) -> Self {
+        Self(value.encode_utf16().collect())
+    }
+}
+
+impl From<String> for U16String {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<&U16Str> for U16String {
+    fn from(value: &U16Str) -> Self {
+        value.to_u16_string()
+    }
+}
+
+impl From<Vec<u16>> for U16String {
+    fn from(value: Vec<u16>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<U16String> for Vec<u16> {
+    fn from(value: U16String) -> Self {
+        value.0
+    }
+}
+
+impl From<U16String> for ParamValue {
+    fn from(value: U16String) -> Self {
+        Self::String(value.0)
+    }
+}
+
+impl From<&U16Str> for ParamValue {
+    fn from(value: &U16Str) -> Self {
+        Self::String(value.0.to_vec())
+    }
+}
+
+impl ParamValue {
+    /// Returns value of `String` as `U16Str`
+    pub fn as_u16_str(&self) -> Option<&U16Str> {
+        self.as_utf16().map(U16Str::from_slice)
+    }
+}
@@ -0,0 +1,55 @@
+use native_api_1c_core::{
+    ffi::provided_types::{DataBlob, TVariant, VariantType, VariantValue},
+    interface::{ParamValue, ParamValues, PlatformBlob},
+};
+
+fn blob_variant(value: &mut [u8]) -> TVariant {
+    TVariant {
+        value: VariantValue {
+            data_blob: DataBlob {
+                ptr: value.as_mut_ptr(),
+                len: value.len() as u32,
+            },
+        },
+        elements: 0,
+        vt: VariantType::Blob,
+    }
+}
+
+#[test]
+fn test_borrowed_blob() {
+    let mut data = vec![1u8, 2, 3];
+    let ptr = data.as_ptr();
+    let variants = [blob_variant(&mut data), TVariant::default()];
+    let mut params = ParamValues::from_variants(&variants);
+
+    let borrowed = params.get_blob(0).unwrap();
+    assert_eq!(borrowed, [1, 2, 3]);
+    assert_eq!(borrowed.as_ptr(), ptr);
+    assert_eq!(params.get_blob(1), None);
+
+    assert_eq!(params[0], ParamValue::Blob(vec![1, 2, 3]));
+    params[0] = ParamValue::Blob(vec![4]);
+    assert_eq!(params.get_blob(0), Some([4u8].as_slice()));
+}
+
+#[test]
+fn test_empty_blob_variant() {
+    let variant = TVariant {
+        value: VariantValue {
+            data_blob: DataBlob {
+                ptr: std::ptr::null_mut(),
+                len: 0,
+            },
+        },
+        elements: 0,
+        vt: VariantType::Blob,
+    };
+    assert_eq!(variant.as_blob(), Some([].as_slice()));
+    assert_eq!(ParamValue::from(&variant), ParamValue::Blob(vec![]));
+}
+
+#[test]
+fn test_platform_blob_outside_call() {
+    assert!(PlatformBlob::new(16).is_err());
+}
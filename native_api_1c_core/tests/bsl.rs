@@ -0,0 +1,209 @@
+use native_api_1c_core::{
+    bsl::BslModule,
+    description::{
+        AddInDescription, MethodDescription, ParamDescription, PropDescription,
+        ValueType,
+    },
+    ffi::{provided_types::Tm, string_utils::os_string},
+    interface::ParamValue,
+};
+
+fn param(name: &'static str, ty: ValueType) -> ParamDescription {
+    ParamDescription {
+        name: Some(name),
+        ty,
+        default: None,
+        optional: false,
+        by_ref: false,
+    }
+}
+
+fn description() -> AddInDescription {
+    AddInDescription {
+        name: "Files",
+        doc: "",
+        props: vec![PropDescription {
+            name: "Count",
+            name_ru: "Количество",
+            doc: "Number of open files",
+            ty: ValueType::Number,
+            readable: true,
+            writable: true,
+        }],
+        methods: vec![
+            MethodDescription {
+                name: "Read",
+                name_ru: "Прочитать",
+                doc: "Reads the file\n# Arguments\n* `file_path` - path \
+                      to the file,\n  relative or absolute\n# Returns\n\
+                      Text of the file",
+                params: vec![
+                    param("file_path", ValueType::String),
+                    ParamDescription {
+                        default: Some(ParamValue::String(os_string(
+                            "say \"hi\"",
+                        ))),
+                        ..param("greeting", ValueType::String)
+                    },
+                    ParamDescription {
+                        default: Some(ParamValue::Date(Tm::from_fields(
+                            2024, 1, 31, 12, 0, 5,
+                        ))),
+                        ..param("since", ValueType::Date)
+                    },
+                ],
+                returns: Some(ValueType::String),
+            },
+            MethodDescription {
+                name: "Log",
+                name_ru: "Лог",
+                doc: "",
+                params: vec![
+                    ParamDescription {
+                        by_ref: true,
+                        ..param("result", ValueType::Bool)
+                    },
+                    ParamDescription {
+                        optional: true,
+                        ..param("values", ValueType::Any)
+                    },
+                    ParamDescription {
+                        optional: true,
+                        ..param("values", ValueType::Any)
+                    },
+                    ParamDescription {
+                        name: None,
+                        default: Some(ParamValue::Blob(vec![1])),
+                        ..param("", ValueType::Blob)
+                    },
+                ],
+                returns: None,
+            },
+            MethodDescription {
+                name: "Scale",
+                name_ru: "Масштаб",
+                doc: "",
+                params: vec![
+                    ParamDescription {
+                        default: Some(ParamValue::F64(f64::INFINITY)),
+                        ..param("factor", ValueType::Number)
+                    },
+                    ParamDescription {
+                        default: Some(ParamValue::Blob(vec![1, 255])),
+                        ..param("data", ValueType::Blob)
+                    },
+                ],
+                returns: Some(ValueType::Number),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_render_module() {
+    let module = BslModule::new("ОбщийМакет.Файлы", "FilesLib")
+        .description(description())
+        .render();
+
+    for expected in [
+        "#Область ПрограммныйИнтерфейс\n\n#Область Files\n",
+        "Функция Files_Создать() Экспорт\n\t\n\
+         \tПодключитьКомпоненту();\n\
+         \tВозврат Новый(\"AddIn.FilesLib.Files\");\n",
+        "// Number of open files\n\
+         //\n\
+         // Параметры:\n\
+         //  Компонента - ВнешняяКомпонента - объект, созданный функцией \
+         Files_Создать\n\
+         //\n\
+         // Возвращаемое значение:\n\
+         //  Число\n\
+         //\n\
+         Функция Files_Количество(Компонента) Экспорт\n\t\n\
+         \tВозврат Компонента.Количество;\n",
+        "Процедура Files_УстановитьКоличество(Компонента, Знач Значение) \
+         Экспорт\n\t\n\tКомпонента.Количество = Значение;\n",
+        "// Reads the file\n//\n",
+        "//  FilePath - Строка - path to the file, relative or absolute\n\
+         //  Greeting - Строка\n\
+         //  Since - Дата\n\
+         //\n\
+         // Возвращаемое значение:\n\
+         //  Строка - Text of the file\n",
+        "Функция Files_Прочитать(Компонента, Знач FilePath, \
+         Знач Greeting = \"say \"\"hi\"\"\", Знач Since = '20240131120005') \
+         Экспорт\n\t\n\
+         \tВозврат Компонента.Прочитать(FilePath, Greeting, Since);\n\t\n\
+         КонецФункции\n",
+        "Процедура Files_Лог(Компонента, Result, Знач Values1 = Неопределено, \
+         Знач Values2 = Неопределено, Знач Параметр4 = Неопределено) \
+         Экспорт\n\t\n\
+         \tКомпонента.Лог(Result, Values1, Values2, ?(Параметр4 = Неопределено, \
+         ПолучитьДвоичныеДанныеИзHexСтроки(\"01\"), Параметр4));\n\t\n\
+         КонецПроцедуры\n",
+        "Функция Files_Масштаб(Компонента, Знач Factor = Неопределено, \
+         Знач Data = Неопределено) Экспорт\n\t\n\
+         \tЕсли Factor = Неопределено Тогда\n\
+         \t\tВозврат Компонента.Масштаб(, ?(Data = Неопределено, \
+         ПолучитьДвоичныеДанныеИзHexСтроки(\"01FF\"), Data));\n\
+         \tИначе\n\
+         \t\tВозврат Компонента.Масштаб(Factor, ?(Data = Неопределено, \
+         ПолучитьДвоичныеДанныеИзHexСтроки(\"01FF\"), Data));\n\
+         \tКонецЕсли;\n\t\n\
+         КонецФункции\n",
+        "Если Не ПодключитьВнешнююКомпоненту(\"ОбщийМакет.Файлы\", \
+         \"FilesLib\", ТипВнешнейКомпоненты.Native) Тогда\n",
+    ] {
+        assert!(
+            module.contains(expected),
+            "{expected}\nnot found in\n{module}"
+        );
+    }
+    assert!(module.ends_with("КонецПроцедуры\n\n#КонецОбласти\n"));
+}
+
+#[test]
+fn test_write_module() {
+    let path = std::env::temp_dir().join("native_api_1c_bsl_module.bsl");
+    let module = BslModule::new("ОбщийМакет.Файлы", "FilesLib")
+        .description(description());
+    module.write(&path).unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        written.strip_prefix('\u{feff}'),
+        Some(module.render().as_str())
+    );
+}
+
+#[test]
+fn test_omitted_params_limit() {
+    let infinite = |name| ParamDescription {
+        default: Some(ParamValue::F64(f64::INFINITY)),
+        ..param(name, ValueType::Number)
+    };
+    let module = BslModule::new("ОбщийМакет.Файлы", "FilesLib")
+        .description(AddInDescription {
+            name: "Limits",
+            doc: "",
+            props: vec![],
+            methods: vec![MethodDescription {
+                name: "Set",
+                name_ru: "Установить",
+                doc: "",
+                params: (1..=64).map(|_| infinite("limit")).collect::<Vec<_>>(),
+                returns: None,
+            }],
+        })
+        .render();
+
+    // only first parameters are left out, the rest are passed as they are
+    assert_eq!(module.matches("\tЕсли Limit").count(), 1);
+    assert_eq!(module.matches("\tИначеЕсли ").count(), 6);
+    assert!(module.contains(
+        "\tЕсли Limit1 = Неопределено И Limit2 <> Неопределено \
+         И Limit3 <> Неопределено Тогда\n\
+         \t\tКомпонента.Установить(, Limit2, Limit3, Limit4,"
+    ));
+}
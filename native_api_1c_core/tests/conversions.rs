@@ -0,0 +1,115 @@
+use native_api_1c_core::{
+    ffi::string_utils::os_string,
+    interface::{ParamValue, ParamValueConversionError},
+};
+
+#[cfg(feature = "chrono")]
+fn date_time() -> chrono::NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_opt(12, 30, 15)
+        .unwrap()
+}
+
+#[test]
+fn test_from_rust_types() {
+    assert_eq!(ParamValue::from(true), ParamValue::Bool(true));
+    assert_eq!(ParamValue::from(-5i8), ParamValue::I32(-5));
+    assert_eq!(ParamValue::from(65535u16), ParamValue::I32(65535));
+    assert_eq!(ParamValue::from(42i64), ParamValue::I32(42));
+    assert_eq!(
+        ParamValue::from(u32::MAX),
+        ParamValue::F64(f64::from(u32::MAX))
+    );
+    assert_eq!(ParamValue::from(1.5), ParamValue::F64(1.5));
+    assert_eq!(
+        ParamValue::from("Привет"),
+        ParamValue::String(os_string("Привет"))
+    );
+    assert_eq!(
+        ParamValue::from(String::from("text")),
+        ParamValue::String(os_string("text"))
+    );
+    assert_eq!(ParamValue::from(vec![1u8, 2]), ParamValue::Blob(vec![1, 2]));
+    assert_eq!(ParamValue::from(None::<i32>), ParamValue::Empty);
+    assert_eq!(
+        ParamValue::from(Some("a")),
+        ParamValue::String(os_string("a"))
+    );
+}
+
+#[test]
+fn test_into_rust_types() {
+    assert_eq!(bool::try_from(ParamValue::Bool(true)), Ok(true));
+    assert_eq!(u8::try_from(ParamValue::I32(200)), Ok(200));
+    assert_eq!(i64::try_from(ParamValue::F64(1e12)), Ok(1_000_000_000_000));
+    assert_eq!(f64::try_from(ParamValue::I32(3)), Ok(3.0));
+    assert_eq!(
+        String::try_from(ParamValue::String(os_string("Привет"))),
+        Ok(String::from("Привет"))
+    );
+    assert_eq!(Vec::<u8>::try_from(ParamValue::Blob(vec![1])), Ok(vec![1]));
+    assert_eq!(Option::<i32>::try_from(ParamValue::Empty), Ok(None));
+    assert_eq!(Option::<i32>::try_from(ParamValue::I32(1)), Ok(Some(1)));
+}
+
+#[test]
+fn test_conversion_errors() {
+    assert_eq!(
+        i32::try_from(ParamValue::String(os_string("1"))),
+        Err(ParamValueConversionError::UnexpectedVariant {
+            expected: "number",
+            actual: "String",
+        })
+    );
+    assert_eq!(
+        u8::try_from(ParamValue::I32(256)),
+        Err(ParamValueConversionError::OutOfRange {
+            expected: "u8",
+            value: 256.0,
+        })
+    );
+    assert_eq!(
+        i32::try_from(ParamValue::F64(1.5)),
+        Err(ParamValueConversionError::OutOfRange {
+            expected: "i32",
+            value: 1.5,
+        })
+    );
+    assert_eq!(
+        Option::<bool>::try_from(ParamValue::I32(1)),
+        Err(ParamValueConversionError::UnexpectedVariant {
+            expected: "Bool",
+            actual: "I32",
+        })
+    );
+    assert_eq!(
+        String::try_from(ParamValue::Empty).unwrap_err().to_string(),
+        "expected String value, got Empty"
+    );
+}
+
+#[test]
+fn test_accessors() {
+    let string = ParamValue::from("text");
+    assert_eq!(string.as_string().as_deref(), Some("text"));
+    assert_eq!(string.as_utf16(), Some(os_string("text").as_slice()));
+    assert_eq!(string.as_i64(), None);
+
+    assert_eq!(ParamValue::I32(7).as_i64(), Some(7));
+    assert_eq!(ParamValue::F64(7.0).as_i32(), Some(7));
+    assert_eq!(ParamValue::F64(7.5).as_i32(), None);
+    assert_eq!(ParamValue::I32(7).as_f64(), Some(7.0));
+    assert_eq!(ParamValue::Bool(false).as_bool(), Some(false));
+    assert_eq!(ParamValue::Blob(vec![1]).as_blob(), Some([1u8].as_slice()));
+    assert!(ParamValue::Empty.is_empty());
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn test_date_time() {
+    let value = ParamValue::from(date_time());
+    assert_eq!(value, ParamValue::Date(date_time().into()));
+    assert_eq!(value.as_date_time(), Some(date_time()));
+    assert_eq!(chrono::NaiveDateTime::try_from(value), Ok(date_time()));
+}
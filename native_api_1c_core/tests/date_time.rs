@@ -0,0 +1,190 @@
+use native_api_1c_core::ffi::provided_types::Tm;
+
+fn tm(year: i32, month: i32, day: i32) -> Tm {
+    Tm::from_fields(year, month, day, 12, 30, 15)
+}
+
+#[test]
+fn test_from_fields() {
+    let value = tm(2024, 3, 1);
+    assert_eq!(value.year, 124);
+    assert_eq!(value.mon, 2);
+    // Friday, after February 29th
+    assert_eq!(value.wday, 5);
+    assert_eq!(value.yday, 60);
+}
+
+#[test]
+fn test_empty_date() {
+    let empty = Tm::empty_date();
+    assert!(empty.is_empty_date());
+    // 0001-01-01 of proleptic Gregorian calendar is Monday
+    assert_eq!(empty.wday, 1);
+    assert!(!tm(1, 1, 1).is_empty_date());
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Timelike};
+    use native_api_1c_core::{
+        date_time::DateConversionError,
+        interface::{ParamValue, ParamValueConversionError},
+    };
+
+    use super::*;
+
+    fn date_time(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(12, 30, 15)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = date_time(2024, 2, 29);
+        assert_eq!(Tm::from(value), tm(2024, 2, 29));
+        assert_eq!(NaiveDateTime::try_from(Tm::from(value)), Ok(value));
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        assert_eq!(
+            NaiveDateTime::try_from(Tm::default()),
+            Err(DateConversionError::InvalidField {
+                field: "mday",
+                value: 0
+            })
+        );
+        assert_eq!(
+            NaiveDateTime::try_from(tm(2023, 2, 29)),
+            Err(DateConversionError::InvalidDate {
+                year: 2023,
+                month: 2,
+                day: 29
+            })
+        );
+        let mut value = tm(2024, 1, 1);
+        value.hour = 24;
+        assert_eq!(
+            NaiveDateTime::try_from(value),
+            Err(DateConversionError::InvalidField {
+                field: "hour",
+                value: 24
+            })
+        );
+    }
+
+    #[test]
+    fn test_leap_second() {
+        let mut value = tm(2016, 12, 31);
+        value.sec = 60;
+        let converted = NaiveDateTime::try_from(value).unwrap();
+        assert_eq!(converted.second(), 59);
+        assert_eq!(Tm::from(converted), value);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_offset() {
+        let offset = FixedOffset::east_opt(3 * 3600).unwrap();
+        let value = date_time(2024, 2, 29).and_local_timezone(offset).unwrap();
+
+        let converted = Tm::from(value);
+        assert_eq!(converted.gmtoff, 3 * 3600);
+        assert_eq!(converted.hour, 12);
+        assert_eq!(DateTime::<FixedOffset>::try_from(converted), Ok(value));
+
+        let mut invalid = converted;
+        invalid.gmtoff = 25 * 3600;
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(invalid),
+            Err(DateConversionError::InvalidOffset(25 * 3600))
+        );
+    }
+
+    #[test]
+    fn test_param_value() {
+        assert_eq!(
+            Option::<NaiveDateTime>::try_from(ParamValue::Date(
+                Tm::empty_date()
+            )),
+            Ok(None)
+        );
+        assert_eq!(
+            NaiveDateTime::try_from(ParamValue::Date(Tm::empty_date())),
+            Ok(NaiveDate::from_ymd_opt(1, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap())
+        );
+        assert_eq!(
+            NaiveDateTime::try_from(ParamValue::Date(Tm::default())),
+            Err(ParamValueConversionError::InvalidDate(
+                DateConversionError::InvalidField {
+                    field: "mday",
+                    value: 0
+                }
+            ))
+        );
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use native_api_1c_core::{
+        date_time::DateConversionError, interface::ParamValue,
+    };
+    use time::{
+        Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+    };
+
+    use super::*;
+
+    fn date_time(year: i32, month: Month, day: u8) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(
+            Date::from_calendar_date(year, month, day).unwrap(),
+            Time::from_hms(12, 30, 15).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = date_time(2024, Month::February, 29);
+        assert_eq!(Tm::from(value), tm(2024, 2, 29));
+        assert_eq!(PrimitiveDateTime::try_from(Tm::from(value)), Ok(value));
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        assert_eq!(
+            PrimitiveDateTime::try_from(tm(2023, 2, 29)),
+            Err(DateConversionError::InvalidDate {
+                year: 2023,
+                month: 2,
+                day: 29
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_offset() {
+        let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        let value = date_time(2024, Month::February, 29).assume_offset(offset);
+
+        let converted = Tm::from(value);
+        assert_eq!(converted.gmtoff, -5 * 3600);
+        assert_eq!(OffsetDateTime::try_from(converted), Ok(value));
+    }
+
+    #[test]
+    fn test_param_value() {
+        let value = date_time(2024, Month::February, 29);
+        assert_eq!(ParamValue::from(value), ParamValue::Date(tm(2024, 2, 29)));
+        assert_eq!(
+            Option::<PrimitiveDateTime>::try_from(ParamValue::Empty),
+            Ok(None)
+        );
+    }
+}
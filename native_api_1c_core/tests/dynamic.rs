@@ -0,0 +1,118 @@
+use std::{cell::RefCell, rc::Rc};
+
+use native_api_1c_core::{
+    dynamic::DynamicAddIn,
+    ffi::string_utils::os_string,
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+
+/// Methods, as if they were loaded from a configuration
+const COMMANDS: [(&str, &str); 3] = [
+    ("Start", "Запустить"),
+    ("Stop", "Остановить"),
+    ("Restart", "Перезапустить"),
+];
+
+fn with_nul(name: &str) -> Vec<u16> {
+    os_string(name).into_iter().chain(Some(0)).collect()
+}
+
+fn add_in(log: Rc<RefCell<Vec<String>>>) -> DynamicAddIn {
+    let mut builder = DynamicAddIn::builder("Commands");
+    for (name, name_ru) in COMMANDS {
+        let log = log.clone();
+        builder = builder.procedure(name, name_ru, 1, [], move |params| {
+            let target = params[0].as_string().ok_or(())?;
+            log.borrow_mut().push(format!("{name} {target}"));
+            Ok(())
+        });
+    }
+
+    let level = Rc::new(RefCell::new(1));
+    let level_setter = level.clone();
+    builder
+        .method(
+            "Sum",
+            "Сумма",
+            2,
+            [(1, ParamValue::I32(10))],
+            |params| {
+                let a = params[0].as_i32().ok_or(())?;
+                let b = params[1].as_i32().ok_or(())?;
+                Ok(ParamValue::I32(a + b))
+            },
+        )
+        .property(
+            "Level",
+            "Уровень",
+            Some(Box::new(move || Ok(ParamValue::I32(*level.borrow())))),
+            Some(Box::new(move |value| {
+                *level_setter.borrow_mut() = value.as_i32().ok_or(())?;
+                Ok(())
+            })),
+        )
+        .property(
+            "Log",
+            "Журнал",
+            Some(Box::new(move || Ok(log.borrow().join("\n").into()))),
+            None,
+        )
+        .build()
+}
+
+#[test]
+fn test_methods() {
+    let log = Rc::default();
+    let mut add_in = add_in(Rc::clone(&log));
+
+    assert_eq!(add_in.register_extension_as(), with_nul("Commands"));
+    assert_eq!(add_in.get_n_methods(), 4);
+    assert_eq!(add_in.find_method(&os_string("Stop")), Some(1));
+    assert_eq!(add_in.find_method(&with_nul("Перезапустить")), Some(2));
+    assert_eq!(add_in.find_method(&os_string("Сумма")), Some(3));
+    assert_eq!(add_in.find_method(&os_string("Unknown")), None);
+    assert_eq!(add_in.get_method_name(3, 0), Some(with_nul("Sum")));
+    assert_eq!(add_in.get_method_name(3, 1), Some(with_nul("Сумма")));
+    assert_eq!(add_in.get_method_name(4, 0), None);
+
+    assert_eq!(add_in.get_n_params(0), 1);
+    assert_eq!(add_in.get_n_params(3), 2);
+    assert_eq!(add_in.get_param_def_value(3, 0), None);
+    assert_eq!(add_in.get_param_def_value(3, 1), Some(ParamValue::I32(10)));
+    assert!(!add_in.has_ret_val(0));
+    assert!(add_in.has_ret_val(3));
+
+    let mut params = ParamValues::new(vec!["server".into()]);
+    assert_eq!(add_in.call_as_proc(2, &mut params), Ok(()));
+    assert_eq!(add_in.call_as_func(2, &mut params), Err(()));
+    assert_eq!(*log.borrow(), ["Restart server"]);
+
+    let mut params = ParamValues::new(vec![2.into(), 3.into()]);
+    assert_eq!(add_in.call_as_func(3, &mut params), Ok(ParamValue::I32(5)));
+    assert_eq!(add_in.call_as_proc(3, &mut params), Ok(()));
+    assert_eq!(add_in.call_as_func(4, &mut params), Err(()));
+}
+
+#[test]
+fn test_props() {
+    let mut add_in = add_in(Rc::default());
+
+    assert_eq!(add_in.get_n_props(), 2);
+    assert_eq!(add_in.find_prop(&os_string("Уровень")), Some(0));
+    assert_eq!(add_in.find_prop(&with_nul("Log")), Some(1));
+    assert_eq!(add_in.get_prop_name(1, 1), Some(with_nul("Журнал")));
+    assert!(add_in.is_prop_readable(0) && add_in.is_prop_writable(0));
+    assert!(add_in.is_prop_readable(1) && !add_in.is_prop_writable(1));
+
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(1)));
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(3)), Ok(()));
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(3)));
+    assert_eq!(add_in.set_prop_val(0, "high".into()), Err(()));
+    assert_eq!(add_in.set_prop_val(1, "".into()), Err(()));
+    assert_eq!(add_in.get_prop_val(2), Err(()));
+
+    let mut params = ParamValues::new(vec!["db".into()]);
+    add_in.call_as_proc(0, &mut params).unwrap();
+    add_in.call_as_proc(1, &mut params).unwrap();
+    assert_eq!(add_in.get_prop_val(1), Ok("Start db\nStop db".into()));
+}
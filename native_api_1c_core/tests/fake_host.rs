@@ -0,0 +1,306 @@
+use native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        provided_types::{
+            DataBlob, DataStr, TVariant, VariantType, VariantValue,
+        },
+        string_utils::{from_os_string, os_string},
+    },
+    interface::{AddInWrapper, AddInWrapperResult, ParamValue, ParamValues},
+};
+
+use native_api_1c_test_utils::{allocated, free_variant, FakeHost};
+
+/// Counts memory, allocated by the current thread and not freed yet, as
+/// results, returned to hosts without MemoryManager, are freed by the AddIn
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    struct Counting;
+
+    thread_local! {
+        static LIVE: Cell<isize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = LIVE.try_with(|live| live.set(live.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let _ = LIVE.try_with(|live| live.set(live.get() - 1));
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    /// Number of blocks, allocated by the current thread and not freed
+    pub fn live() -> isize {
+        LIVE.with(|live| live.get())
+    }
+}
+
+/// AddIn with function `Greet(Name = "мир")` and procedure
+/// `Swap(First, Second)`, which only writes the first parameter
+struct Greeter {
+    info: u16,
+    name: Vec<u16>,
+}
+
+impl Greeter {
+    fn new(info: u16) -> Self {
+        Self {
+            info,
+            name: os_string("Greeter"),
+        }
+    }
+}
+
+impl AddInWrapper for Greeter {
+    fn init(&mut self, _: &'static Connection) -> bool {
+        true
+    }
+
+    fn get_info(&self) -> u16 {
+        self.info
+    }
+
+    fn done(&mut self) {}
+
+    fn register_extension_as(&mut self) -> &[u16] {
+        &self.name
+    }
+
+    fn get_n_props(&self) -> usize {
+        1
+    }
+
+    fn find_prop(&self, _: &[u16]) -> Option<usize> {
+        Some(0)
+    }
+
+    fn get_prop_name(&self, _: usize, _: usize) -> Option<Vec<u16>> {
+        Some(os_string("Name"))
+    }
+
+    fn get_prop_val(&self, _: usize) -> AddInWrapperResult<ParamValue> {
+        Ok(ParamValue::String(os_string("Greeter")))
+    }
+
+    fn set_prop_val(
+        &mut self,
+        _: usize,
+        _: ParamValue,
+    ) -> AddInWrapperResult<()> {
+        Err(())
+    }
+
+    fn is_prop_readable(&self, _: usize) -> bool {
+        true
+    }
+
+    fn is_prop_writable(&self, _: usize) -> bool {
+        false
+    }
+
+    fn get_n_methods(&self) -> usize {
+        2
+    }
+
+    fn find_method(&self, _: &[u16]) -> Option<usize> {
+        None
+    }
+
+    fn get_method_name(&self, _: usize, _: usize) -> Option<Vec<u16>> {
+        None
+    }
+
+    fn get_n_params(&self, num: usize) -> usize {
+        match num {
+            0 => 1,
+            _ => 2,
+        }
+    }
+
+    fn get_param_def_value(
+        &self,
+        method_num: usize,
+        _: usize,
+    ) -> Option<ParamValue> {
+        (method_num == 0).then(|| ParamValue::String(os_string("мир")))
+    }
+
+    fn has_ret_val(&self, method_num: usize) -> bool {
+        method_num == 0
+    }
+
+    fn call_as_proc(
+        &mut self,
+        _: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<()> {
+        let second = params[1].clone();
+        params[0] = second;
+        Ok(())
+    }
+
+    fn call_as_func(
+        &mut self,
+        _: usize,
+        params: &mut ParamValues,
+    ) -> AddInWrapperResult<ParamValue> {
+        let ParamValue::String(name) = &params[0] else {
+            return Err(());
+        };
+        let greeting = format!("Привет, {}!", from_os_string(name));
+        Ok(ParamValue::String(os_string(&greeting)))
+    }
+
+    fn set_locale(&mut self, _: &[u16]) {}
+
+    fn set_user_interface_language_code(&mut self, _: &[u16]) {}
+}
+
+/// String variant, owned by the host
+fn host_str(value: &mut Vec<u16>) -> TVariant {
+    TVariant {
+        value: VariantValue {
+            data_str: DataStr {
+                ptr: value.as_mut_ptr(),
+                len: value.len() as u32,
+            },
+        },
+        elements: 0,
+        vt: VariantType::WStr,
+    }
+}
+
+/// Null-terminated UTF-8 string variant of Native API 1.0, owned by the host
+fn host_pstr(value: &mut Vec<u8>) -> TVariant {
+    TVariant {
+        value: VariantValue {
+            data_blob: DataBlob {
+                ptr: value.as_mut_ptr(),
+                len: value.len() as u32 - 1,
+            },
+        },
+        elements: 0,
+        vt: VariantType::PStr,
+    }
+}
+
+#[test]
+fn test_v2_host() {
+    let host = FakeHost::new(Greeter::new(2000), true);
+    assert_eq!(host.get_info(), 2000);
+
+    let mut name = os_string("мир");
+    let result = host.call_as_func(0, &mut [host_str(&mut name)]).unwrap();
+    assert_eq!(result.vt, VariantType::WStr);
+    assert_eq!(result.as_str().unwrap(), os_string("Привет, мир!"));
+    free_variant(&result);
+
+    let mut first = os_string("first");
+    let mut second = os_string("second");
+    let mut params = [host_str(&mut first), host_str(&mut second)];
+    assert!(host.call_as_proc(1, &mut params));
+    assert_eq!(params[0].as_str().unwrap(), os_string("second"));
+    // unchanged parameter still points to the host memory
+    assert_eq!(unsafe { params[1].value.data_str.ptr }, second.as_mut_ptr());
+    free_variant(&params[0]);
+
+    let name = host.get_prop_val(0).unwrap();
+    assert_eq!(name.as_str().unwrap(), os_string("Greeter"));
+    free_variant(&name);
+
+    assert!(allocated().is_empty());
+}
+
+#[test]
+fn test_v1_host_without_memory_manager() {
+    let host = FakeHost::new(Greeter::new(1000), false);
+    assert_eq!(host.get_info(), 1000);
+
+    let mut name = b"world\0".to_vec();
+    let result = host.call_as_func(0, &mut [host_pstr(&mut name)]).unwrap();
+    assert_eq!(result.vt, VariantType::PStr);
+    assert_eq!(result.as_pstr().unwrap(), "Привет, world!".as_bytes());
+    let terminator = unsafe {
+        *result
+            .value
+            .data_blob
+            .ptr
+            .add(result.value.data_blob.len as usize)
+    };
+    assert_eq!(terminator, 0);
+
+    // memory is owned by the component and freed after the next call
+    let name = host.get_prop_val(0).unwrap();
+    assert_eq!(name.as_pstr().unwrap(), b"Greeter");
+}
+
+#[test]
+fn test_v1_host_default_value_passed_back() {
+    let host = FakeHost::new(Greeter::new(1000), false);
+
+    // host keeps the default and passes it to the call, while the component
+    // allocates other values meanwhile
+    let default = host.get_param_def_value(0, 0).unwrap();
+    assert_eq!(default.as_pstr().unwrap(), "мир".as_bytes());
+    let other_default = host.get_param_def_value(0, 0).unwrap();
+    let name = host.get_prop_val(0).unwrap();
+    assert_eq!(name.as_pstr().unwrap(), b"Greeter");
+
+    let result = host.call_as_func(0, &mut [default]).unwrap();
+    assert_eq!(result.as_pstr().unwrap(), "Привет, мир!".as_bytes());
+    let result = host.call_as_func(0, &mut [other_default]).unwrap();
+    assert_eq!(result.as_pstr().unwrap(), "Привет, мир!".as_bytes());
+}
+
+#[test]
+fn test_v1_host_frees_results() {
+    let host = FakeHost::new(Greeter::new(1000), false);
+    let mut name = b"world\0".to_vec();
+    let mut greet = |host: &FakeHost| {
+        let result = host.call_as_func(0, &mut [host_pstr(&mut name)]);
+        assert!(result.is_some());
+        let default = host.get_param_def_value(0, 0).unwrap();
+        assert_eq!(default.as_pstr().unwrap(), "мир".as_bytes());
+        assert!(host.get_prop_val(0).is_some());
+    };
+
+    greet(&host);
+    let live = counting::live();
+    for _ in 0..100 {
+        greet(&host);
+    }
+    // results of previous calls are freed, defaults are reused
+    assert_eq!(counting::live(), live);
+}
+
+#[test]
+fn test_v1_host_with_null_memory_manager() {
+    let host = FakeHost::new(Greeter::new(1000), false);
+    assert!(host.set_mem_manager(None));
+
+    let name = host.get_prop_val(0).unwrap();
+    assert_eq!(name.as_pstr().unwrap(), b"Greeter");
+
+    let host = FakeHost::new(Greeter::new(2000), false);
+    assert!(!host.set_mem_manager(None));
+}
+
+#[test]
+fn test_v2_host_without_memory_manager() {
+    let host = FakeHost::new(Greeter::new(2000), false);
+
+    let mut name = os_string("мир");
+    assert!(host.call_as_func(0, &mut [host_str(&mut name)]).is_none());
+    assert!(host.get_prop_val(0).is_none());
+}
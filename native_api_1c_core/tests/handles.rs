@@ -0,0 +1,105 @@
+use native_api_1c_core::{
+    handles::{Handle, HandleError, HandleRegistry},
+    interface::ParamValue,
+};
+
+#[test]
+fn test_insert_and_get() {
+    let mut registry = HandleRegistry::new();
+    let first = registry.insert(String::from("first"));
+    let second = registry.insert(String::from("second"));
+
+    assert_ne!(first, second);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(*registry.get(first).unwrap(), "first");
+    registry.get(second).unwrap().push('!');
+    assert_eq!(*registry.get(second).unwrap(), "second!");
+    assert_eq!(
+        registry.get(Handle::default()).err(),
+        Some(HandleError::Stale)
+    );
+}
+
+#[test]
+fn test_stale_handle() {
+    let mut registry = HandleRegistry::new();
+    let first = registry.insert(1);
+
+    assert_eq!(registry.remove(first), Ok(1));
+    assert_eq!(registry.remove(first), Err(HandleError::Stale));
+    assert!(!registry.contains(first));
+
+    // slot is reused with the next generation
+    let second = registry.insert(2);
+    assert_ne!(first, second);
+    assert_eq!(registry.get(first).err(), Some(HandleError::Stale));
+    assert!(registry.resolve(first).is_err());
+    assert_eq!(*registry.get(second).unwrap(), 2);
+}
+
+#[test]
+fn test_value_in_use() {
+    let mut registry = HandleRegistry::new();
+    let handle = registry.insert(1);
+
+    let value = registry.resolve(handle).unwrap();
+    let guard = HandleRegistry::lock(&value).unwrap();
+    assert_eq!(registry.get(handle).err(), Some(HandleError::Busy));
+    assert_eq!(registry.resolve(handle).err(), Some(HandleError::Busy));
+    assert_eq!(HandleRegistry::lock(&value).err(), Some(HandleError::Busy));
+    drop(guard);
+    assert_eq!(*registry.get(handle).unwrap(), 1);
+
+    // value, shared with `resolve`, is kept until it's released
+    assert_eq!(registry.remove(handle), Err(HandleError::Busy));
+    assert!(registry.contains(handle));
+    drop(value);
+    assert_eq!(registry.remove(handle), Ok(1));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_registry_in_other_thread() {
+    let mut registry = HandleRegistry::new();
+    let handle = registry.insert(String::from("first"));
+
+    let registry = std::thread::spawn(move || {
+        registry.get(handle).unwrap().push('!');
+        registry
+    })
+    .join()
+    .unwrap();
+    assert_eq!(*registry.get(handle).unwrap(), "first!");
+}
+
+#[test]
+fn test_clear() {
+    let mut registry = HandleRegistry::new();
+    let handles = [registry.insert('a'), registry.insert('b')];
+
+    registry.clear();
+    assert!(registry.is_empty());
+    assert!(handles.iter().all(|handle| !registry.contains(*handle)));
+
+    let handle = registry.insert('c');
+    assert!(!handles.contains(&handle));
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn test_param_value() {
+    let mut registry = HandleRegistry::new();
+    registry.insert(());
+    let handle = registry.insert(());
+
+    let value = ParamValue::from(handle);
+    assert_eq!(value, ParamValue::F64(handle.to_bits() as f64));
+    assert_eq!(Handle::try_from(value), Ok(handle));
+
+    assert_eq!(
+        ParamValue::from(Handle::<()>::default()),
+        ParamValue::I32(0)
+    );
+    assert!(Handle::<()>::try_from(ParamValue::F64(-1.0)).is_err());
+    assert!(Handle::<()>::try_from(ParamValue::Bool(true)).is_err());
+}
@@ -0,0 +1,100 @@
+use native_api_1c_core::{
+    ffi::string_utils::{os_string, os_string_nil},
+    localization::{CatalogError, Message, MessageCatalog, UiLanguage},
+};
+
+const RU: &str = "
+# ошибки
+not_found = Файл {path} не найден
+too_big = Файл {path} больше {max} байт
+";
+
+const EN: &str =
+    "not_found = File {path} not found\nmultiline = First\\nSecond";
+
+fn catalog() -> MessageCatalog {
+    MessageCatalog::new("ru")
+        .with_messages("ru", RU)
+        .unwrap()
+        .with_messages("en", EN)
+        .unwrap()
+}
+
+#[test]
+fn test_language_from_locale() {
+    let mut language = UiLanguage::new();
+    assert_eq!(language.code(), None);
+
+    language.set_locale(&os_string_nil("kk_KZ"));
+    assert_eq!(language.code(), Some("kk"));
+
+    // interface language takes precedence over the locale
+    language.set_code(&os_string("EN"));
+    assert!(language.is("en"));
+    language.set_locale(&os_string("ru_RU.UTF-8"));
+    assert_eq!(language.code(), Some("en"));
+}
+
+#[test]
+fn test_text_for_language() {
+    let catalog = catalog();
+    let message = Message::new("not_found").arg("path", "data.xml");
+
+    let en = UiLanguage::from_code("en_US");
+    assert_eq!(catalog.text(&en, &message), "File data.xml not found");
+    let ru = UiLanguage::from_code("ru");
+    assert_eq!(catalog.text(&ru, &message), "Файл data.xml не найден");
+    assert_eq!(catalog.get(&en, "multiline"), Some("First\nSecond"));
+}
+
+#[test]
+fn test_text_fallback() {
+    let catalog = catalog();
+    let too_big = Message::new("too_big").arg("path", "a.txt").arg("max", 10);
+
+    // missing in English and for unknown languages, default one is used
+    let expected = "Файл a.txt больше 10 байт";
+    assert_eq!(
+        catalog.text(&UiLanguage::from_code("en"), &too_big),
+        expected
+    );
+    assert_eq!(
+        catalog.text(&UiLanguage::from_code("kk"), &too_big),
+        expected
+    );
+    assert_eq!(catalog.text(&UiLanguage::new(), &too_big), expected);
+
+    let unknown = Message::new("unknown");
+    assert_eq!(catalog.text(&UiLanguage::new(), &unknown), "unknown");
+    assert_eq!(unknown.to_string(), "unknown");
+}
+
+#[test]
+fn test_invalid_messages() {
+    let error = |source| MessageCatalog::new("en").with_messages("en", source);
+
+    let CatalogError { line, message, .. } =
+        error("a = 1\n\nno separator").unwrap_err();
+    assert_eq!((line, message.as_str()), (3, "expected `key = text`"));
+
+    assert_eq!(error(" = text").unwrap_err().message, "key is empty");
+    assert_eq!(error("a = 1\na = 2").unwrap_err().line, 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_messages() {
+    let catalog = MessageCatalog::new("en")
+        .with_json("en", r#"{"hello": "Hello, {name}!"}"#)
+        .unwrap();
+    let message = Message::new("hello").arg("name", "world");
+    assert_eq!(
+        catalog.text(&UiLanguage::from_code("en"), &message),
+        "Hello, world!"
+    );
+
+    let error = MessageCatalog::new("en")
+        .with_json("en", "{\n\"hello\": 1}")
+        .unwrap_err();
+    assert_eq!(error.line, 2);
+}
@@ -0,0 +1,135 @@
+use native_api_1c_core::{
+    ffi::{
+        memory_manager::{PlatformBlob, PlatformString},
+        provided_types::{DataStr, TVariant, VariantType, VariantValue},
+        string_utils::os_string,
+    },
+    interface::{ParamValue, ParamValues},
+};
+
+use native_api_1c_test_utils::{allocated, memory_manager, MAX_ALLOCATION};
+
+#[test]
+fn test_freed_on_drop() {
+    let mem = memory_manager();
+    {
+        let mut blob = PlatformBlob::new_in(mem, 4).unwrap();
+        assert_eq!(*blob, [0, 0, 0, 0]);
+        blob[1] = 7;
+        assert_eq!(allocated(), [4]);
+
+        let string =
+            PlatformString::from_slice_in(mem, &os_string("ab")).unwrap();
+        assert_eq!(*string, os_string("ab"));
+    }
+    assert!(allocated().is_empty());
+}
+
+#[test]
+fn test_sent_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ParamValue>();
+    assert_send_sync::<ParamValues>();
+
+    let blob = PlatformBlob::from_slice_in(memory_manager(), &[1, 2]).unwrap();
+    // read on other thread and dropped on the one, that allocated it
+    let blob = std::thread::spawn(move || {
+        assert_eq!(*blob, [1, 2]);
+        blob
+    })
+    .join()
+    .unwrap();
+    drop(blob);
+    assert!(allocated().is_empty());
+
+    // MemoryManager isn't used on other thread, so the memory is leaked
+    let blob = PlatformBlob::from_slice_in(memory_manager(), &[3]).unwrap();
+    std::thread::spawn(move || drop(blob)).join().unwrap();
+    assert_eq!(allocated(), [1]);
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let mem = memory_manager();
+    let (ptr, len) = PlatformString::from_slice_in(mem, &os_string("text"))
+        .unwrap()
+        .into_raw();
+    assert_eq!(allocated(), [8]);
+
+    let string = unsafe { PlatformString::from_raw(mem, ptr, len) };
+    assert_eq!(*string, os_string("text"));
+    drop(string);
+    assert!(allocated().is_empty());
+
+    let (ptr, len) = PlatformBlob::new_in(mem, 0).unwrap().into_raw();
+    assert!(ptr.is_null());
+    assert_eq!(len, 0);
+    assert!(allocated().is_empty());
+}
+
+#[test]
+fn test_variant_update() {
+    let mem = memory_manager();
+    let mut variant = TVariant::default();
+
+    variant
+        .update_from_return(mem, &ParamValue::String(os_string("first")))
+        .unwrap();
+    assert_eq!(variant.vt, VariantType::WStr);
+    assert_eq!(allocated(), [10]);
+
+    // old string is freed, as it's owned by the variant
+    variant
+        .update_from_return(mem, &ParamValue::Blob(vec![1, 2, 3]))
+        .unwrap();
+    assert_eq!(variant.as_blob(), Some([1u8, 2, 3].as_slice()));
+    assert_eq!(allocated(), [3]);
+
+    unsafe { PlatformBlob::from_raw(mem, variant.value.data_blob.ptr, 3) };
+    assert!(allocated().is_empty());
+}
+
+#[test]
+fn test_platform_owned_update() {
+    let mem = memory_manager();
+    // string of the parameter, passed by 1C
+    let mut text = os_string("from 1C");
+    let mut variant = TVariant {
+        value: VariantValue {
+            data_str: DataStr {
+                ptr: text.as_mut_ptr(),
+                len: text.len() as u32,
+            },
+        },
+        elements: 0,
+        vt: VariantType::WStr,
+    };
+
+    // memory of 1C is not freed by the fake manager, as it would panic
+    variant
+        .update_platform_owned(mem, ParamValue::I32(1))
+        .unwrap();
+    assert_eq!(variant.vt, VariantType::Int32);
+    assert!(allocated().is_empty());
+
+    variant
+        .update_platform_owned(mem, ParamValue::String(os_string("out")))
+        .unwrap();
+    assert_eq!(variant.as_str(), Some(os_string("out").as_slice()));
+    assert_eq!(allocated(), [6]);
+    unsafe { PlatformString::from_raw(mem, variant.value.data_str.ptr, 3) };
+}
+
+#[test]
+fn test_allocation_error() {
+    let mem = memory_manager();
+    let mut variant = TVariant::default();
+
+    let result = variant.update_platform_owned(
+        mem,
+        ParamValue::Blob(vec![0; MAX_ALLOCATION + 1]),
+    );
+    assert!(result.is_err());
+    assert_eq!(variant.vt, VariantType::Empty);
+    assert!(PlatformBlob::new_in(mem, MAX_ALLOCATION + 1).is_err());
+}
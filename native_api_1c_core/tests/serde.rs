@@ -0,0 +1,130 @@
+#![cfg(feature = "serde")]
+
+use native_api_1c_core::{
+    ffi::{provided_types::Tm, string_utils::os_string},
+    interface::{ParamValue, ParamValues},
+};
+use serde_json::json;
+
+fn date(year: i32, month: i32, day: i32, hour: i32, min: i32, sec: i32) -> Tm {
+    Tm::from_fields(year, month, day, hour, min, sec)
+}
+
+#[test]
+fn test_param_value_representation() {
+    let cases = [
+        (ParamValue::Empty, json!({"type": "Empty"})),
+        (
+            ParamValue::Bool(true),
+            json!({"type": "Bool", "value": true}),
+        ),
+        (ParamValue::I32(-42), json!({"type": "I32", "value": -42})),
+        (ParamValue::F64(1.5), json!({"type": "F64", "value": 1.5})),
+        (
+            ParamValue::Date(date(2024, 2, 29, 12, 30, 15)),
+            json!({"type": "Date", "value": "2024-02-29T12:30:15"}),
+        ),
+        (
+            ParamValue::String(os_string("Привет, 1C")),
+            json!({"type": "String", "value": "Привет, 1C"}),
+        ),
+        (
+            ParamValue::Blob(vec![0, 1, 2, 255]),
+            json!({"type": "Blob", "value": "AAEC/w=="}),
+        ),
+    ];
+
+    for (value, expected) in cases {
+        let serialized = serde_json::to_value(&value).unwrap();
+        assert_eq!(serialized, expected);
+
+        let deserialized: ParamValue =
+            serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, value);
+    }
+}
+
+#[test]
+fn test_param_values_round_trip() {
+    let values = ParamValues::new(vec![
+        ParamValue::I32(1),
+        ParamValue::String(os_string("two")),
+        ParamValue::Date(date(1999, 12, 31, 23, 59, 59)),
+        ParamValue::Empty,
+    ]);
+
+    let json = serde_json::to_string(&values).unwrap();
+    let deserialized: ParamValues = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.len(), values.len());
+    for (actual, expected) in deserialized.iter().zip(values.iter()) {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_date_fields() {
+    let cases = [
+        ("1970-01-01T00:00:00", date(1970, 1, 1, 0, 0, 0)),
+        ("2000-03-01T01:02:03", date(2000, 3, 1, 1, 2, 3)),
+        ("2023-12-31T23:59:59", date(2023, 12, 31, 23, 59, 59)),
+        ("0001-01-01T00:00:00", date(1, 1, 1, 0, 0, 0)),
+        ("3999-12-31T00:00:00", date(3999, 12, 31, 0, 0, 0)),
+    ];
+
+    for (iso, expected) in cases {
+        let tm: Tm = serde_json::from_value(json!(iso)).unwrap();
+        assert_eq!(tm, expected, "{iso}");
+        assert_eq!(serde_json::to_value(tm).unwrap(), json!(iso));
+    }
+}
+
+#[test]
+fn test_date_offset() {
+    let tm: Tm =
+        serde_json::from_value(json!("2024-02-29T12:30:15+03:30")).unwrap();
+    assert_eq!(tm.hour, 12);
+
+    #[cfg(target_family = "unix")]
+    {
+        assert_eq!(tm.gmtoff, 3 * 3600 + 30 * 60);
+        assert_eq!(
+            serde_json::to_value(tm).unwrap(),
+            json!("2024-02-29T12:30:15+03:30")
+        );
+    }
+
+    let tm: Tm = serde_json::from_value(json!("2024-02-29T12:30:15Z")).unwrap();
+    assert_eq!(tm, date(2024, 2, 29, 12, 30, 15));
+}
+
+#[test]
+fn test_invalid_values() {
+    let invalid_dates = [
+        "2023-02-29T00:00:00",
+        "2024-13-01T00:00:00",
+        "2024-01-01 00:00:00",
+        "2024-01-01T24:00:00",
+        "2024-01-01",
+        "2024-01-01T00:00:00+3:00",
+        "+024-01-01T00:00:00",
+    ];
+    for invalid in invalid_dates {
+        assert!(
+            serde_json::from_value::<Tm>(json!(invalid)).is_err(),
+            "{invalid}"
+        );
+    }
+
+    let invalid_values = [
+        json!({"type": "Blob", "value": "not base64!"}),
+        json!({"type": "I32", "value": "1"}),
+        json!({"type": "Unknown"}),
+        json!({"value": 1}),
+    ];
+    for invalid in invalid_values {
+        assert!(
+            serde_json::from_value::<ParamValue>(invalid.clone()).is_err(),
+            "{invalid}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+};
+
+use native_api_1c_core::shared::{self, Shared, SharedKey};
+
+const KEY: SharedKey = SharedKey::named("tests");
+
+struct Pool(u32);
+
+struct Settings(&'static str);
+
+#[test]
+fn test_acquire_shares_value() {
+    let first = shared::acquire(KEY, || Pool(1));
+    let second = shared::acquire(KEY, || Pool(2));
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(second.0, 1);
+
+    drop(first);
+    assert!(shared::get::<Pool>(KEY).is_some());
+    drop(second);
+    assert!(shared::get::<Pool>(KEY).is_none());
+
+    // value is created again after the last one is dropped
+    assert_eq!(shared::acquire(KEY, || Pool(3)).0, 3);
+}
+
+#[test]
+fn test_shared_field() {
+    let mut field = Shared::<Settings>::new();
+    assert!(field.get().is_none());
+
+    field.acquire(KEY, || Settings("ru"));
+    let mut other = Shared::<Settings>::default();
+    other.acquire(KEY, || Settings("en"));
+    assert_eq!(field.0, "ru");
+    assert_eq!(other.0, "ru");
+}
+
+#[test]
+fn test_keys_dont_collide() {
+    struct Config(u32);
+    struct FirstAddIn;
+    struct SecondAddIn;
+
+    let first = shared::acquire(SharedKey::owner::<FirstAddIn>(), || Config(1));
+    let second =
+        shared::acquire(SharedKey::owner::<SecondAddIn>(), || Config(2));
+    let named = shared::acquire(KEY, || Config(3));
+    assert_eq!((first.0, second.0, named.0), (1, 2, 3));
+    assert!(Arc::ptr_eq(
+        &first,
+        &shared::get(SharedKey::owner::<FirstAddIn>()).unwrap()
+    ));
+}
+
+#[test]
+fn test_acquire_from_threads() {
+    struct Counter;
+
+    let barrier = Arc::new(Barrier::new(8));
+    let values: Vec<Arc<Counter>> = (0..8)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                shared::acquire(KEY, || Counter)
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert!(values.iter().all(|value| Arc::ptr_eq(value, &values[0])));
+}
+
+#[test]
+#[should_panic(expected = "after AddIn is initialized")]
+fn test_deref_before_acquire() {
+    let field = Shared::<Settings>::new();
+    let _ = field.0;
+}
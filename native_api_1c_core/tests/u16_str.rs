@@ -0,0 +1,74 @@
+use native_api_1c_core::{
+    ffi::{
+        provided_types::{DataStr, TVariant, VariantType, VariantValue},
+        string_utils::os_string,
+    },
+    interface::{ParamValue, ParamValues, U16Str, U16String},
+};
+
+fn str_variant(value: &mut [u16]) -> TVariant {
+    TVariant {
+        value: VariantValue {
+            data_str: DataStr {
+                ptr: value.as_mut_ptr(),
+                len: value.len() as u32,
+            },
+        },
+        elements: 0,
+        vt: VariantType::WStr,
+    }
+}
+
+#[test]
+fn test_u16_string() {
+    let mut value = U16String::from("Привет");
+    value.push_str(", мир");
+    value.push_u16_str(U16Str::from_slice(&os_string("!")));
+    assert_eq!(value.len(), 12);
+    assert_eq!(value.to_string(), "Привет, мир!");
+    assert_eq!(value.as_slice(), os_string("Привет, мир!").as_slice());
+    assert_eq!(
+        ParamValue::from(value.clone()),
+        ParamValue::String(os_string("Привет, мир!"))
+    );
+    assert_eq!(
+        U16String::try_from(ParamValue::String(os_string("Привет, мир!"))),
+        Ok(value)
+    );
+}
+
+#[test]
+fn test_invalid_utf16() {
+    let value = U16Str::from_slice(&[0x61, 0xd800, 0x62]);
+    assert_eq!(value.to_string_lossy(), "a\u{fffd}b");
+    assert_eq!(value.to_string(), "a\u{fffd}b");
+    assert!(value.to_string_checked().is_err());
+}
+
+#[test]
+fn test_params_borrow_strings() {
+    let mut buffer = os_string("text");
+    let variants = [str_variant(&mut buffer), TVariant::default()];
+    let buffer_ptr = variants[0].as_str().unwrap().as_ptr();
+
+    let params = ParamValues::from_variants(&variants);
+    let str = params.get_str(0).unwrap();
+    assert_eq!(str.as_slice().as_ptr(), buffer_ptr);
+    assert_eq!(str.to_string(), "text");
+    assert_eq!(params.get_str(1), None);
+    assert_eq!(params[0], ParamValue::String(os_string("text")));
+    assert_eq!(params[1], ParamValue::Empty);
+}
+
+#[test]
+fn test_params_write_back() {
+    let mut first = os_string("first");
+    let mut second = os_string("second");
+    let variants = [str_variant(&mut first), str_variant(&mut second)];
+
+    let mut params = ParamValues::from_variants(&variants);
+    assert_eq!(params[0], ParamValue::String(os_string("first")));
+    params[1].set_str(os_string("changed"));
+    assert_eq!(params.get_str(1).unwrap().to_string(), "changed");
+    assert_eq!(params.iter().len(), 2);
+}
@@ -0,0 +1,213 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, FnArg, ImplItem, ItemImpl, Pat};
+
+use crate::derive_addin::{
+    description,
+    functions::{
+        collectors::method_definitions, parse::self_param_desc, FuncArgumentDesc, FuncDesc,
+        FuncParamType, ParamMode,
+    },
+    parsers::UniqueNames,
+    utils::macros::tkn_err,
+};
+
+/// Attributes, that are used by `#[add_in_methods]` and have to be removed from
+/// methods, as they are not known to the compiler outside of the macro
+const HELPER_ATTRS: &[&str] = &["add_in_func", "arg", "returns"];
+
+pub fn add_in_methods(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+
+    let result = if attr.is_empty() {
+        build_methods_impl(&item_impl)
+    } else {
+        tkn_err!(
+            "`add_in_methods` does not take any arguments",
+            &TokenStream::from(attr)
+        )
+    };
+    strip_helper_attrs(&mut item_impl);
+
+    match result {
+        Ok(methods_impl) => quote! {
+            #item_impl
+            #methods_impl
+        },
+        Err(darling_error) => {
+            let error_tokens = darling_error.write_errors();
+            let stub_impl = stub_methods_impl(&item_impl);
+            quote! {
+                #item_impl
+                #stub_impl
+                #error_tokens
+            }
+        }
+    }
+    .into()
+}
+
+fn build_methods_impl(item_impl: &ItemImpl) -> Result<TokenStream, darling::Error> {
+    let mut functions = vec![];
+    for item in &item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let has_add_in_func_attr = method
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("add_in_func"));
+        if !has_add_in_func_attr {
+            continue;
+        }
+
+        let mut inputs = method.sig.inputs.iter().peekable();
+        let self_param = match inputs.peek() {
+            Some(FnArg::Receiver(receiver)) => {
+                if receiver.reference.is_none() {
+                    return tkn_err!(
+                        "AddIn methods must take `self` by reference",
+                        &receiver.span()
+                    );
+                }
+                Some(FuncArgumentDesc {
+                    ty: FuncParamType::SelfType,
+                    default: None,
+                    mode: match receiver.mutability {
+                        Some(_) => ParamMode::InOut,
+                        None => ParamMode::In,
+                    },
+                })
+            }
+            Some(FnArg::Typed(pat_type)) => self_param_desc(&pat_type.ty),
+            None => None,
+        };
+        if self_param.is_some() {
+            inputs.next();
+        }
+
+        let (arg_names, arg_types) = inputs
+            .map(|input| match input {
+                FnArg::Typed(pat_type) => {
+                    let name = match pat_type.pat.as_ref() {
+                        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                        _ => None,
+                    };
+                    Ok((name, pat_type.ty.as_ref()))
+                }
+                FnArg::Receiver(receiver) => {
+                    tkn_err!("Unexpected `self` argument", &receiver.span())
+                }
+            })
+            .collect::<Result<Vec<(Option<String>, &syn::Type)>, darling::Error>>()?
+            .into_iter()
+            .unzip();
+
+        let method_ident = &method.sig.ident;
+        functions.push(FuncDesc::from_parts(
+            method_ident,
+            &method.attrs,
+            self_param,
+            arg_names,
+            arg_types,
+            &method.sig.output,
+            quote! { Self::#method_ident },
+        )?);
+    }
+
+    let mut names = UniqueNames::default();
+    let mut errors = darling::Error::accumulator();
+    for function in &functions {
+        errors.handle(names.check("method", &function.names));
+    }
+    errors.finish()?;
+
+    let func_definitions = method_definitions(&functions)?;
+    let method_descriptions = description::method_descriptions(&functions);
+
+    let method_names = functions
+        .iter()
+        .flat_map(|function| &function.names)
+        .map(|str_lit| str_lit.value().to_lowercase());
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics native_api_1c::native_api_1c_core::interface::AddInMethods
+            for #self_ty #where_clause
+        {
+            const METHOD_NAMES: &'static [&'static str] = &[#(#method_names),*];
+
+            #(#func_definitions)*
+
+            fn method_descriptions(
+            ) -> Vec<native_api_1c::native_api_1c_core::description::MethodDescription> {
+                #method_descriptions
+            }
+        }
+    })
+}
+
+/// Implementation without methods, emitted along with errors, so that they are
+/// not followed by errors of the AddIn, that requires `AddInMethods`
+fn stub_methods_impl(item_impl: &ItemImpl) -> TokenStream {
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+    let interface = quote! { native_api_1c::native_api_1c_core::interface };
+    quote! {
+        impl #impl_generics #interface::AddInMethods for #self_ty #where_clause {
+            fn get_n_methods(&self) -> usize {
+                0
+            }
+            fn find_method(&self, _name: &[u16]) -> Option<usize> {
+                None
+            }
+            fn get_method_name(&self, _num: usize, _alias: usize) -> Option<Vec<u16>> {
+                None
+            }
+            fn get_n_params(&self, _num: usize) -> usize {
+                0
+            }
+            fn get_param_def_value(
+                &self,
+                _method_num: usize,
+                _param_num: usize,
+            ) -> Option<#interface::ParamValue> {
+                None
+            }
+            fn has_ret_val(&self, _method_num: usize) -> bool {
+                false
+            }
+            fn call_as_proc(
+                &mut self,
+                _method_num: usize,
+                _params: &mut #interface::ParamValues,
+            ) -> #interface::AddInWrapperResult<()> {
+                Err(())
+            }
+            fn call_as_func(
+                &mut self,
+                _method_num: usize,
+                _params: &mut #interface::ParamValues,
+            ) -> #interface::AddInWrapperResult<#interface::ParamValue> {
+                Err(())
+            }
+        }
+    }
+}
+
+fn strip_helper_attrs(item_impl: &mut ItemImpl) {
+    for item in &mut item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        method.attrs.retain(|attr| {
+            !HELPER_ATTRS
+                .iter()
+                .any(|helper| attr.path().is_ident(helper))
+        });
+    }
+}
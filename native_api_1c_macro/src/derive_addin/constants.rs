@@ -4,6 +4,9 @@ pub const F64_TYPE: &str = "Float";
 pub const STRING_TYPE: &str = "Str";
 pub const DATE_TYPE: &str = "Date";
 pub const BLOB_TYPE: &str = "Blob";
+pub const JSON_TYPE: &str = "Json";
+pub const U16STR_TYPE: &str = "U16Str";
+pub const HANDLE_TYPE: &str = "Handle";
 pub const UNTYPED_TYPE: &str = "None";
 
 pub const ALL_RETURN_TYPES: &[&str] = &[
@@ -13,6 +16,9 @@ pub const ALL_RETURN_TYPES: &[&str] = &[
     STRING_TYPE,
     DATE_TYPE,
     BLOB_TYPE,
+    JSON_TYPE,
+    U16STR_TYPE,
+    HANDLE_TYPE,
     UNTYPED_TYPE,
 ];
 pub const ALL_ARG_TYPES: &[&str] = &[
@@ -22,4 +28,7 @@ pub const ALL_ARG_TYPES: &[&str] = &[
     STRING_TYPE,
     DATE_TYPE,
     BLOB_TYPE,
+    JSON_TYPE,
+    U16STR_TYPE,
+    HANDLE_TYPE,
 ];
@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::{
+    functions::{FuncDesc, FuncParamType, ParamMode},
+    parsers::ParamType,
+    props::PropDesc,
+    utils::expr_to_os_value,
+};
+
+/// Expression of `Vec<PropDescription>` of AddIn properties
+pub fn prop_descriptions(props: &[PropDesc]) -> TokenStream {
+    let descriptions = props.iter().map(|prop| {
+        let name = &prop.name_literal;
+        let name_ru = &prop.name_ru_literal;
+        let doc = &prop.doc;
+        let ty = value_type(Some(&prop.ty));
+        let readable = prop.readable;
+        let writable = prop.writable;
+        quote! {
+            native_api_1c::native_api_1c_core::description::PropDescription {
+                name: #name,
+                name_ru: #name_ru,
+                doc: #doc,
+                ty: #ty,
+                readable: #readable,
+                writable: #writable,
+            }
+        }
+    });
+    quote! { vec![#(#descriptions),*] }
+}
+
+/// Expression of `Vec<MethodDescription>` of AddIn functions
+pub fn method_descriptions(functions: &[FuncDesc]) -> TokenStream {
+    let descriptions = functions.iter().map(|function| {
+        let name = &function.name_literal;
+        let name_ru = &function.name_ru_literal;
+        let doc = &function.doc;
+        let params = param_descriptions(function);
+        let returns = match (&function.return_value.ty, function.raw) {
+            (_, Some(raw)) if !raw.returns => quote! { None },
+            (_, Some(_)) => {
+                let ty = value_type(None);
+                quote! { Some(#ty) }
+            }
+            (Some(ty), None) => {
+                let ty = value_type(Some(ty));
+                quote! { Some(#ty) }
+            }
+            (None, None) => quote! { None },
+        };
+        quote! {
+            native_api_1c::native_api_1c_core::description::MethodDescription {
+                name: #name,
+                name_ru: #name_ru,
+                doc: #doc,
+                params: vec![#(#params),*],
+                returns: #returns,
+            }
+        }
+    });
+    quote! { vec![#(#descriptions),*] }
+}
+
+/// Descriptions of parameters, passed by 1C, variadic argument is described
+/// as `max` optional parameters
+fn param_descriptions(function: &FuncDesc) -> Vec<TokenStream> {
+    if let Some(raw) = function.raw {
+        let param = param_description(None, value_type(None), None, false, true);
+        return vec![param; raw.n_params];
+    }
+
+    let mut params = vec![];
+    let names = function.param_names.iter().map(Option::as_deref);
+    for (arg, name) in function.get_1c_params().into_iter().zip(names) {
+        let by_ref = arg.mode != ParamMode::In;
+        match &arg.ty {
+            FuncParamType::PlatformType(ty) => {
+                let default = arg
+                    .default
+                    .as_ref()
+                    .map(|expr| expr_to_os_value(expr, ty, true));
+                params.push(param_description(
+                    name,
+                    value_type(Some(ty)),
+                    default,
+                    false,
+                    by_ref,
+                ));
+            }
+            FuncParamType::Variadic(ty, max) => {
+                let param = param_description(name, value_type(ty.as_ref()), None, true, false);
+                params.extend(std::iter::repeat_n(param, *max));
+            }
+            FuncParamType::Resolved { .. } => {
+                let ty = value_type(Some(&ParamType::Handle));
+                params.push(param_description(name, ty, None, false, false));
+            }
+            FuncParamType::SelfType => {}
+        }
+    }
+    params
+}
+
+fn param_description(
+    name: Option<&str>,
+    ty: TokenStream,
+    default: Option<TokenStream>,
+    optional: bool,
+    by_ref: bool,
+) -> TokenStream {
+    let name = match name {
+        Some(name) => quote! { Some(#name) },
+        None => quote! { None },
+    };
+    let default = match default {
+        Some(default) => quote! { Some(#default) },
+        None => quote! { None },
+    };
+    quote! {
+        native_api_1c::native_api_1c_core::description::ParamDescription {
+            name: #name,
+            ty: #ty,
+            default: #default,
+            optional: #optional,
+            by_ref: #by_ref,
+        }
+    }
+}
+
+/// `ValueType` of the parameter type, `Any` for `ParamValue`
+fn value_type(ty: Option<&ParamType>) -> TokenStream {
+    let variant = match ty {
+        Some(ParamType::Bool) => quote! { Bool },
+        Some(ParamType::I32 | ParamType::F64 | ParamType::Handle) => quote! { Number },
+        Some(ParamType::String | ParamType::Json | ParamType::U16Str) => quote! { String },
+        Some(ParamType::Date) => quote! { Date },
+        Some(ParamType::Blob) => quote! { Blob },
+        None => quote! { Any },
+    };
+    quote! { native_api_1c::native_api_1c_core::description::ValueType::#variant }
+}
@@ -22,7 +22,7 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for CallAsFuncCollector {
     fn from_iter<T: IntoIterator<Item = (usize, &'a FuncDesc)>>(iter: T) -> Self {
         let mut body = TokenStream::new();
         for (func_index, func_desc) in iter {
-            if func_desc.return_value.ty.is_none() {
+            if !func_desc.has_ret_val() {
                 // Skip functions without return value
                 continue;
             }
@@ -24,6 +24,7 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for FindMethodCollector {
         for (func_index, func_desc) in iter {
             let name_literal = func_desc.name_literal.clone();
             let name_ru_literal = func_desc.name_ru_literal.clone();
+            let alias_names = func_desc.aliases.iter().map(|(_, name)| name);
 
             find_method_body.extend(quote! {
                 if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_literal) == name { 
@@ -32,6 +33,9 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for FindMethodCollector {
                 if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_ru_literal) == name { 
                     return Some(#func_index) 
                 };
+                #(if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#alias_names) == name {
+                    return Some(#func_index)
+                };)*
             });
         }
 
@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::derive_addin::functions::FuncDesc;
+use crate::derive_addin::{functions::FuncDesc, utils::localized_name};
 
 use super::{empty_func_collector_error, FunctionCollector};
 
@@ -22,19 +22,16 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for GetMethodNameCollector {
         let mut get_func_name_body = TokenStream::new();
 
         for (func_index, func_desc) in iter {
-            let name_literal = func_desc.name_literal.clone();
-            let name_ru_literal = func_desc.name_ru_literal.clone();
+            let name = localized_name(
+                &func_desc.name_literal,
+                &func_desc.name_ru_literal,
+                &func_desc.aliases,
+            );
 
             get_func_name_body.extend(quote! {
-                #get_func_name_body
-                if num == #func_index && alias == 0 {
-                    return Some(native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(
-                        #name_literal).into()
-                    )
-                };
                 if num == #func_index {
                     return Some(native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(
-                        #name_ru_literal).into()
+                        #name).into()
                     )
                 };
             });
@@ -22,7 +22,7 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for GetNParamsCollector {
         let mut body = TokenStream::new();
 
         for (func_index, func_desc) in iter {
-            let number_of_params = func_desc.get_1c_params().len();
+            let number_of_params = func_desc.get_n_1c_params();
             body.extend(quote! {
                 if num == #func_index { return #number_of_params };
             });
@@ -25,6 +25,17 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for GetParamDefValueCollector {
         for (func_index, func_desc) in iter {
             let mut func_body = quote! {};
             for (arg_index, arg_desc) in func_desc.get_1c_params().iter().enumerate() {
+                if let FuncParamType::Variadic(_, max) = arg_desc.ty {
+                    // Values of variadic argument are optional, so 1C can omit them
+                    let last_index = arg_index + max;
+                    func_body.extend(quote! {
+                        if (#arg_index..#last_index).contains(&param_num) {
+                            return Some(native_api_1c::native_api_1c_core::interface::ParamValue::Empty);
+                        }
+                    });
+                    continue;
+                }
+
                 let Some(expr) = &arg_desc.default else {
                     // Skip parameters without default value
                     continue;
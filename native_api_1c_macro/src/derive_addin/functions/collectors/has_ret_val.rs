@@ -22,7 +22,7 @@ impl<'a> FromIterator<(usize, &'a FuncDesc)> for HasReturnValueCollector {
         let mut body = TokenStream::new();
 
         for (func_index, func_desc) in iter {
-            let has_ret_val = func_desc.return_value.ty.is_some();
+            let has_ret_val = func_desc.has_ret_val();
             body.extend(quote! {
                 if method_num == #func_index { return #has_ret_val };
             });
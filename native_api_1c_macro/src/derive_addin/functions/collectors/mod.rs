@@ -25,6 +25,23 @@ pub trait FunctionCollector<'a>: FromIterator<(usize, &'a FuncDesc)> + Default {
     fn release(self) -> Result<TokenStream, darling::Error>;
 }
 
+/// Generates all `AddInWrapper` methods, related to AddIn functions
+pub fn method_definitions(functions: &[FuncDesc]) -> Result<Vec<TokenStream>, darling::Error> {
+    let fi = functions.iter().enumerate();
+    Ok(vec![
+        fi.clone().collect::<FindMethodCollector>().release()?,
+        fi.clone().collect::<GetMethodNameCollector>().release()?,
+        fi.clone().collect::<GetNMethodsCollector>().release()?,
+        fi.clone().collect::<GetNParamsCollector>().release()?,
+        fi.clone().collect::<HasReturnValueCollector>().release()?,
+        fi.clone().collect::<CallAsProcCollector>().release()?,
+        fi.clone().collect::<CallAsFuncCollector>().release()?,
+        fi.clone()
+            .collect::<GetParamDefValueCollector>()
+            .release()?,
+    ])
+}
+
 pub fn empty_func_collector_error() -> darling::Error {
     tkn_err_inner!("No functions found", &proc_macro2::Span::call_site())
 }
@@ -0,0 +1,150 @@
+use base64::Engine;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::derive_addin::parsers::ParamType;
+
+use super::parse::ErrorConvertingMeta;
+
+const HEX_PREFIX: &str = "hex:";
+const BASE64_PREFIX: &str = "base64:";
+
+/// Converts expression, passed as `default = ...`, into tokens of the value, that
+/// can be converted into `ParamValue` of the argument type. Literals of `Date` and
+/// `Blob` types are parsed and validated at compile time, other expressions are
+/// passed as is
+pub fn default_value_tokens(
+    ty: &ParamType,
+    expr: &syn::Expr,
+) -> Result<TokenStream, ErrorConvertingMeta> {
+    let syn::Expr::Lit(syn::ExprLit { lit, .. }) = expr else {
+        return Ok(expr.to_token_stream());
+    };
+
+    match (ty, lit) {
+        (ParamType::Date, syn::Lit::Str(str_lit)) => {
+            let date_time = parse_date_time(&str_lit.value())
+                .map_err(|err| ErrorConvertingMeta::InvalidDefault(str_lit.span(), err))?;
+            Ok(date_time_tokens(&date_time))
+        }
+        (ParamType::Blob, syn::Lit::ByteStr(byte_str_lit)) => {
+            let bytes = byte_str_lit.value();
+            Ok(quote! { vec![#(#bytes),*] })
+        }
+        (ParamType::Blob, syn::Lit::Str(str_lit)) => {
+            let bytes = parse_blob(&str_lit.value())
+                .map_err(|err| ErrorConvertingMeta::InvalidDefault(str_lit.span(), err))?;
+            Ok(quote! { vec![#(#bytes),*] })
+        }
+        (ParamType::Date | ParamType::Blob, _) => Err(ErrorConvertingMeta::InvalidDefault(
+            lit.span(),
+            format!("unexpected literal for `{ty}` default value"),
+        )),
+        _ => Ok(expr.to_token_stream()),
+    }
+}
+
+/// Date with time and offset from UTC in seconds
+struct DefaultDateTime {
+    date_time: NaiveDateTime,
+    offset: i32,
+}
+
+/// Parses ISO-8601 date, date with time, or date with time and offset
+fn parse_date_time(value: &str) -> Result<DefaultDateTime, String> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(DefaultDateTime {
+            date_time: date_time.naive_local(),
+            offset: date_time.offset().local_minus_utc(),
+        });
+    }
+
+    let date_time = if value.len() > "YYYY-MM-DD".len() {
+        let format = if value.contains('T') {
+            "%Y-%m-%dT%H:%M:%S"
+        } else {
+            "%Y-%m-%d %H:%M:%S"
+        };
+        NaiveDateTime::parse_from_str(value, format)
+    } else {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+    }
+    .map_err(|err| {
+        format!(
+            "invalid ISO-8601 date `{value}`: {err}, expected `YYYY-MM-DD`, \
+            `YYYY-MM-DDThh:mm:ss` or `YYYY-MM-DDThh:mm:ss+hh:mm`"
+        )
+    })?;
+
+    Ok(DefaultDateTime {
+        date_time,
+        offset: 0,
+    })
+}
+
+fn date_time_tokens(value: &DefaultDateTime) -> TokenStream {
+    let date_time = &value.date_time;
+    let sec = date_time.second() as i32;
+    let min = date_time.minute() as i32;
+    let hour = date_time.hour() as i32;
+    let mday = date_time.day() as i32;
+    let mon = date_time.month0() as i32;
+    let year = date_time.year() - 1900;
+    let wday = date_time.weekday().num_days_from_sunday() as i32;
+    let yday = date_time.ordinal0() as i32;
+    let offset = value.offset;
+
+    quote! {
+        native_api_1c::native_api_1c_core::ffi::provided_types::Tm {
+            sec: #sec,
+            min: #min,
+            hour: #hour,
+            mday: #mday,
+            mon: #mon,
+            year: #year,
+            wday: #wday,
+            yday: #yday,
+            #[cfg(target_family = "unix")]
+            gmtoff: #offset as std::ffi::c_long,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parses blob literal, that is either hex or base64 encoded with a prefix
+fn parse_blob(value: &str) -> Result<Vec<u8>, String> {
+    if let Some(hex) = value.strip_prefix(HEX_PREFIX) {
+        return parse_hex(hex);
+    }
+    if let Some(base64) = value.strip_prefix(BASE64_PREFIX) {
+        return base64::engine::general_purpose::STANDARD
+            .decode(base64)
+            .map_err(|err| format!("invalid base64 blob: {err}"));
+    }
+    Err(format!(
+        "blob default must be a byte string `b\"...\"`, or a string with \
+        `{HEX_PREFIX}` or `{BASE64_PREFIX}` prefix"
+    ))
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!(
+            "invalid hex blob: odd number of digits ({})",
+            digits.len()
+        ));
+    }
+
+    digits
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let byte: String = pair.iter().collect();
+            u8::from_str_radix(&byte, 16)
+                .map_err(|_| format!("invalid hex blob: `{byte}` at byte {i} is not a hex number"))
+        })
+        .collect()
+}
@@ -2,12 +2,19 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::Ident;
 
-use crate::derive_addin::utils::{expr_from_os_value, expr_to_os_value};
+use crate::derive_addin::{
+    parsers::ParamType,
+    utils::{expr_from_os_value, expr_to_os_value, report_error_tkn},
+};
 
-use super::{FuncArgumentDesc, FuncDesc, FuncParamType};
+use super::{FuncArgumentDesc, FuncDesc, FuncParamType, ParamMode};
 
 pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
-    let func_ident = func.ident.clone();
+    let callee = &func.callee;
+
+    if func.raw.is_some() {
+        return raw_func_call_tkn(func, set_to);
+    }
 
     let mut pre_call = quote! {};
     let mut func_args = quote! {};
@@ -17,7 +24,7 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
         let param_ident = Ident::new(&format!("param_{}", param_index + 1), Span::call_site());
 
         let (pre_call_param, post_call_param) =
-            gen_param_prep(param_desc, param_index, &param_ident);
+            gen_param_prep(param_desc, param_index, &param_ident, &func.name_literal);
 
         if func_args.is_empty() {
             func_args.extend(quote! {#param_ident})
@@ -38,21 +45,40 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
     }
 
     let mut func_call = quote! {
-        let call_result = (self.#func_ident)(#func_args);
+        let call_result = #callee(#func_args);
     };
 
     if func.return_value.result {
+        let report_error = report_error_tkn(&quote! { &err }, &func.name_literal);
         func_call.extend(quote! {
-            if call_result.is_err() {
-                return Err(());
-            }
-            let call_result = call_result.unwrap();
+            let call_result = match call_result {
+                Ok(value) => value,
+                Err(err) => {
+                    #report_error
+                    return Err(());
+                }
+            };
         });
     };
 
     if let Some(set_to) = set_to {
         let return_ty = func.return_value.ty.clone().unwrap();
-        let result_wrap = expr_to_os_value(&quote! { call_result }, &return_ty, true);
+        let result_wrap = match return_ty {
+            ParamType::Json => json_to_param_tkn(
+                &quote! { &call_result },
+                quote! { None },
+                &func.name_literal,
+            ),
+            // moved without copying
+            ParamType::U16Str => quote! {
+                #return_ty(::std::vec::Vec::<u16>::from(call_result))
+            },
+            // moved without copying, `PlatformBlob` is passed to 1C as is
+            ParamType::Blob => quote! {
+                <native_api_1c::native_api_1c_core::interface::ParamValue as ::core::convert::From<_>>::from(call_result)
+            },
+            _ => expr_to_os_value(&quote! { call_result }, &return_ty, true),
+        };
         func_call.extend(quote! {
             let #set_to
         });
@@ -67,37 +93,250 @@ pub fn func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
     }
 }
 
+/// `raw` function gets `ParamValues` as is, and its result is returned to 1C
+/// without conversion, error is reported like the one of other functions
+fn raw_func_call_tkn(func: &FuncDesc, set_to: Option<&Ident>) -> TokenStream {
+    let callee = &func.callee;
+    let func_args = if func.has_self_param() {
+        quote! { self, params }
+    } else {
+        quote! { params }
+    };
+
+    let set_to = match set_to {
+        Some(set_to) => quote! { let #set_to = },
+        None => quote! { let _ = },
+    };
+    let report_error = report_error_tkn(&quote! { &err }, &func.name_literal);
+    quote! {
+        #set_to match #callee(#func_args) {
+            Ok(value) => value,
+            Err(err) => {
+                #report_error
+                return Err(());
+            }
+        };
+    }
+}
+
 fn gen_param_prep(
     param: &FuncArgumentDesc,
     param_index: usize,
     param_ident: &Ident,
+    source: &TokenStream,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let FuncParamType::PlatformType(param_ty) = &param.ty else {
-        panic!("SelfType is not allowed here");
+    let param_ty = match &param.ty {
+        FuncParamType::PlatformType(param_ty) => param_ty,
+        FuncParamType::Variadic(elem_ty, max) => {
+            return (
+                gen_variadic_prep(elem_ty.as_ref(), *max, param_index, param_ident),
+                quote! {},
+            );
+        }
+        FuncParamType::Resolved { registry, mutable } => {
+            return (
+                gen_resolved_prep(registry, *mutable, param_index, param_ident, source),
+                quote! {},
+            );
+        }
+        FuncParamType::SelfType => panic!("SelfType is not allowed here"),
     };
 
-    let param_unwrap = expr_from_os_value(&quote! { params[#param_index]}, param_ty);
-    let mut pre_call = quote! {;
-        let #param_ident = #param_unwrap;
-        let mut #param_ident = #param_ident.clone().into();
-    };
-    if param.out_param {
-        pre_call.extend(quote! {
+    let pre_call = match param.mode {
+        ParamMode::In => {
+            let param_value = param_value_tkn(param_ty, param_index, source);
+            quote! {
+                let #param_ident = #param_value;
+            }
+        }
+        // incoming value is ignored, so it may be of any type, e.g. `Empty`
+        ParamMode::Out => quote! {
+            let mut #param_ident = Default::default();
             let #param_ident = &mut #param_ident;
-        });
-    }
+        },
+        ParamMode::InOut => {
+            let param_value = param_value_tkn(param_ty, param_index, source);
+            quote! {
+                let mut #param_ident = #param_value;
+                let #param_ident = &mut #param_ident;
+            }
+        }
+    };
 
-    let post_call = if !param.out_param {
-        quote! {}
-    } else {
-        let param_wrap = expr_to_os_value(&param_ident.to_token_stream(), param_ty, false);
-        let mut q = quote! {
-            params[#param_index]
-        };
-        q.extend(quote! { = });
-        q.extend(quote! { #param_wrap; });
-        q
+    let post_call = match param.mode {
+        ParamMode::In => quote! {},
+        ParamMode::Out | ParamMode::InOut if *param_ty == ParamType::Json => {
+            let param_wrap = json_to_param_tkn(
+                &quote! { &*#param_ident },
+                quote! { Some(#param_index) },
+                source,
+            );
+            quote! {
+                params[#param_index] = #param_wrap;
+            }
+        }
+        // moved without copying, as argument is not used after the call
+        ParamMode::Out | ParamMode::InOut if *param_ty == ParamType::U16Str => {
+            let param_wrap = quote! { #param_ty };
+            quote! {
+                params[#param_index] = #param_wrap(::std::vec::Vec::<u16>::from(
+                    ::core::mem::take(#param_ident)
+                ));
+            }
+        }
+        ParamMode::Out | ParamMode::InOut if *param_ty == ParamType::Blob => {
+            let param_wrap = quote! { #param_ty };
+            quote! {
+                params[#param_index] = #param_wrap(::core::convert::Into::into(
+                    ::core::mem::take(#param_ident)
+                ));
+            }
+        }
+        ParamMode::Out | ParamMode::InOut => {
+            let param_wrap = expr_to_os_value(&param_ident.to_token_stream(), param_ty, false);
+            quote! {
+                params[#param_index] = #param_wrap;
+            }
+        }
     };
 
     (pre_call, post_call)
 }
+
+/// Value of parameter, converted to the type of function argument
+fn param_value_tkn(param_ty: &ParamType, param_index: usize, source: &TokenStream) -> TokenStream {
+    match param_ty {
+        ParamType::Json => return json_from_param_tkn(param_index, source),
+        // borrowed from 1C, and only copied if argument is `U16String`
+        ParamType::U16Str => {
+            return quote! {
+                ::core::convert::From::from(params.get_str(#param_index).ok_or(())?)
+            }
+        }
+        // borrowed from 1C, and only copied if argument is `Vec<u8>`
+        ParamType::Blob => {
+            return quote! {
+                ::core::convert::From::from(params.get_blob(#param_index).ok_or(())?)
+            }
+        }
+        _ => {}
+    }
+
+    expr_from_os_value(&quote! { params[#param_index]}, param_ty)
+}
+
+/// Deserializes parameter from JSON, error is reported to 1C with `source`
+/// being the function name
+fn json_from_param_tkn(param_index: usize, source: &TokenStream) -> TokenStream {
+    let report_error = report_error_tkn(&quote! { &err }, source);
+    quote! {
+        match native_api_1c::native_api_1c_core::json::from_param(&params[#param_index], #param_index) {
+            Ok(value) => value,
+            Err(err) => {
+                #report_error
+                return Err(());
+            }
+        }
+    }
+}
+
+/// Serializes value to JSON, error is reported to 1C with `source` being the
+/// function name
+fn json_to_param_tkn(
+    value: &TokenStream,
+    param_index: TokenStream,
+    source: &TokenStream,
+) -> TokenStream {
+    let report_error = report_error_tkn(&quote! { &err }, source);
+    quote! {
+        match native_api_1c::native_api_1c_core::json::to_param(#value, #param_index) {
+            Ok(value) => value,
+            Err(err) => {
+                #report_error
+                return Err(());
+            }
+        }
+    }
+}
+
+/// Collects values from `param_index` up to `max` of them into `Vec`, trailing
+/// `Empty` values are omitted parameters, so they are trimmed
+fn gen_variadic_prep(
+    elem_ty: Option<&ParamType>,
+    max: usize,
+    param_index: usize,
+    param_ident: &Ident,
+) -> proc_macro2::TokenStream {
+    let collect = match elem_ty {
+        None => quote! {
+            values.into_iter().cloned().collect::<Vec<_>>()
+        },
+        Some(elem_ty) => {
+            let value_unwrap = expr_from_os_value(&quote! { (*value) }, elem_ty);
+            quote! {
+                values
+                    .into_iter()
+                    .map(|value| -> Result<_, ()> { Ok(#value_unwrap) })
+                    .collect::<Result<Vec<_>, ()>>()?
+            }
+        }
+    };
+
+    quote! {
+        let #param_ident = {
+            let mut values: Vec<&native_api_1c::native_api_1c_core::interface::ParamValue> =
+                params.iter().skip(#param_index).take(#max).collect();
+            while matches!(
+                values.last(),
+                Some(native_api_1c::native_api_1c_core::interface::ParamValue::Empty)
+            ) {
+                values.pop();
+            }
+            #collect
+        };
+    }
+}
+
+/// Resolves reference to the value in `HandleRegistry` field from `Handle`,
+/// passed by 1C. Value is shared, so the registry is not borrowed during the
+/// call, and stale handle or value, that is already in use, fails the call
+/// with the error, reported with `source` being the function name
+fn gen_resolved_prep(
+    registry: &Ident,
+    mutable: bool,
+    param_index: usize,
+    param_ident: &Ident,
+    source: &TokenStream,
+) -> proc_macro2::TokenStream {
+    let value_ident = Ident::new(&format!("{param_ident}_value"), Span::call_site());
+    let guard_ident = Ident::new(&format!("{param_ident}_guard"), Span::call_site());
+    let report_error = report_error_tkn(&quote! { &err }, source);
+    let reference = if mutable {
+        quote! { &mut *#guard_ident }
+    } else {
+        quote! { &*#guard_ident }
+    };
+    quote! {
+        let #value_ident = {
+            let handle = ::core::convert::TryFrom::try_from(params[#param_index].clone())
+                .map_err(|_| ())?;
+            match self.#registry.resolve(handle) {
+                Ok(value) => value,
+                Err(err) => {
+                    #report_error
+                    return Err(());
+                }
+            }
+        };
+        #[allow(unused_mut)]
+        let mut #guard_ident =
+            match native_api_1c::native_api_1c_core::handles::HandleRegistry::lock(&#value_ident) {
+                Ok(guard) => guard,
+                Err(err) => {
+                    #report_error
+                    return Err(());
+                }
+            };
+        let #param_ident = #reference;
+    }
+}
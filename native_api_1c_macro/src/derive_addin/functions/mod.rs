@@ -1,27 +1,53 @@
 use std::fmt::Display;
 
 use darling::FromMeta;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use super::{
-    constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE},
-    parsers::ParamType,
+    constants::{
+        BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, HANDLE_TYPE, I32_TYPE, JSON_TYPE, STRING_TYPE,
+        U16STR_TYPE,
+    },
+    parsers::{check_type_feature, ParamType},
 };
 
 pub mod collectors;
+pub mod defaults;
 pub mod generate;
 pub mod parse;
 
 #[derive(Debug)]
 pub struct FuncDesc {
-    pub ident: Ident,
+    /// Expression to call the function with, `(self.field)` for `fn` fields
+    /// and `Self::method` for methods from `impl` blocks
+    pub callee: TokenStream,
 
     pub name_literal: TokenStream,
     pub name_ru_literal: TokenStream,
+    /// Names in other languages by lowercase language codes
+    pub aliases: Vec<(String, TokenStream)>,
+    /// Names, set with string literals, checked for uniqueness
+    pub names: Vec<syn::LitStr>,
+    /// Doc comment of the field or method
+    pub doc: String,
+    /// Names of Rust arguments, passed by 1C, if they are known
+    pub param_names: Vec<Option<String>>,
 
     pub params: Vec<FuncArgumentDesc>,
     pub return_value: ReturnTypeDesc,
+
+    /// Options of `raw` function, that receives `ParamValues` as is, `None` for
+    /// regular functions
+    pub raw: Option<RawFunc>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RawFunc {
+    /// Number of parameters, passed by 1C
+    pub n_params: usize,
+    /// Function returns `ParamValue`, rather than `()` of a procedure
+    pub returns: bool,
 }
 
 impl FuncDesc {
@@ -32,6 +58,27 @@ impl FuncDesc {
             .collect()
     }
 
+    /// Number of parameters as seen by 1C, variadic argument takes up to `max` of them
+    pub fn get_n_1c_params(&self) -> usize {
+        if let Some(raw) = self.raw {
+            return raw.n_params;
+        }
+        self.get_1c_params()
+            .iter()
+            .map(|param| match param.ty {
+                FuncParamType::Variadic(_, max) => max,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    pub fn has_ret_val(&self) -> bool {
+        match self.raw {
+            Some(raw) => raw.returns,
+            None => self.return_value.ty.is_some(),
+        }
+    }
+
     pub fn has_self_param(&self) -> bool {
         self.params
             .iter()
@@ -43,7 +90,20 @@ impl FuncDesc {
 pub struct FuncArgumentDesc {
     pub ty: FuncParamType,
     pub default: Option<TokenStream>,
-    pub out_param: bool,
+    pub mode: ParamMode,
+}
+
+/// How the argument value is passed between 1C and the function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParamMode {
+    /// Value is read from 1C and passed to the function, `as_in`
+    In,
+    /// Function receives `&mut` to a default value, that is written back
+    /// to 1C after the call. Incoming value is ignored, `as_out`
+    Out,
+    /// Function receives `&mut` to the incoming value, that is written back
+    /// to 1C after the call, `as_inout`
+    InOut,
 }
 
 #[derive(Debug)]
@@ -57,13 +117,25 @@ const META_TYPE_ERR: &str = "expected string literal or path";
 pub enum FuncParamType {
     SelfType,
     PlatformType(ParamType),
+    /// Trailing `Vec<T>` argument, collecting up to `max` values. Element type is
+    /// `None` for `Vec<ParamValue>`, which values are passed as is
+    Variadic(Option<ParamType>, usize),
+    /// `&T` or `&mut T` argument, resolved from `Handle<T>`, passed by 1C, with
+    /// `HandleRegistry<T>` field of the AddIn, `#[arg(handle = field)]`
+    Resolved {
+        registry: syn::Ident,
+        mutable: bool,
+    },
 }
 
 impl Display for FuncParamType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let type_str = match self {
             FuncParamType::SelfType => "Self".to_string(),
-            FuncParamType::PlatformType(param_type) => format!("{param_type:?}"),
+            FuncParamType::PlatformType(param_type) => param_type.to_string(),
+            FuncParamType::Variadic(Some(param_type), _) => format!("Vec<{param_type}>"),
+            FuncParamType::Variadic(None, _) => "Vec<ParamValue>".to_string(),
+            FuncParamType::Resolved { .. } => HANDLE_TYPE.to_string(),
         };
         write!(f, "{}", type_str)
     }
@@ -84,6 +156,7 @@ impl FromMeta for FuncParamType {
     }
 
     fn from_string(value: &str) -> darling::Result<Self> {
+        check_type_feature(value)?;
         let joined_allowed_types = crate::derive_addin::constants::ALL_ARG_TYPES.join(", ");
         Self::try_from(value).map_err(|_| {
             darling::Error::custom(format!(
@@ -104,6 +177,9 @@ impl TryFrom<&str> for FuncParamType {
             STRING_TYPE => Ok(FuncParamType::PlatformType(ParamType::String)),
             DATE_TYPE => Ok(FuncParamType::PlatformType(ParamType::Date)),
             BLOB_TYPE => Ok(FuncParamType::PlatformType(ParamType::Blob)),
+            JSON_TYPE => Ok(FuncParamType::PlatformType(ParamType::Json)),
+            U16STR_TYPE => Ok(FuncParamType::PlatformType(ParamType::U16Str)),
+            HANDLE_TYPE => Ok(FuncParamType::PlatformType(ParamType::Handle)),
             _ => Err(()),
         }
     }
@@ -112,7 +188,11 @@ impl TryFrom<&str> for FuncParamType {
 impl ToTokens for FuncParamType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         *tokens = match self {
-            FuncParamType::SelfType => panic!("type not supported for selection"),
+            FuncParamType::SelfType
+            | FuncParamType::Variadic(..)
+            | FuncParamType::Resolved { .. } => {
+                panic!("type not supported for selection")
+            }
             FuncParamType::PlatformType(param_type) => match param_type {
                 ParamType::Bool => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Bool }
@@ -120,10 +200,10 @@ impl ToTokens for FuncParamType {
                 ParamType::I32 => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::I32 }
                 }
-                ParamType::F64 => {
+                ParamType::F64 | ParamType::Handle => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::F64 }
                 }
-                ParamType::String => {
+                ParamType::String | ParamType::Json | ParamType::U16Str => {
                     quote! { native_api_1c::native_api_1c_core::interface::ParamValue::String }
                 }
                 ParamType::Date => {
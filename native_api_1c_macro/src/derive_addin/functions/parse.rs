@@ -1,115 +1,195 @@
 use darling::{FromField, FromMeta};
-use proc_macro2::{Span, TokenStream};
-use quote::ToTokens;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
 use syn::{spanned::Spanned, Attribute, DataStruct, Meta};
 
 use crate::derive_addin::{
-    parsers::{ParamType, PropName},
-    utils::ident_option_to_darling_err,
+    constants::UNTYPED_TYPE,
+    parsers::{
+        add_in_result_ok_type, is_named_type, is_param_value, is_unit, literal_names,
+        vec_element_type, NameAliases, ParamType, PropName, ReturnInference,
+    },
+    utils::{doc_comment, ident_option_to_darling_err},
 };
 
-use super::{FuncArgumentDesc, FuncDesc, FuncParamType, ReturnTypeDesc};
+use super::{
+    defaults::default_value_tokens, FuncArgumentDesc, FuncDesc, FuncParamType, ParamMode, RawFunc,
+    ReturnTypeDesc,
+};
 
 impl FromField for FuncDesc {
     fn from_field(field: &syn::Field) -> darling::Result<Self> {
         let field_ident = ident_option_to_darling_err(field.ident.as_ref())?;
 
-        let add_in_func_attr: Vec<&Attribute> = field
-            .attrs
+        let syn::Type::BareFn(bare_fn) = &field.ty else {
+            return Err(
+                darling::Error::custom("AddIn functions must have bare `fn` type")
+                    .with_span(field_ident),
+            );
+        };
+
+        let mut inputs = bare_fn.inputs.iter().peekable();
+        let self_param = inputs.peek().and_then(|input| self_param_desc(&input.ty));
+        if self_param.is_some() {
+            inputs.next();
+        }
+        let (arg_names, arg_types) = inputs
+            .map(|input| {
+                let name = input.name.as_ref().map(|(ident, _)| ident.to_string());
+                (name, &input.ty)
+            })
+            .unzip();
+
+        Self::from_parts(
+            field_ident,
+            &field.attrs,
+            self_param,
+            arg_names,
+            arg_types,
+            &bare_fn.output,
+            quote! { (self.#field_ident) },
+        )
+    }
+}
+
+impl FuncDesc {
+    /// Builds function description from `#[add_in_func]`, `#[arg]` and `#[returns]`
+    /// attributes and function signature parts, shared between `fn` fields and
+    /// methods in `impl` blocks
+    /// # Arguments
+    /// * `ident` - identifier of the field or method, used for error spans
+    /// * `attrs` - attributes of the field or method
+    /// * `self_param` - description of `self` parameter, if function has one
+    /// * `arg_names` - names of the rest of arguments, if they are known
+    /// * `arg_types` - types of the rest of arguments
+    /// * `output` - return type of the function
+    /// * `callee` - expression, that is called with arguments
+    pub fn from_parts(
+        ident: &Ident,
+        attrs: &[Attribute],
+        self_param: Option<FuncArgumentDesc>,
+        arg_names: Vec<Option<String>>,
+        arg_types: Vec<&syn::Type>,
+        output: &syn::ReturnType,
+        callee: TokenStream,
+    ) -> darling::Result<Self> {
+        let add_in_func_attr: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("add_in_func"))
             .collect();
         if add_in_func_attr.is_empty() {
             return Err(
-                darling::Error::custom("Field must have `add_in_func` attribute")
-                    .with_span(field_ident),
+                darling::Error::custom("Field must have `add_in_func` attribute").with_span(ident),
             );
         } else if add_in_func_attr.len() > 1 {
             return Err(
                 darling::Error::custom("Field can have only 1 `add_in_func` attribute")
-                    .with_span(field_ident),
+                    .with_span(ident),
             );
         };
         let add_in_func_attr = add_in_func_attr[0];
 
-        let arg_attrs: Vec<&Attribute> = field
-            .attrs
+        let arg_attrs: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("arg"))
             .collect();
 
-        let returns_attrs: Vec<&Attribute> = field
-            .attrs
+        let returns_attrs: Vec<&Attribute> = attrs
             .iter()
             .filter(|attr| attr.path().is_ident("returns"))
             .collect();
         if returns_attrs.len() > 1 {
             return Err(
                 darling::Error::custom("Field can have at most 1 `returns` attribute")
-                    .with_span(field_ident),
+                    .with_span(ident),
             );
         };
         let returns_attr = returns_attrs.first().copied();
 
         let func_meta = FuncHeadMeta::from_meta(&add_in_func_attr.meta)?;
+        let doc = doc_comment(attrs);
+        let names = literal_names(
+            [&func_meta.name, &func_meta.name_ru]
+                .into_iter()
+                .chain(func_meta.aliases.0.iter().map(|(_, name)| name)),
+        )?;
+        let aliases: Vec<(String, TokenStream)> = func_meta
+            .aliases
+            .0
+            .into_iter()
+            .map(|(language, name)| (language, name.into()))
+            .collect();
+        let raw_n_params = match (func_meta.raw, func_meta.n_params) {
+            (Some(_), Some(n_params)) => Some(n_params),
+            (Some(_), None) => {
+                return Err(
+                    darling::Error::custom("`raw` function must have `n_params` set")
+                        .with_span(add_in_func_attr),
+                );
+            }
+            (None, Some(_)) => {
+                return Err(darling::Error::custom(
+                    "`n_params` can only be set for `raw` function",
+                )
+                .with_span(add_in_func_attr));
+            }
+            (None, None) => None,
+        };
+        if let Some(n_params) = raw_n_params {
+            if let Some(attr) = arg_attrs.first().or(returns_attr.as_ref()) {
+                return Err(darling::Error::custom(
+                    "`arg` and `returns` attributes can't be used with `raw` function",
+                )
+                .with_span(attr));
+            }
+            let returns = check_raw_signature(ident, &arg_types, output)?;
+
+            return Ok(Self {
+                callee,
+
+                name_literal: func_meta.name.into(),
+                name_ru_literal: func_meta.name_ru.into(),
+                aliases,
+                names,
+                doc,
+                param_names: Vec::new(),
+
+                params: self_param.into_iter().collect(),
+                return_value: ReturnTypeDesc {
+                    ty: None,
+                    result: true,
+                },
+                raw: Some(RawFunc { n_params, returns }),
+            });
+        }
+
         let params_meta = arg_attrs
             .iter()
-            .map(|attr| FuncArgumentMeta::from_meta(&attr.meta))
-            .collect::<darling::Result<Vec<FuncArgumentMeta>>>()?;
+            .map(|attr| FuncArgumentMeta::from_attr(attr).map(|meta| (meta, attr.span())))
+            .collect::<darling::Result<Vec<(FuncArgumentMeta, Span)>>>()?;
         let return_meta = returns_attr
             .map(|attr| FuncReturnMeta::from_meta(&attr.meta))
             .transpose()?;
-        let return_value = match return_meta {
-            Some(meta) => ReturnTypeDesc::from(meta),
-            None => ReturnTypeDesc {
-                ty: None,
-                result: false,
-            },
-        };
-
-        let mut params = params_meta
-            .into_iter()
-            .map(FuncArgumentDesc::try_from)
-            .map(|res| res.map_err(|err| err.into()))
-            .collect::<Result<Vec<FuncArgumentDesc>, darling::Error>>()?;
 
-        let syn::Type::BareFn(bare_fn) = &field.ty else {
-            return Err(
-                darling::Error::custom("AddIn functions must have bare `fn` type")
-                    .with_span(field_ident),
-            );
-        };
-
-        if let Some(first_input) = bare_fn.inputs.first() {
-            let arg_tkn_stream: TokenStream = first_input.to_token_stream();
-
-            if let Ok(reference) = syn::parse2::<syn::TypeReference>(arg_tkn_stream.clone()) {
-                if arg_tkn_stream
-                    .into_iter()
-                    .filter(|t| t.to_string() == "Self")
-                    .count()
-                    == 1
-                {
-                    params.insert(
-                        0,
-                        FuncArgumentDesc {
-                            ty: FuncParamType::SelfType,
-                            default: None,
-                            out_param: reference.mutability.is_some(),
-                        },
-                    )
-                };
-            };
-        };
+        let mut params = resolve_params(params_meta, arg_types)?;
+        if let Some(self_param) = self_param {
+            params.insert(0, self_param);
+        }
+        let return_value = resolve_return(return_meta, output)?;
 
         Ok(Self {
-            ident: field_ident.to_owned(),
+            callee,
 
             name_literal: func_meta.name.into(),
             name_ru_literal: func_meta.name_ru.into(),
+            aliases,
+            names,
+            doc,
+            param_names: arg_names,
 
             params,
             return_value,
+            raw: None,
         })
     }
 }
@@ -118,62 +198,326 @@ impl FromField for FuncDesc {
 struct FuncHeadMeta {
     name: PropName,
     name_ru: PropName,
+    #[darling(default)]
+    aliases: NameAliases,
+    raw: Option<()>,
+    n_params: Option<usize>,
 }
 
-#[derive(FromMeta, Debug)]
+#[derive(FromMeta, Debug, Default)]
 struct FuncArgumentMeta {
     ident: Option<syn::Ident>,
-    ty: FuncParamType,
+    ty: Option<FuncParamType>,
     default: Option<Meta>,
-    #[allow(dead_code)]
     as_in: Option<()>,
     as_out: Option<()>,
+    as_inout: Option<()>,
+    variadic: Option<()>,
+    max: Option<usize>,
+    /// `HandleRegistry<T>` field, that resolves `&T` or `&mut T` argument
+    handle: Option<syn::Ident>,
 }
 
-impl TryFrom<FuncArgumentMeta> for FuncArgumentDesc {
-    type Error = ErrorConvertingMeta;
+impl FuncArgumentMeta {
+    /// Parses `#[arg(...)]` attribute, bare `#[arg]` is allowed for arguments
+    /// which type is inferred and that have no extra options
+    fn from_attr(attr: &Attribute) -> darling::Result<Self> {
+        match &attr.meta {
+            Meta::Path(_) => Ok(Self::default()),
+            meta => Self::from_meta(meta),
+        }
+    }
+}
+
+/// Checks that `raw` function takes `&mut ParamValues` besides `self` and returns
+/// `Result<ParamValue, E>`, or `Result<(), E>` for procedures. Returns if the
+/// function has return value
+fn check_raw_signature(
+    ident: &Ident,
+    arg_types: &[&syn::Type],
+    output: &syn::ReturnType,
+) -> darling::Result<bool> {
+    let args_valid = matches!(
+        arg_types,
+        [syn::Type::Reference(reference)]
+            if reference.mutability.is_some() && is_named_type(&reference.elem, "ParamValues")
+    );
+    let ok_type = match output {
+        syn::ReturnType::Type(_, ty) => add_in_result_ok_type(ty),
+        syn::ReturnType::Default => None,
+    };
+    match ok_type {
+        Some(ty) if args_valid && is_param_value(ty) => return Ok(true),
+        Some(ty) if args_valid && is_unit(ty) => return Ok(false),
+        _ => {}
+    }
+
+    Err(darling::Error::custom(
+        "`raw` function must have `fn(&mut Self, &mut ParamValues) -> Result<ParamValue, E>` \
+         or `fn(&mut Self, &mut ParamValues) -> Result<(), E>` type",
+    )
+    .with_span(ident))
+}
+
+/// Resolves description of function argument from `#[arg(...)]` attribute and
+/// type of argument in function signature. Explicitly set type must match the
+/// inferred one, if the latter is known
+fn resolve_argument(
+    arg_meta: FuncArgumentMeta,
+    arg_ty: &syn::Type,
+) -> Result<FuncArgumentDesc, ErrorConvertingMeta> {
+    if arg_meta.handle.is_some() {
+        return resolve_handle_argument(arg_meta, arg_ty);
+    }
+
+    let modes = [
+        ("as_in", arg_meta.as_in, ParamMode::In),
+        ("as_out", arg_meta.as_out, ParamMode::Out),
+        ("as_inout", arg_meta.as_inout, ParamMode::InOut),
+    ];
+    let mut set_modes = modes.iter().filter(|(_, flag, _)| flag.is_some());
+    let explicit_mode = set_modes.next();
+    if let (Some((first, ..)), Some((second, ..))) = (explicit_mode, set_modes.next()) {
+        return Err(ErrorConvertingMeta::ConflictingParams(
+            arg_ty.span(),
+            first.to_string(),
+            second.to_string(),
+        ));
+    }
 
-    fn try_from(arg_meta: FuncArgumentMeta) -> Result<Self, Self::Error> {
-        if arg_meta.as_in.is_some() && arg_meta.as_out.is_some() {
-            return Err(Self::Error::ConflictingParams(
-                arg_meta.ident.span(),
-                "as_in".to_string(),
-                "as_out".to_string(),
+    let ty = match (arg_meta.variadic, arg_meta.max) {
+        (Some(_), max) => resolve_variadic_type(arg_meta.ty, max, arg_ty)?,
+        (None, Some(_)) => {
+            return Err(ErrorConvertingMeta::InvalidVariadic(
+                arg_ty.span(),
+                "`max` can only be set for `variadic` argument".to_string(),
             ));
         }
+        (None, None) => resolve_type(arg_meta.ty, arg_ty)?,
+    };
 
-        let allowed_defaults = match arg_meta.ty.clone() {
-            FuncParamType::SelfType => false,
-            FuncParamType::PlatformType(ty) => match ty {
-                ParamType::Bool => true,
-                ParamType::I32 => true,
-                ParamType::F64 => true,
-                ParamType::String => true,
-                ParamType::Date => false,
-                ParamType::Blob => false,
-            },
-        };
+    let is_variadic = matches!(ty, FuncParamType::Variadic(..));
+    if is_variadic
+        && matches!(
+            explicit_mode,
+            Some((_, _, ParamMode::Out | ParamMode::InOut))
+        )
+    {
+        return Err(ErrorConvertingMeta::InvalidVariadic(
+            arg_ty.span(),
+            "variadic argument can only be `as_in`".to_string(),
+        ));
+    }
+
+    let can_be_default = !matches!(
+        ty,
+        FuncParamType::SelfType
+            | FuncParamType::Variadic(..)
+            | FuncParamType::PlatformType(ParamType::Json | ParamType::Handle)
+    );
+    if !can_be_default && arg_meta.default.is_some() {
+        return Err(ErrorConvertingMeta::TypeCannotBeDefault(
+            ty,
+            arg_meta.default.span(),
+        ));
+    }
 
-        if arg_meta.default.is_some() && !allowed_defaults {
-            return Err(Self::Error::TypeCannotBeDefault(
-                arg_meta.ty,
-                arg_meta.default.span(),
+    let is_out = matches!(explicit_mode, Some((_, _, ParamMode::Out)));
+    if is_out && arg_meta.default.is_some() {
+        return Err(ErrorConvertingMeta::OutCannotBeDefault(
+            arg_meta.default.span(),
+        ));
+    }
+
+    // if you pass "some_string" as default, it would get parsed by darling as `Ident`
+    let default_fixed = arg_meta.default.map(|d| match (d, &ty) {
+        (Meta::NameValue(nv), FuncParamType::PlatformType(param_type)) => {
+            default_value_tokens(param_type, &nv.value)
+        }
+        _ => Err(ErrorConvertingMeta::UnexpectedMetaType(
+            arg_meta.ident.span(),
+        )),
+    });
+    let default_fixed = default_fixed.transpose()?;
+
+    let mut_ref =
+        matches!(arg_ty, syn::Type::Reference(reference) if reference.mutability.is_some());
+
+    // `&mut T` without explicit mode keeps the incoming value
+    let mode = match explicit_mode {
+        Some((_, _, mode)) => *mode,
+        None if mut_ref => ParamMode::InOut,
+        None => ParamMode::In,
+    };
+
+    Ok(FuncArgumentDesc {
+        ty,
+        default: default_fixed,
+        mode,
+    })
+}
+
+/// Resolves `#[arg(handle = field)]` argument, which must be a reference to the
+/// value, stored in `HandleRegistry` field. 1C passes `Handle` of the value
+fn resolve_handle_argument(
+    arg_meta: FuncArgumentMeta,
+    arg_ty: &syn::Type,
+) -> Result<FuncArgumentDesc, ErrorConvertingMeta> {
+    let syn::Type::Reference(reference) = arg_ty else {
+        return Err(ErrorConvertingMeta::InvalidHandle(
+            arg_ty.span(),
+            "argument must be `&T` or `&mut T`".to_string(),
+        ));
+    };
+    let options = [
+        ("ty", arg_meta.ty.is_some()),
+        ("default", arg_meta.default.is_some()),
+        ("as_in", arg_meta.as_in.is_some()),
+        ("as_out", arg_meta.as_out.is_some()),
+        ("as_inout", arg_meta.as_inout.is_some()),
+        ("variadic", arg_meta.variadic.is_some()),
+        ("max", arg_meta.max.is_some()),
+    ];
+    if let Some((option, _)) = options.iter().find(|(_, set)| *set) {
+        return Err(ErrorConvertingMeta::InvalidHandle(
+            arg_ty.span(),
+            format!("`{option}` can't be set for argument, resolved from handle"),
+        ));
+    }
+
+    Ok(FuncArgumentDesc {
+        ty: FuncParamType::Resolved {
+            registry: arg_meta.handle.unwrap(),
+            mutable: reference.mutability.is_some(),
+        },
+        default: None,
+        mode: ParamMode::In,
+    })
+}
+
+/// Resolves type of argument from explicitly set type and type in function signature.
+/// Explicitly set type must match the inferred one, if the latter is known
+fn resolve_type(
+    explicit_ty: Option<FuncParamType>,
+    arg_ty: &syn::Type,
+) -> Result<FuncParamType, ErrorConvertingMeta> {
+    match (explicit_ty, ParamType::infer(arg_ty)) {
+        // any type can be transported as JSON
+        (Some(FuncParamType::PlatformType(ParamType::Json)), _) => {
+            Ok(FuncParamType::PlatformType(ParamType::Json))
+        }
+        (Some(FuncParamType::PlatformType(explicit)), Some(inferred)) if explicit != inferred => {
+            Err(ErrorConvertingMeta::ConflictingTypes(
+                arg_ty.span(),
+                explicit.to_string(),
+                inferred.to_string(),
+            ))
+        }
+        (Some(explicit), _) => Ok(explicit),
+        (None, Some(inferred)) => Ok(FuncParamType::PlatformType(inferred)),
+        (None, None) => Err(ErrorConvertingMeta::TypeNotInferred(
+            arg_ty.span(),
+            arg_ty.to_token_stream().to_string(),
+        )),
+    }
+}
+
+/// Resolves type of `#[arg(variadic, max = N)]` argument, which must be `Vec<T>`,
+/// where `T` is either `ParamValue` or one of platform types. Explicitly set type
+/// applies to `T`
+fn resolve_variadic_type(
+    explicit_ty: Option<FuncParamType>,
+    max: Option<usize>,
+    arg_ty: &syn::Type,
+) -> Result<FuncParamType, ErrorConvertingMeta> {
+    let Some(elem_ty) = vec_element_type(arg_ty) else {
+        return Err(ErrorConvertingMeta::InvalidVariadic(
+            arg_ty.span(),
+            "variadic argument must be of `Vec<T>` type".to_string(),
+        ));
+    };
+    let max = match max {
+        Some(max) if max > 0 => max,
+        _ => {
+            return Err(ErrorConvertingMeta::InvalidVariadic(
+                arg_ty.span(),
+                "`max` must be set to maximum number of values, greater than 0".to_string(),
             ));
         }
+    };
 
-        // if you pass "some_string" as default, it would get parsed by darling as `Ident`
-        let default_fixed = arg_meta.default.map(|d| match d {
-            Meta::NameValue(nv) => Ok(nv.value.to_token_stream()),
-            _ => Err(Self::Error::UnexpectedMetaType(arg_meta.ident.span())),
-        });
-        let default_fixed = default_fixed.transpose()?;
+    if is_param_value(elem_ty) {
+        return match explicit_ty {
+            Some(explicit) => Err(ErrorConvertingMeta::ConflictingTypes(
+                elem_ty.span(),
+                explicit.to_string(),
+                "ParamValue".to_string(),
+            )),
+            None => Ok(FuncParamType::Variadic(None, max)),
+        };
+    }
 
-        Ok(Self {
-            ty: arg_meta.ty,
-            default: default_fixed,
-            out_param: arg_meta.as_out.is_some(),
+    match resolve_type(explicit_ty, elem_ty)? {
+        FuncParamType::PlatformType(param_type) if param_type != ParamType::Json => {
+            Ok(FuncParamType::Variadic(Some(param_type), max))
+        }
+        ty => Err(ErrorConvertingMeta::InvalidVariadic(
+            elem_ty.span(),
+            format!("`{ty}` cannot be collected into variadic argument"),
+        )),
+    }
+}
+
+/// Matches `#[arg(...)]` attributes with function arguments by position. Trailing
+/// arguments may have no attribute, in which case their types are inferred
+fn resolve_params(
+    params_meta: Vec<(FuncArgumentMeta, Span)>,
+    arg_types: Vec<&syn::Type>,
+) -> Result<Vec<FuncArgumentDesc>, darling::Error> {
+    if let Some((_, span)) = params_meta.get(arg_types.len()) {
+        return Err(ErrorConvertingMeta::UnmatchedArgument(*span).into());
+    }
+
+    let n_args = arg_types.len();
+    let mut params_meta = params_meta.into_iter().map(|(meta, _)| meta);
+    arg_types
+        .into_iter()
+        .enumerate()
+        .map(|(arg_index, arg_ty)| {
+            let arg_meta = params_meta.next().unwrap_or_default();
+            let arg_desc = resolve_argument(arg_meta, arg_ty)?;
+            if matches!(arg_desc.ty, FuncParamType::Variadic(..)) && arg_index + 1 != n_args {
+                return Err(ErrorConvertingMeta::InvalidVariadic(
+                    arg_ty.span(),
+                    "only the last argument can be variadic".to_string(),
+                )
+                .into());
+            }
+            Ok(arg_desc)
         })
+        .collect()
+}
+
+/// Detects if the first argument of function is `&Self` or `&mut Self`
+pub fn self_param_desc(arg_ty: &syn::Type) -> Option<FuncArgumentDesc> {
+    let arg_tkn_stream: TokenStream = arg_ty.to_token_stream();
+    let reference = syn::parse2::<syn::TypeReference>(arg_tkn_stream.clone()).ok()?;
+    let self_count = arg_tkn_stream
+        .into_iter()
+        .filter(|t| t.to_string() == "Self")
+        .count();
+    if self_count != 1 {
+        return None;
     }
+
+    Some(FuncArgumentDesc {
+        ty: FuncParamType::SelfType,
+        default: None,
+        mode: match reference.mutability {
+            Some(_) => ParamMode::InOut,
+            None => ParamMode::In,
+        },
+    })
 }
 
 #[derive(FromMeta, Debug)]
@@ -182,21 +526,78 @@ struct FuncReturnMeta {
     result: Option<()>,
 }
 
-impl From<FuncReturnMeta> for ReturnTypeDesc {
-    fn from(arg_meta: FuncReturnMeta) -> Self {
-        Self {
-            ty: arg_meta.ty,
-            result: arg_meta.result.is_some(),
-        }
+/// Resolves description of function return value from `#[returns(...)]` attribute
+/// and return type in function signature
+fn resolve_return(
+    return_meta: Option<FuncReturnMeta>,
+    output: &syn::ReturnType,
+) -> Result<ReturnTypeDesc, darling::Error> {
+    let (explicit_ty, explicit_result) = match return_meta {
+        Some(meta) => (meta.ty, meta.result.is_some()),
+        None => (None, false),
+    };
+
+    let (inferred_ty, result, known) = match ParamType::infer_return(output) {
+        ReturnInference::Inferred(ty, result) => (ty, result, true),
+        ReturnInference::Unknown(result) => (None, result, false),
+    };
+
+    let span = match output {
+        syn::ReturnType::Type(_, ty) => ty.span(),
+        syn::ReturnType::Default => output.span(),
+    };
+
+    if explicit_result && !result {
+        return Err(ErrorConvertingMeta::NotAResult(span).into());
     }
+
+    let ty = match (explicit_ty, inferred_ty) {
+        // any type can be transported as JSON
+        (Some(ParamType::Json), _) => Some(ParamType::Json),
+        (Some(explicit), Some(inferred)) if explicit != inferred => {
+            return Err(ErrorConvertingMeta::ConflictingTypes(
+                span,
+                explicit.to_string(),
+                inferred.to_string(),
+            )
+            .into());
+        }
+        (Some(explicit), None) if known => {
+            return Err(ErrorConvertingMeta::ConflictingTypes(
+                span,
+                explicit.to_string(),
+                UNTYPED_TYPE.to_string(),
+            )
+            .into());
+        }
+        (Some(explicit), _) => Some(explicit),
+        (None, inferred) if known => inferred,
+        (None, _) => {
+            return Err(ErrorConvertingMeta::TypeNotInferred(
+                span,
+                output.to_token_stream().to_string(),
+            )
+            .into());
+        }
+    };
+
+    Ok(ReturnTypeDesc { ty, result })
 }
 
 pub enum ErrorConvertingMeta {
     UnexpectedMetaType(Span),
     TypeCannotBeDefault(FuncParamType, Span),
+    OutCannotBeDefault(Span),
     InvalidTypeForParam(Span, String),
     InvalidTypeForReturn(Span, String),
     ConflictingParams(Span, String, String),
+    ConflictingTypes(Span, String, String),
+    TypeNotInferred(Span, String),
+    UnmatchedArgument(Span),
+    NotAResult(Span),
+    InvalidDefault(Span, String),
+    InvalidVariadic(Span, String),
+    InvalidHandle(Span, String),
 }
 
 impl From<ErrorConvertingMeta> for darling::Error {
@@ -225,9 +626,46 @@ impl From<ErrorConvertingMeta> for darling::Error {
                 darling::Error::custom(format!("Type `{param_type}` cannot have default value"))
                     .with_span(&span)
             }
+            ErrorConvertingMeta::OutCannotBeDefault(span) => darling::Error::custom(
+                "`as_out` argument cannot have default value, its incoming value is ignored",
+            )
+            .with_span(&span),
             ErrorConvertingMeta::UnexpectedMetaType(span) => {
                 darling::Error::custom("Unexpected meta type").with_span(&span)
             }
+            ErrorConvertingMeta::ConflictingTypes(span, explicit, inferred) => {
+                darling::Error::custom(format!(
+                    "Type `{explicit}` conflicts with type `{inferred}` inferred from function signature"
+                ))
+                .with_span(&span)
+            }
+            ErrorConvertingMeta::TypeNotInferred(span, ty) => {
+                let joined_allowed_types = crate::derive_addin::constants::ALL_ARG_TYPES.join(", ");
+                darling::Error::custom(format!(
+                    "Unable to infer 1C type for `{ty}`, set it with `ty = ...`, one of: {joined_allowed_types}"
+                ))
+                .with_span(&span)
+            }
+            ErrorConvertingMeta::UnmatchedArgument(span) => {
+                darling::Error::custom("`arg` attribute does not match any function argument")
+                    .with_span(&span)
+            }
+            ErrorConvertingMeta::InvalidDefault(span, message) => {
+                darling::Error::custom(format!("Invalid default value: {message}"))
+                    .with_span(&span)
+            }
+            ErrorConvertingMeta::InvalidVariadic(span, message) => {
+                darling::Error::custom(format!("Invalid variadic argument: {message}"))
+                    .with_span(&span)
+            }
+            ErrorConvertingMeta::InvalidHandle(span, message) => {
+                darling::Error::custom(format!("Invalid handle argument: {message}"))
+                    .with_span(&span)
+            }
+            ErrorConvertingMeta::NotAResult(span) => {
+                darling::Error::custom("`result` is set, but function does not return `Result`")
+                    .with_span(&span)
+            }
         }
     }
 }
@@ -1,16 +1,21 @@
+use darling::FromMeta;
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, Attribute, DeriveInput};
 
+use functions::FuncDesc;
 use functions::{collectors::*, parse::parse_functions};
+use parsers::UniqueNames;
+use props::PropDesc;
 use props::{collectors::*, parse::parse_props};
-use utils::{macros::tkn_err, str_literal_token};
+use utils::{doc_comment, macros::tkn_err, str_literal_token};
 
 mod constants;
-mod functions;
-mod parsers;
+pub(crate) mod description;
+pub(crate) mod functions;
+pub(crate) mod parsers;
 mod props;
-mod utils;
+pub(crate) mod utils;
 
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -21,13 +26,8 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 fn derive_result(input: &DeriveInput) -> Result<TokenStream, TokenStream> {
-    let impl_block = build_impl_block(input).map_err(|darling_error| {
-        let error_tokens = darling_error.write_errors();
-        let error_tokens = quote! {
-            compile_error!(#error_tokens);
-        };
-        error_tokens
-    })?;
+    let impl_block =
+        build_impl_block(input).map_err(|darling_error| darling_error.write_errors())?;
 
     Ok(quote! {
         #impl_block
@@ -44,8 +44,39 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
     };
     let add_in_name_literal = str_literal_token(&struct_ident.to_string(), struct_ident)?;
 
-    let props = parse_props(struct_data)?;
+    let add_in_meta = AddInMeta::from_attrs(&input.attrs)?;
+    let props = parse_props(&input.attrs, struct_data)?;
     let functions = parse_functions(struct_data)?;
+    check_unique_names(&props, &functions)?;
+    let handle_registries = parse_handle_registries(struct_data);
+    let language_field = parse_language_field(struct_data)?;
+    let language_fields: Vec<&syn::Ident> = language_field.into_iter().collect();
+    let language_definitions = language_field.map(|field| {
+        quote! {
+            fn ui_language(&self) -> Option<&native_api_1c::native_api_1c_core::localization::UiLanguage> {
+                Some(&self.#field)
+            }
+        }
+    });
+    let catalog_definition = add_in_meta.messages.as_ref().map(|catalog| {
+        quote! {
+            fn message_catalog(&self) -> Option<&native_api_1c::native_api_1c_core::localization::MessageCatalog> {
+                Some(&#catalog)
+            }
+        }
+    });
+    let shared_fields = parse_shared_fields(struct_data)?;
+    let shared_acquires = shared_fields.iter().map(|(field, meta)| {
+        let shared_key = quote! { native_api_1c::native_api_1c_core::shared::SharedKey };
+        let key = match &meta.key {
+            Some(name) => quote! { #shared_key::named(#name) },
+            None => quote! { #shared_key::owner::<Self>() },
+        };
+        match &meta.init {
+            Some(init) => quote! { self.#field.acquire(#key, #init); },
+            None => quote! { self.#field.acquire(#key, Default::default); },
+        }
+    });
 
     let pi = props.iter().enumerate();
     let prop_definitions = [
@@ -58,31 +89,54 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
         pi.clone().collect::<SetPropValCollector>().release()?,
     ];
 
-    let fi = functions.iter().enumerate();
-    let func_definitions = [
-        fi.clone().collect::<FindMethodCollector>().release()?,
-        fi.clone().collect::<GetMethodNameCollector>().release()?,
-        fi.clone().collect::<GetNMethodsCollector>().release()?,
-        fi.clone().collect::<GetNParamsCollector>().release()?,
-        fi.clone().collect::<HasReturnValueCollector>().release()?,
-        fi.clone().collect::<CallAsProcCollector>().release()?,
-        fi.clone().collect::<CallAsFuncCollector>().release()?,
-        fi.clone()
-            .collect::<GetParamDefValueCollector>()
-            .release()?,
-    ];
+    let func_definitions = if add_in_meta.impl_methods.is_some() {
+        if !functions.is_empty() {
+            return tkn_err!(
+                "AddIn with `impl_methods` can't have `add_in_func` fields",
+                &struct_ident.span()
+            );
+        }
+        impl_methods_definitions()
+    } else {
+        method_definitions(&functions)?
+    };
+
+    let method_name_checks = if add_in_meta.impl_methods.is_some() {
+        method_name_checks(struct_ident, &props)
+    } else {
+        vec![]
+    };
+
+    let doc = doc_comment(&input.attrs);
+    let prop_descriptions = description::prop_descriptions(&props);
+    let method_descriptions = if add_in_meta.impl_methods.is_some() {
+        quote! {
+            <Self as native_api_1c::native_api_1c_core::interface::AddInMethods>::method_descriptions()
+        }
+    } else {
+        description::method_descriptions(&functions)
+    };
+
+    let info: u16 = if add_in_meta.protocol_v1.is_some() {
+        1000
+    } else {
+        2000
+    };
 
     let result = quote! {
         impl native_api_1c::native_api_1c_core::interface::AddInWrapper for #struct_ident {
             fn init(&mut self, interface: &'static native_api_1c::native_api_1c_core::ffi::connection::Connection) -> bool {
                 self.connection = std::sync::Arc::new(Some(interface));
+                #(#shared_acquires)*
                 true
             }
 
             fn get_info(&self) -> u16 {
-                2000
+                #info
+            }
+            fn done(&mut self) {
+                #(self.#handle_registries.clear();)*
             }
-            fn done(&mut self) {}
             fn register_extension_as(&mut self) -> &[u16] {
                 &utf16_lit::utf16_null!(#add_in_name_literal)
             }
@@ -91,10 +145,215 @@ fn build_impl_block(input: &DeriveInput) -> Result<proc_macro2::TokenStream, dar
             #(#func_definitions)*
 
             fn set_locale(&mut self, loc: &[u16]) {
+                #(self.#language_fields.set_locale(loc);)*
             }
             fn set_user_interface_language_code(&mut self, lang: &[u16]) {
+                #(self.#language_fields.set_code(lang);)*
+            }
+            #language_definitions
+            #catalog_definition
+        }
+
+        #(#method_name_checks)*
+
+        impl native_api_1c::native_api_1c_core::description::DescribeAddIn for #struct_ident {
+            fn description() -> native_api_1c::native_api_1c_core::description::AddInDescription {
+                native_api_1c::native_api_1c_core::description::AddInDescription {
+                    name: #add_in_name_literal,
+                    doc: #doc,
+                    props: #prop_descriptions,
+                    methods: #method_descriptions,
+                }
             }
         }
     };
     Ok(result)
 }
+
+/// Checks that names of properties and methods don't repeat, regardless of
+/// case. Methods from `impl` block are checked by `#[add_in_methods]` and by
+/// `method_name_checks`
+fn check_unique_names(props: &[PropDesc], functions: &[FuncDesc]) -> Result<(), darling::Error> {
+    let mut names = UniqueNames::default();
+    let mut errors = darling::Error::accumulator();
+    for prop in props {
+        errors.handle(names.check("property", &prop.names));
+    }
+    for function in functions {
+        errors.handle(names.check("method", &function.names));
+    }
+    errors.finish()
+}
+
+/// Constant assertions, that names of properties are not used by methods of
+/// `impl_methods` AddIn, which are known only to `#[add_in_methods]`
+fn method_name_checks(struct_ident: &syn::Ident, props: &[PropDesc]) -> Vec<TokenStream> {
+    let interface = quote! { native_api_1c::native_api_1c_core::interface };
+    props
+        .iter()
+        .flat_map(|prop| &prop.names)
+        .map(|str_lit| {
+            let name = str_lit.value();
+            let key = name.to_lowercase();
+            let message = format!("Name `{name}` is already used by a method");
+            quote_spanned! {str_lit.span()=>
+                const _: () = assert!(
+                    !#interface::contains_name(
+                        <#struct_ident as #interface::AddInMethods>::METHOD_NAMES,
+                        #key,
+                    ),
+                    #message
+                );
+            }
+        })
+        .collect()
+}
+
+/// Fields with `HandleRegistry`, marked with `#[add_in_handles]`, which values
+/// are dropped when AddIn is unloaded
+fn parse_handle_registries(struct_data: &syn::DataStruct) -> Vec<&syn::Ident> {
+    struct_data
+        .fields
+        .iter()
+        .filter(|field| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("add_in_handles"))
+        })
+        .filter_map(|field| field.ident.as_ref())
+        .collect()
+}
+
+/// Field with `UiLanguage`, marked with `#[add_in_lang]`, that keeps the
+/// interface language, passed by 1C
+fn parse_language_field(
+    struct_data: &syn::DataStruct,
+) -> Result<Option<&syn::Ident>, darling::Error> {
+    let mut fields = struct_data.fields.iter().filter_map(|field| {
+        let attr = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("add_in_lang"))?;
+        Some((field, attr))
+    });
+    let Some((field, attr)) = fields.next() else {
+        return Ok(None);
+    };
+    if let Some((_, attr)) = fields.next() {
+        return tkn_err!("AddIn can have only 1 `add_in_lang` field", attr);
+    }
+    match &field.ident {
+        Some(ident) => Ok(Some(ident)),
+        None => tkn_err!("Language field must be named", attr),
+    }
+}
+
+/// Options of a `Shared` field, set with `#[add_in_shared(...)]` attribute
+#[derive(FromMeta, Debug, Default)]
+struct SharedMeta {
+    /// Function, that creates the value, `Default::default` if not set
+    init: Option<syn::Path>,
+    /// Name, that shares the value with other AddIn types, otherwise it's
+    /// shared only by AddIns of this type
+    key: Option<syn::LitStr>,
+}
+
+/// Fields with `Shared`, marked with `#[add_in_shared]`, which values are
+/// acquired when AddIn is initialized, with their options
+fn parse_shared_fields(
+    struct_data: &syn::DataStruct,
+) -> Result<Vec<(&syn::Ident, SharedMeta)>, darling::Error> {
+    let mut fields = Vec::new();
+    for field in &struct_data.fields {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("add_in_shared"))
+        else {
+            continue;
+        };
+        let meta = match &attr.meta {
+            syn::Meta::Path(_) => SharedMeta::default(),
+            meta => SharedMeta::from_meta(meta)?,
+        };
+        let Some(ident) = field.ident.as_ref() else {
+            return tkn_err!("Shared field must be named", attr);
+        };
+        fields.push((ident, meta));
+    }
+    Ok(fields)
+}
+
+/// Options of the whole AddIn, set with `#[add_in(...)]` attribute on the struct
+#[derive(FromMeta, Debug, Default)]
+struct AddInMeta {
+    /// Methods are taken from `impl` block, marked with `#[add_in_methods]`
+    impl_methods: Option<()>,
+    /// AddIn reports Native API 1.0, so strings are passed as UTF-8 and host
+    /// may not provide MemoryManager
+    protocol_v1: Option<()>,
+    /// Static `MessageCatalog`, that translates `Message` errors of methods
+    /// and properties into the interface language
+    messages: Option<syn::Path>,
+}
+
+impl AddInMeta {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self, darling::Error> {
+        let add_in_attrs: Vec<&Attribute> = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("add_in"))
+            .collect();
+        match add_in_attrs.as_slice() {
+            [] => Ok(Self::default()),
+            [attr] => Self::from_meta(&attr.meta),
+            [_, attr, ..] => tkn_err!("AddIn can have only 1 `add_in` attribute", attr),
+        }
+    }
+}
+
+/// Generates `AddInWrapper` methods, related to AddIn functions, that delegate
+/// to `AddInMethods` implementation
+fn impl_methods_definitions() -> Vec<TokenStream> {
+    let methods_trait = quote! { native_api_1c::native_api_1c_core::interface::AddInMethods };
+    vec![quote! {
+        fn get_n_methods(&self) -> usize {
+            <Self as #methods_trait>::get_n_methods(self)
+        }
+        fn find_method(&self, name: &[u16]) -> Option<usize> {
+            <Self as #methods_trait>::find_method(self, name)
+        }
+        fn get_method_name(&self, num: usize, alias: usize) -> Option<Vec<u16>> {
+            <Self as #methods_trait>::get_method_name(self, num, alias)
+        }
+        fn get_n_params(&self, num: usize) -> usize {
+            <Self as #methods_trait>::get_n_params(self, num)
+        }
+        fn get_param_def_value(
+            &self,
+            method_num: usize,
+            param_num: usize,
+        ) -> Option<native_api_1c::native_api_1c_core::interface::ParamValue> {
+            <Self as #methods_trait>::get_param_def_value(self, method_num, param_num)
+        }
+        fn has_ret_val(&self, method_num: usize) -> bool {
+            <Self as #methods_trait>::has_ret_val(self, method_num)
+        }
+        fn call_as_proc(
+            &mut self,
+            method_num: usize,
+            params: &mut native_api_1c::native_api_1c_core::interface::ParamValues,
+        ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<()> {
+            <Self as #methods_trait>::call_as_proc(self, method_num, params)
+        }
+        fn call_as_func(
+            &mut self,
+            method_num: usize,
+            params: &mut native_api_1c::native_api_1c_core::interface::ParamValues,
+        ) -> native_api_1c::native_api_1c_core::interface::AddInWrapperResult<
+            native_api_1c::native_api_1c_core::interface::ParamValue
+        > {
+            <Self as #methods_trait>::call_as_func(self, method_num, params)
+        }
+    }]
+}
@@ -1,8 +1,13 @@
+use std::{collections::HashMap, fmt::Display};
+
 use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
-use super::constants::{BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, I32_TYPE, STRING_TYPE};
+use super::constants::{
+    BLOB_TYPE, BOOL_TYPE, DATE_TYPE, F64_TYPE, HANDLE_TYPE, I32_TYPE, JSON_TYPE, STRING_TYPE,
+    U16STR_TYPE,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ParamType {
@@ -12,6 +17,12 @@ pub enum ParamType {
     String,
     Date,
     Blob,
+    /// Any `T: Serialize + DeserializeOwned`, transported as JSON string
+    Json,
+    /// `&U16Str` or `U16String`, transported as string without conversion
+    U16Str,
+    /// `Handle<T>` of a value in `HandleRegistry`, transported as number
+    Handle,
 }
 
 const META_TYPE_ERR: &str = "expected string literal or path";
@@ -31,6 +42,7 @@ impl FromMeta for ParamType {
     }
 
     fn from_string(value: &str) -> darling::Result<Self> {
+        check_type_feature(value)?;
         let joined_allowed_types = crate::derive_addin::constants::ALL_ARG_TYPES.join(", ");
         Self::try_from(value).map_err(|_| {
             darling::Error::custom(format!(
@@ -51,11 +63,246 @@ impl TryFrom<&str> for ParamType {
             STRING_TYPE => Ok(ParamType::String),
             DATE_TYPE => Ok(ParamType::Date),
             BLOB_TYPE => Ok(ParamType::Blob),
+            JSON_TYPE => Ok(ParamType::Json),
+            U16STR_TYPE => Ok(ParamType::U16Str),
+            HANDLE_TYPE => Ok(ParamType::Handle),
             _ => Err(()),
         }
     }
 }
 
+/// Checks that type, that requires optional feature, can be used
+pub fn check_type_feature(value: &str) -> darling::Result<()> {
+    if value == JSON_TYPE && !cfg!(feature = "serde") {
+        return Err(darling::Error::custom(
+            "type `Json` requires `serde` feature of `native_api_1c`",
+        ));
+    }
+    Ok(())
+}
+
+impl Display for ParamType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let type_str = match self {
+            ParamType::Bool => BOOL_TYPE,
+            ParamType::I32 => I32_TYPE,
+            ParamType::F64 => F64_TYPE,
+            ParamType::String => STRING_TYPE,
+            ParamType::Date => DATE_TYPE,
+            ParamType::Blob => BLOB_TYPE,
+            ParamType::Json => JSON_TYPE,
+            ParamType::U16Str => U16STR_TYPE,
+            ParamType::Handle => HANDLE_TYPE,
+        };
+        write!(f, "{}", type_str)
+    }
+}
+
+impl ParamType {
+    /// Infers platform type from Rust type, used in AddIn field or method signature.
+    /// References are looked through, so `&mut String` is inferred as `Str`
+    pub fn infer(ty: &syn::Type) -> Option<Self> {
+        let ty = match ty {
+            syn::Type::Reference(reference) => reference.elem.as_ref(),
+            syn::Type::Paren(paren) => paren.elem.as_ref(),
+            _ => ty,
+        };
+        if let syn::Type::Slice(slice) = ty {
+            return is_primitive(&slice.elem, "u8").then_some(ParamType::Blob);
+        }
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let last_segment = type_path.path.segments.last()?;
+
+        match known_type_name(type_path)? {
+            "bool" => Some(ParamType::Bool),
+            "i32" => Some(ParamType::I32),
+            "f64" => Some(ParamType::F64),
+            // dereferences to the class name
+            "String" | "PendingInstance" => Some(ParamType::String),
+            "U16Str" | "U16String" => Some(ParamType::U16Str),
+            "PlatformBlob" => Some(ParamType::Blob),
+            "Handle" => Some(ParamType::Handle),
+            "NaiveDateTime" | "PrimitiveDateTime" | "OffsetDateTime" => Some(ParamType::Date),
+            "DateTime" => match generic_args(last_segment).as_slice() {
+                [syn::Type::Path(offset)] if known_type_name(offset) == Some("FixedOffset") => {
+                    Some(ParamType::Date)
+                }
+                _ => None,
+            },
+            "Vec" => match generic_args(last_segment).as_slice() {
+                [inner] if is_primitive(inner, "u8") => Some(ParamType::Blob),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Infers platform type of the value returned from function, as well as if
+    /// it is wrapped in `Result`. `None` as platform type means that function
+    /// does not return a value
+    pub fn infer_return(output: &syn::ReturnType) -> ReturnInference {
+        let syn::ReturnType::Type(_, ty) = output else {
+            return ReturnInference::Inferred(None, false);
+        };
+
+        let (value_ty, result) = match result_ok_type(ty) {
+            Some(ok_ty) => (ok_ty, true),
+            None => (ty.as_ref(), false),
+        };
+
+        if is_unit(value_ty) {
+            return ReturnInference::Inferred(None, result);
+        }
+        match Self::infer(value_ty) {
+            Some(param_type) => ReturnInference::Inferred(Some(param_type), result),
+            None => ReturnInference::Unknown(result),
+        }
+    }
+}
+
+/// Full paths of the types, that are inferred, without `native_api_1c::` prefix
+/// of the core crate
+const KNOWN_TYPE_PATHS: &[&str] = &[
+    "std::string::String",
+    "alloc::string::String",
+    "std::vec::Vec",
+    "alloc::vec::Vec",
+    "native_api_1c_core::instances::PendingInstance",
+    "native_api_1c_core::u16_str::U16Str",
+    "native_api_1c_core::u16_str::U16String",
+    "native_api_1c_core::interface::U16Str",
+    "native_api_1c_core::interface::U16String",
+    "native_api_1c_core::ffi::memory_manager::PlatformBlob",
+    "native_api_1c_core::interface::PlatformBlob",
+    "native_api_1c_core::handles::Handle",
+    "chrono::NaiveDateTime",
+    "chrono::naive::NaiveDateTime",
+    "chrono::DateTime",
+    "chrono::FixedOffset",
+    "chrono::offset::FixedOffset",
+    "time::PrimitiveDateTime",
+    "time::OffsetDateTime",
+];
+
+/// Name of the type, which platform type can be inferred from the path: either
+/// a single name, that is expected to be imported, or one of known full paths.
+/// Other paths, e.g. `my::Handle`, may be any type, so they are not inferred
+fn known_type_name(type_path: &syn::TypePath) -> Option<&'static str> {
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let path = &type_path.path;
+    let segments = path
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>();
+    let name = segments.last()?;
+    if segments.len() == 1 && path.leading_colon.is_none() {
+        return KNOWN_TYPE_PATHS
+            .iter()
+            .filter_map(|known| known.rsplit("::").next())
+            .chain(["bool", "i32", "f64"])
+            .find(|known| known == name);
+    }
+    let full_path = match segments.as_slice() {
+        [facade, rest @ ..] if facade == "native_api_1c" => rest.join("::"),
+        _ => segments.join("::"),
+    };
+    KNOWN_TYPE_PATHS
+        .iter()
+        .find(|known| **known == full_path)
+        .and_then(|known| known.rsplit("::").next())
+}
+
+/// Result of return type inference
+#[derive(Debug, PartialEq)]
+pub enum ReturnInference {
+    /// Platform type (`None` for no value) and `Result` flag
+    Inferred(Option<ParamType>, bool),
+    /// Type is not known to the derive, but `Result` flag is
+    Unknown(bool),
+}
+
+/// Element type of `Vec<T>`, used for variadic arguments
+pub fn vec_element_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if known_type_name(type_path) != Some("Vec") {
+        return None;
+    }
+    match generic_args(last_segment).as_slice() {
+        [inner] => Some(inner),
+        _ => None,
+    }
+}
+
+/// Checks if type is `ParamValue`, values of which are passed without conversion
+pub fn is_param_value(ty: &syn::Type) -> bool {
+    is_named_type(ty, "ParamValue")
+}
+
+/// Checks if type is a path, which last segment is `name`, e.g. `interface::ParamValues`
+pub fn is_named_type(ty: &syn::Type, name: &str) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(type_path)
+            if type_path.path.segments.last().is_some_and(|s| s.ident == name)
+    )
+}
+
+fn generic_args(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return vec![];
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_primitive(ty: &syn::Type, name: &str) -> bool {
+    matches!(ty, syn::Type::Path(type_path) if type_path.path.is_ident(name))
+}
+
+pub fn is_unit(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// `T` of `Result<T, E>`, if `ty` is one
+pub fn result_ok_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Result" {
+        return None;
+    }
+    generic_args(last_segment).first().copied()
+}
+
+/// `T` of `Result<T, E>` or `AddInWrapperResult<T>`, if `ty` is one
+pub fn add_in_result_ok_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let Some(ok_type) = result_ok_type(ty) {
+        return Some(ok_type);
+    }
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "AddInWrapperResult" {
+        return None;
+    }
+    generic_args(last_segment).first().copied()
+}
+
 impl ToTokens for ParamType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         *tokens = match self {
@@ -65,13 +312,15 @@ impl ToTokens for ParamType {
             ParamType::I32 => {
                 quote! { native_api_1c::native_api_1c_core::interface::ParamValue::I32 }
             }
-            ParamType::F64 => {
+            // handles are converted with `From`/`TryFrom`, as they may not fit into `I32`
+            ParamType::F64 | ParamType::Handle => {
                 quote! { native_api_1c::native_api_1c_core::interface::ParamValue::F64 }
             }
             ParamType::Date => {
                 quote! { native_api_1c::native_api_1c_core::interface::ParamValue::Date }
             }
-            ParamType::String => {
+            // JSON is transported as string
+            ParamType::String | ParamType::Json | ParamType::U16Str => {
                 quote! { native_api_1c::native_api_1c_core::interface::ParamValue::String }
             }
             ParamType::Blob => {
@@ -100,6 +349,116 @@ impl FromMeta for PropName {
     }
 }
 
+impl PropName {
+    /// Name, set with string literal. Names from constants can't be checked
+    /// by the macro
+    pub fn literal(&self) -> Option<&syn::LitStr> {
+        match self {
+            PropName::StringLiteral(str_lit) => Some(str_lit),
+            PropName::Ident(_) => None,
+        }
+    }
+}
+
+/// Names in other languages, set with `aliases(kk = "...", uk = "...")`,
+/// by lowercase language codes
+#[derive(Debug, Default)]
+pub struct NameAliases(pub Vec<(String, PropName)>);
+
+impl FromMeta for NameAliases {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        let mut aliases: Vec<(String, PropName)> = vec![];
+        for item in items {
+            let darling::ast::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = item else {
+                return Err(
+                    darling::Error::custom("expected `language = \"name\"`").with_span(item)
+                );
+            };
+            let Some(language) = name_value.path.get_ident() else {
+                return Err(darling::Error::custom("expected language code, e.g. `kk`")
+                    .with_span(&name_value.path));
+            };
+            let language = language.to_string().to_lowercase();
+            if matches!(language.as_str(), "en" | "ru")
+                || aliases.iter().any(|(other, _)| *other == language)
+            {
+                return Err(darling::Error::custom(format!(
+                    "Name in language `{language}` is already set"
+                ))
+                .with_span(&name_value.path));
+            }
+            aliases.push((language, PropName::from_expr(&name_value.value)?));
+        }
+        Ok(Self(aliases))
+    }
+}
+
+/// Checks that literal names are valid BSL identifiers: a letter or `_`,
+/// followed by letters, digits or `_`, and returns them
+pub fn literal_names<'a>(
+    names: impl IntoIterator<Item = &'a PropName>,
+) -> darling::Result<Vec<syn::LitStr>> {
+    let mut errors = darling::Error::accumulator();
+    let mut literals = vec![];
+    for str_lit in names.into_iter().filter_map(PropName::literal) {
+        let name = str_lit.value();
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+        if valid {
+            literals.push(str_lit.clone());
+        } else {
+            errors.push(
+                darling::Error::custom(format!("`{name}` is not a valid 1C identifier"))
+                    .with_span(str_lit),
+            );
+        }
+    }
+    errors.finish_with(literals)
+}
+
+/// Literal names of AddIn methods and properties, 1C finds them regardless of
+/// case and the first match wins, so names must be unique
+#[derive(Default)]
+pub struct UniqueNames {
+    /// Kinds of members, that use lowercase names
+    names: HashMap<String, &'static str>,
+}
+
+impl UniqueNames {
+    /// Checks names of one member, which may have the same name in both
+    /// languages
+    /// # Arguments
+    /// * `kind` - `method` or `property`, used in errors
+    /// * `names` - literal names of the member
+    pub fn check(&mut self, kind: &'static str, names: &[syn::LitStr]) -> darling::Result<()> {
+        let mut errors = darling::Error::accumulator();
+        let mut member_names = HashMap::new();
+        for str_lit in names {
+            let name = str_lit.value();
+            let key = name.to_lowercase();
+            match self.names.get(&key) {
+                Some(other) => {
+                    let other = if *other == kind {
+                        format!("another {kind}")
+                    } else {
+                        format!("a {other}")
+                    };
+                    errors.push(
+                        darling::Error::custom(format!("Name `{name}` is already used by {other}"))
+                            .with_span(str_lit),
+                    );
+                }
+                None => {
+                    member_names.insert(key, kind);
+                }
+            }
+        }
+        self.names.extend(member_names);
+        errors.finish()
+    }
+}
+
 impl From<PropName> for proc_macro2::TokenStream {
     fn from(prop_name: PropName) -> proc_macro2::TokenStream {
         match prop_name {
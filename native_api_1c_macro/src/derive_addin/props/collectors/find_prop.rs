@@ -24,6 +24,7 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for FindPropCollector {
         for (prop_index, prop_desc) in iter {
             let name_literal = prop_desc.name_literal.clone();
             let name_ru_literal = prop_desc.name_ru_literal.clone();
+            let alias_names = prop_desc.aliases.iter().map(|(_, name)| name);
 
             find_prop_body.extend(quote! {
                 if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_literal) == name { 
@@ -32,6 +33,9 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for FindPropCollector {
                 if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_ru_literal) == name { 
                     return Some(#prop_index) 
                 };
+                #(if native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#alias_names) == name {
+                    return Some(#prop_index)
+                };)*
             });
         }
 
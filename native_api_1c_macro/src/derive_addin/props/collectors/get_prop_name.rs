@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::derive_addin::props::PropDesc;
+use crate::derive_addin::{props::PropDesc, utils::localized_name};
 
 use super::{empty_prop_collector_error, PropCollector};
 
@@ -22,12 +22,18 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for GetPropNameCollector {
         let mut get_prop_name_body = TokenStream::new();
 
         for (prop_index, prop_desc) in iter {
-            let name_literal = prop_desc.name_literal.clone();
-            let name_ru_literal = prop_desc.name_ru_literal.clone();
+            let name = localized_name(
+                &prop_desc.name_literal,
+                &prop_desc.name_ru_literal,
+                &prop_desc.aliases,
+            );
 
             get_prop_name_body.extend(quote! {
-                if num == #prop_index && alias == 0 { return Some(native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_literal).into()) };
-                if num == #prop_index { return Some(native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(#name_ru_literal).into()) };
+                if num == #prop_index {
+                    return Some(native_api_1c::native_api_1c_core::ffi::string_utils::os_string_nil(
+                        #name).into()
+                    )
+                };
             });
         }
 
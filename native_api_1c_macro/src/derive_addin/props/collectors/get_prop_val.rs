@@ -27,8 +27,12 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for GetPropValCollector {
                 continue;
             }
 
-            let prop_ident = &prop_desc.ident;
-            let prop_setter = expr_to_os_value(&quote! {self.#prop_ident}, &prop_desc.ty, false);
+            let prop_value = match (&prop_desc.getter, &prop_desc.ident) {
+                (Some(getter), _) => quote! { #getter(self) },
+                (None, Some(prop_ident)) => quote! { self.#prop_ident },
+                (None, None) => unreachable!("property without field must have getter"),
+            };
+            let prop_setter = expr_to_os_value(&prop_value, &prop_desc.ty, false);
             body.extend(quote! {
                 if num == #prop_index {
                     return Ok(#prop_setter);
@@ -1,7 +1,10 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::derive_addin::{props::PropDesc, utils::expr_from_os_value};
+use crate::derive_addin::{
+    props::PropDesc,
+    utils::{expr_from_os_value, report_error_tkn},
+};
 
 use super::{empty_prop_collector_error, PropCollector};
 
@@ -26,13 +29,32 @@ impl<'a> FromIterator<(usize, &'a PropDesc)> for SetPropValCollector {
                 continue;
             }
 
-            let prop_ident = &prop_desc.ident;
             let prop_getter = expr_from_os_value(&quote! { val }, &prop_desc.ty);
 
+            let prop_update = match (&prop_desc.setter, &prop_desc.ident) {
+                (Some(setter), _) => {
+                    let report = report_error_tkn(&quote! { &err }, &prop_desc.name_literal);
+                    quote! {
+                        let result = native_api_1c::native_api_1c_core::interface::IntoSetterResult::into_setter_result(
+                            #setter(self, #prop_getter)
+                        );
+                        if let Err(err) = result {
+                            #report
+                            return Err(());
+                        }
+                        return Ok(());
+                    }
+                }
+                (None, Some(prop_ident)) => quote! {
+                    self.#prop_ident = #prop_getter;
+                    return Ok(());
+                },
+                (None, None) => unreachable!("property without field must have setter"),
+            };
+
             body.extend(quote! {
                 if num == #prop_index {
-                    self.#prop_ident = #prop_getter.into();
-                    return Ok(());
+                    #prop_update
                 };
             });
         }
@@ -8,12 +8,24 @@ pub mod parse;
 
 #[derive(Debug)]
 pub struct PropDesc {
-    pub ident: Ident,
+    /// Field of the property, `None` for properties, declared on the struct
+    pub ident: Option<Ident>,
 
     pub name_literal: TokenStream,
     pub name_ru_literal: TokenStream,
+    /// Names in other languages by lowercase language codes
+    pub aliases: Vec<(String, TokenStream)>,
+    /// Names, set with string literals, checked for uniqueness
+    pub names: Vec<syn::LitStr>,
+    /// Doc comment of the field
+    pub doc: String,
 
     pub readable: bool,
     pub writable: bool,
     pub ty: ParamType,
+
+    /// Method, that computes value of the property instead of reading the field
+    pub getter: Option<syn::Path>,
+    /// Method, that is called with new value of the property instead of writing the field
+    pub setter: Option<syn::Path>,
 }
@@ -1,8 +1,12 @@
 use darling::{FromField, FromMeta};
 
+use proc_macro2::Ident;
 use syn::{Attribute, DataStruct};
 
-use crate::derive_addin::{parsers::PropName, utils::ident_option_to_darling_err};
+use crate::derive_addin::{
+    parsers::{literal_names, NameAliases, PropName},
+    utils::{doc_comment, ident_option_to_darling_err, macros::tkn_err},
+};
 
 use super::{ParamType, PropDesc};
 
@@ -29,16 +33,74 @@ impl FromField for PropDesc {
         let add_in_prop_attr = add_in_prop_attr[0];
 
         let prop_meta = PropMeta::from_meta(&add_in_prop_attr.meta)?;
+        PropDesc::from_meta(
+            Some(field_ident.clone()),
+            prop_meta,
+            add_in_prop_attr,
+            doc_comment(&field.attrs),
+        )
+    }
+}
+
+impl PropDesc {
+    /// Property, declared with `#[add_in_prop(...)]` on the struct, that has
+    /// no field and is only accessed with `getter` and `setter`
+    fn from_struct_attr(attr: &Attribute) -> darling::Result<Self> {
+        let prop_meta = PropMeta::from_meta(&attr.meta)?;
+        if prop_meta.readable.is_some() && prop_meta.getter.is_none() {
+            return tkn_err!("Readable property without field must have `getter`", attr);
+        }
+        if prop_meta.writable.is_some() && prop_meta.setter.is_none() {
+            return tkn_err!("Writable property without field must have `setter`", attr);
+        }
+        if prop_meta.getter.is_none() && prop_meta.setter.is_none() {
+            return tkn_err!(
+                "Property without field must have `getter` or `setter`",
+                attr
+            );
+        }
+        Self::from_meta(None, prop_meta, attr, String::new())
+    }
+
+    fn from_meta(
+        ident: Option<Ident>,
+        prop_meta: PropMeta,
+        attr: &Attribute,
+        doc: String,
+    ) -> darling::Result<Self> {
+        if prop_meta.ty == ParamType::Json {
+            return Err(
+                darling::Error::custom("Type `Json` is not supported for properties")
+                    .with_span(attr),
+            );
+        }
+
+        let names = literal_names(
+            [&prop_meta.name, &prop_meta.name_ru]
+                .into_iter()
+                .chain(prop_meta.aliases.0.iter().map(|(_, name)| name)),
+        )?;
 
         Ok(Self {
-            ident: field_ident.clone(),
+            ident,
 
             name_literal: prop_meta.name.into(),
             name_ru_literal: prop_meta.name_ru.into(),
+            aliases: prop_meta
+                .aliases
+                .0
+                .into_iter()
+                .map(|(language, name)| (language, name.into()))
+                .collect(),
+            names,
+            doc,
 
-            readable: prop_meta.readable.is_some(),
-            writable: prop_meta.writable.is_some(),
+            readable: prop_meta.readable.is_some() || prop_meta.getter.is_some(),
+            writable: prop_meta.writable.is_some() || prop_meta.setter.is_some(),
             ty: prop_meta.ty,
+
+            getter: prop_meta.getter,
+            setter: prop_meta.setter,
         })
     }
 }
@@ -48,13 +110,28 @@ pub struct PropMeta {
     pub ty: ParamType,
     pub name: PropName,
     pub name_ru: PropName,
+    #[darling(default)]
+    pub aliases: NameAliases,
     pub readable: Option<()>,
     pub writable: Option<()>,
+    pub getter: Option<syn::Path>,
+    pub setter: Option<syn::Path>,
 }
 
-pub fn parse_props(struct_data: &DataStruct) -> Result<Vec<PropDesc>, darling::Error> {
+/// Properties, declared on the struct, followed by properties of its fields
+pub fn parse_props(
+    attrs: &[Attribute],
+    struct_data: &DataStruct,
+) -> Result<Vec<PropDesc>, darling::Error> {
     let mut props = vec![];
 
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("add_in_prop"))
+    {
+        props.push(PropDesc::from_struct_attr(attr)?);
+    }
+
     for field in &struct_data.fields {
         let has_add_in_prop_attr = field
             .attrs
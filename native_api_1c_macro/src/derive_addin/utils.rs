@@ -62,6 +62,22 @@ pub fn expr_to_os_value(
                 #ty(#os_string_fn(&#expr.clone()).clone().into())
             }
         },
+        ParamType::U16Str => quote! {
+            {
+                let _ = "expr_to_os_value: specific case for U16Str";
+                #ty(::std::vec::Vec::<u16>::from(
+                    native_api_1c::native_api_1c_core::interface::U16String::from(#expr.clone())
+                ))
+            }
+        },
+        ParamType::Handle => quote! {
+            {
+                let _ = "expr_to_os_value: specific case for Handle";
+                <native_api_1c::native_api_1c_core::interface::ParamValue as ::core::convert::From<_>>::from(
+                    #expr.clone()
+                )
+            }
+        },
         _ => quote! {
             {
                 let _ = "expr_to_os_value: generic case";
@@ -71,6 +87,9 @@ pub fn expr_to_os_value(
     }
 }
 
+/// Unwraps `ParamValue` of the type and converts it into the type expected in
+/// the context, `Date` conversion is fallible as 1C can pass an invalid date.
+/// Evaluates to `Err(())` on failure
 pub fn expr_from_os_value(expr: &TokenStream, ty: &ParamType) -> proc_macro2::TokenStream {
     match ty {
         ParamType::String => quote! {
@@ -81,7 +100,7 @@ pub fn expr_from_os_value(expr: &TokenStream, ty: &ParamType) -> proc_macro2::To
                         Ok(native_api_1c::native_api_1c_core::ffi::string_utils::from_os_string(&val))
                     },
                     _ => Err(()),
-                }?.clone()
+                }?.clone().into()
             }
         },
         ParamType::Blob => quote! {
@@ -92,7 +111,37 @@ pub fn expr_from_os_value(expr: &TokenStream, ty: &ParamType) -> proc_macro2::To
                         Ok(val)
                     },
                     _ => Err(()),
-                }?.clone()
+                }?.clone().into()
+            }
+        },
+        ParamType::U16Str => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for U16Str";
+                match &#expr {
+                    #ty(val) => {
+                        Ok(::core::convert::From::from(
+                            native_api_1c::native_api_1c_core::interface::U16Str::from_slice(val)
+                        ))
+                    },
+                    _ => Err(()),
+                }?
+            }
+        },
+        ParamType::Date => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Date";
+                match &#expr {
+                    #ty(val) => {
+                        ::core::convert::TryFrom::try_from(*val).map_err(|_| ())
+                    },
+                    _ => Err(()),
+                }?
+            }
+        },
+        ParamType::Handle => quote! {
+            {
+                let _ = "expr_from_os_value: specific case for Handle";
+                ::core::convert::TryFrom::try_from(#expr.clone()).map_err(|_| ())?
             }
         },
         _ => quote! {
@@ -103,8 +152,81 @@ pub fn expr_from_os_value(expr: &TokenStream, ty: &ParamType) -> proc_macro2::To
                         Ok(val)
                     },
                     _ => Err(()),
-                }?.clone()
+                }?.clone().into()
             }
         },
     }
 }
+
+/// Expression of the name of method or property, reported to 1C for `alias`:
+/// English for alias 0, otherwise the name in the interface language, if it's
+/// set with `aliases`, English for English interface and Russian for others
+pub fn localized_name(
+    name: &TokenStream,
+    name_ru: &TokenStream,
+    aliases: &[(String, TokenStream)],
+) -> TokenStream {
+    let (languages, alias_names): (Vec<&String>, Vec<&TokenStream>) = aliases
+        .iter()
+        .map(|(language, name)| (language, name))
+        .unzip();
+    quote! {
+        {
+            let language = native_api_1c::native_api_1c_core::interface::AddInWrapper::ui_language(self)
+                .and_then(native_api_1c::native_api_1c_core::localization::UiLanguage::code);
+            match (alias, language) {
+                (0, _) => #name,
+                #((_, Some(#languages)) => #alias_names,)*
+                (_, Some("en")) => #name,
+                _ => #name_ru,
+            }
+        }
+    }
+}
+
+/// Doc comment from `#[doc = "..."]` attributes, lines without the leading
+/// space. Docs, that are not string literals, e.g. `include_str!`, are skipped
+pub fn doc_comment(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(str_lit),
+                        ..
+                    }),
+                ..
+            }) => Some(str_lit.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Reports error of AddIn method or property setter to 1C with `source`
+/// being its name. `Message` is translated with `message_catalog` into
+/// `ui_language` of the AddIn, or the language, kept by the component, if it
+/// has no `#[add_in_lang]` field. Other errors are reported with their text
+/// if they implement `Display`, see `errors::ErrorRef`
+pub fn report_error_tkn(err: &TokenStream, source: &TokenStream) -> TokenStream {
+    quote! {
+        {
+            use native_api_1c::native_api_1c_core::errors::{
+                ErrorRef, ErrorReport, ReportDisplay as _, ReportMessage as _, ReportOpaque as _,
+            };
+            use native_api_1c::native_api_1c_core::interface::AddInWrapper;
+            use native_api_1c::native_api_1c_core::localization::UiLanguage;
+            UiLanguage::with_current(|current| {
+                (&&&ErrorRef(#err)).report(&ErrorReport {
+                    connection: *self.connection,
+                    source: #source,
+                    language: AddInWrapper::ui_language(self).or(current),
+                    catalog: AddInWrapper::message_catalog(self),
+                })
+            });
+        }
+    }
+}
@@ -4,7 +4,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 
 use parse::ExternAddInsDesc;
-use syn::{LitByte, LitStr};
+use syn::LitStr;
 
 static ASCII_LOWER: [char; 50] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', // numbers
@@ -24,37 +24,68 @@ pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStre
         .into();
     }
 
+    // `name` used to be ignored, so classes of libraries without factory
+    // classes keep their aliases, and objects, created by 1C code, still work
+    let use_names = extern_add_ins
+        .components
+        .iter()
+        .any(|desc| desc.factory.is_some());
+    let class_names = extern_add_ins
+        .components
+        .iter()
+        .zip(ASCII_LOWER)
+        .map(|(add_in_desc, alias)| match &add_in_desc.name_override {
+            Some(name) if use_names => name.clone(),
+            _ => alias.to_string(),
+        })
+        .collect::<Vec<_>>();
+    for (i, name) in class_names.iter().enumerate() {
+        if class_names[..i].contains(name) {
+            let message = format!("duplicate component name `{name}`");
+            return quote! { compile_error!(#message); }.into();
+        }
+    }
+
+    let mut factories = TokenStream::new();
     let mut get_class_object_body = TokenStream::new();
-    for (i, add_in_desc) in extern_add_ins.components.iter().enumerate() {
-        let alias = ASCII_LOWER[i];
-        let alias_literal = LitByte::new(alias as u8, Span::call_site());
-        let alias_literal = alias_literal.to_token_stream();
+    for (add_in_desc, class_name) in extern_add_ins.components.iter().zip(&class_names) {
+        let class_name_literal = LitStr::new(class_name, Span::call_site());
         let init_tkn = &add_in_desc.init_tkn;
 
-        get_class_object_body.extend(quote! {
-            #alias_literal => {
+        let create = if let Some(factory) = &add_in_desc.factory {
+            factories.extend(quote! {
+                pub static #factory: native_api_1c::native_api_1c_core::instances::Factory<
+                    #init_tkn,
+                > = native_api_1c::native_api_1c_core::instances::Factory::new(
+                    #class_name_literal,
+                );
+            });
+            quote! { #factory.create(component) }
+        } else {
+            quote! {
                 let add_in = #init_tkn;
                 native_api_1c::native_api_1c_core::ffi::create_component(component, add_in)
-            },
+            }
+        };
+        get_class_object_body.extend(quote! {
+            if name == utf16_lit::utf16_null!(#class_name_literal) {
+                return { #create };
+            }
         })
     }
     let get_class_object_body = quote! {
-        match *name as u8 {
-            #get_class_object_body
-            _ => 0,
-        }
+        let name = native_api_1c::native_api_1c_core::ffi::string_utils::get_str(name);
+        #get_class_object_body
+        0
     };
 
-    let names = ASCII_LOWER[..extern_add_ins.components.len()]
-        .iter()
-        .map(char::to_string)
-        .collect::<Vec<_>>()
-        .join("|");
-    let names_lit = LitStr::new(&names, Span::call_site());
+    let names_lit = LitStr::new(&class_names.join("|"), Span::call_site());
     let names_lit = names_lit.to_token_stream();
     let get_class_names_body = quote! { utf16_lit::utf16_null!(#names_lit).as_ptr() };
 
     let result = quote! {
+        #factories
+
         pub static mut PLATFORM_CAPABILITIES: std::sync::atomic::AtomicI32 =
             std::sync::atomic::AtomicI32::new(-1);
 
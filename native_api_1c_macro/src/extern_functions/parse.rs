@@ -34,11 +34,18 @@ impl Parse for ExternAddInsDesc {
 struct ExternAddInComponentMeta {
     #[darling(rename = "name")]
     name_override: Option<String>,
+    factory: Option<syn::Ident>,
 }
 
 #[derive(Debug)]
 pub struct ExternAddInComponentDesc {
+    /// Name of the class, used in `New("AddIn.<Library>.<Name>")`, if the
+    /// library has factory classes
     pub name_override: Option<String>,
+    /// Name of the `instances::Factory` static, declared for the class, which
+    /// objects are created from AddIns of `init_tkn` type, prepared with
+    /// `instances::Children::prepare`
+    pub factory: Option<syn::Ident>,
     pub init_tkn: TokenStream,
 }
 
@@ -57,21 +64,29 @@ impl Parse for ExternAddInComponentDesc {
         }
         let attr = add_in_component_attrs.first();
         let addin_desc = attr.map(|attr| ExternAddInComponentMeta::from_meta(&attr.meta));
-        let addin_desc = match addin_desc {
-            Some(Ok(desc)) => Some(desc),
-            Some(Err(err)) => {
-                return Err(syn::Error::new(
-                    add_in_component_attrs[1].span(),
-                    err.to_string(),
-                ))
+        let addin_desc = match (attr, addin_desc) {
+            (_, Some(Ok(desc))) => Some(desc),
+            (Some(attr), Some(Err(err))) => {
+                return Err(syn::Error::new(attr.span(), err.to_string()))
             }
-            None => None,
+            _ => None,
         };
+        let (name_override, factory) = match addin_desc {
+            Some(desc) => (desc.name_override, desc.factory),
+            None => (None, None),
+        };
+        if let (Some(attr), None, Some(_)) = (attr, &name_override, &factory) {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`factory` component must have `name` set",
+            ));
+        }
 
         let init_tkn = input.call(TokenStream::parse).unwrap();
 
         Ok(ExternAddInComponentDesc {
-            name_override: addin_desc.and_then(|desc| desc.name_override),
+            name_override,
+            factory,
             init_tkn,
         })
     }
@@ -1,7 +1,22 @@
+mod add_in_methods;
 mod derive_addin;
 mod extern_functions;
+mod message_catalog;
 
-#[proc_macro_derive(AddIn, attributes(add_in_prop, add_in_func, add_in_con, arg, returns))]
+#[proc_macro_derive(
+    AddIn,
+    attributes(
+        add_in,
+        add_in_prop,
+        add_in_func,
+        add_in_con,
+        add_in_handles,
+        add_in_shared,
+        add_in_lang,
+        arg,
+        returns
+    )
+)]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_addin::derive(input)
 }
@@ -10,3 +25,16 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 pub fn extern_functions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     extern_functions::extern_functions(input)
 }
+
+#[proc_macro_attribute]
+pub fn add_in_methods(
+    attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    add_in_methods::add_in_methods(attr, input)
+}
+
+#[proc_macro]
+pub fn message_catalog(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    message_catalog::message_catalog(input)
+}
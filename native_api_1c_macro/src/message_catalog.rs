@@ -0,0 +1,154 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+/// `default = "ru", ru = "messages/ru.txt", en = "messages/en.txt"`
+struct CatalogDesc {
+    default: LitStr,
+    languages: Vec<(Ident, LitStr)>,
+}
+
+impl Parse for CatalogDesc {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let entries =
+            Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(input, |input| {
+                let name = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Ok((name, input.parse()?))
+            })?;
+
+        let mut default = None;
+        let mut languages = Vec::<(Ident, LitStr)>::new();
+        for (name, value) in entries {
+            if name == "default" {
+                default = Some(value);
+            } else if languages.iter().any(|(language, _)| *language == name) {
+                return Err(syn::Error::new(name.span(), "language is repeated"));
+            } else {
+                languages.push((name, value));
+            }
+        }
+        let Some(default) = default else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected `default = \"<language>\"`",
+            ));
+        };
+        if !languages
+            .iter()
+            .any(|(language, _)| *language == default.value())
+        {
+            return Err(syn::Error::new(
+                default.span(),
+                "default language must have messages",
+            ));
+        }
+        Ok(Self { default, languages })
+    }
+}
+
+pub fn message_catalog(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let desc = syn::parse_macro_input!(input as CatalogDesc);
+    match catalog_tkn(&desc) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn catalog_tkn(desc: &CatalogDesc) -> syn::Result<TokenStream> {
+    let base = base_dir();
+    let mut sources = Vec::new();
+    for (language, path) in &desc.languages {
+        let file = base.join(path.value());
+        let read = |file: &PathBuf| {
+            // `include_str!` resolves relative paths from the file, that
+            // invokes the macro, so it gets the absolute one
+            let file = file.canonicalize()?;
+            Ok::<_, std::io::Error>((std::fs::read_to_string(&file)?, file))
+        };
+        let (source, file) = read(&file).map_err(|err| {
+            let message = format!("unable to read `{}`: {err}", file.display());
+            syn::Error::new(path.span(), message)
+        })?;
+        let keys = message_keys(&source).map_err(|(line, message)| {
+            syn::Error::new(path.span(), format!("line {line}: {message}"))
+        })?;
+        sources.push((language, path, file, keys));
+    }
+
+    // every message must have text in the default language, which is used,
+    // when interface language misses it
+    let default = desc.default.value();
+    let (_, _, _, default_keys) = sources
+        .iter()
+        .find(|(language, ..)| **language == default)
+        .expect("default language is checked by parser");
+    for (language, path, _, keys) in &sources {
+        let mut missing = keys.difference(default_keys).cloned().collect::<Vec<_>>();
+        missing.sort();
+        if !missing.is_empty() {
+            let message = format!(
+                "messages of `{language}` are missing in default language `{default}`: {}",
+                missing.join(", ")
+            );
+            return Err(syn::Error::new(path.span(), message));
+        }
+    }
+
+    let default = &desc.default;
+    let messages = sources.iter().map(|(language, _, file, _)| {
+        let language = LitStr::new(&language.to_string(), language.span());
+        let file = LitStr::new(&file.to_string_lossy(), Span::call_site());
+        quote! {
+            .and_then(|catalog| catalog.with_messages(#language, include_str!(#file)))
+        }
+    });
+    Ok(quote! {
+        Ok::<_, native_api_1c::native_api_1c_core::localization::CatalogError>(
+            native_api_1c::native_api_1c_core::localization::MessageCatalog::new(#default),
+        )
+        #(#messages)*
+        .expect("messages are checked by `message_catalog!`")
+    })
+}
+
+/// Directory, which paths are relative to, like the ones of `include_str!`
+fn base_dir() -> PathBuf {
+    let file = proc_macro::Span::call_site().local_file();
+    match file.as_ref().and_then(|file| file.parent()) {
+        Some(dir) => dir.to_owned(),
+        None => std::env::var("CARGO_MANIFEST_DIR")
+            .unwrap_or_default()
+            .into(),
+    }
+}
+
+/// Keys of messages, parsed the same way as `MessageCatalog::with_messages`
+/// does, or the number of invalid line and error
+fn message_keys(source: &str) -> Result<HashSet<String>, (usize, String)> {
+    let mut keys = HashSet::new();
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| (index + 1, message);
+        let Some((key, _)) = line.split_once('=') else {
+            return Err(error("expected `key = text`".to_string()));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(error("key is empty".to_string()));
+        }
+        if !keys.insert(key.to_owned()) {
+            return Err(error(format!("key `{key}` is repeated")));
+        }
+    }
+    Ok(keys)
+}
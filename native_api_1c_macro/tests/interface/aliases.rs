@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::AddInWrapper,
+    localization::UiLanguage,
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::{fixture, rstest};
+
+const COUNT_NAME_UK: &str = "Кількість";
+
+#[derive(AddIn)]
+struct AliasesAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_lang]
+    language: UiLanguage,
+
+    #[add_in_prop(
+        ty = Int,
+        name = "Count",
+        name_ru = "Количество",
+        aliases(kk = "Саны", uk = COUNT_NAME_UK),
+        readable
+    )]
+    count: i32,
+
+    #[add_in_func(name = "Run", name_ru = "Выполнить", aliases(kk = "Орындау"))]
+    run: fn(),
+}
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct MethodsAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_lang]
+    language: UiLanguage,
+}
+
+#[add_in_methods]
+impl MethodsAddIn {
+    #[add_in_func(name = "Stop", name_ru = "Остановить", aliases(uk = "Зупинити"))]
+    fn stop(&mut self) {}
+}
+
+#[fixture]
+fn add_in() -> AliasesAddIn {
+    AliasesAddIn {
+        connection: Arc::new(None),
+        language: UiLanguage::new(),
+        count: 0,
+        run: || {},
+    }
+}
+
+#[rstest]
+#[case("Count", Some(0))]
+#[case("количество", None)]
+#[case("Саны", Some(0))]
+#[case(COUNT_NAME_UK, Some(0))]
+fn test_find_prop_by_alias(add_in: AliasesAddIn, #[case] name: &str, #[case] index: Option<usize>) {
+    assert_eq!(add_in.find_prop(&os_string_nil(name)), index);
+}
+
+#[rstest]
+#[case(None, 0, "Count")]
+#[case(None, 1, "Количество")]
+#[case(Some("kk"), 0, "Count")]
+#[case(Some("kk"), 1, "Саны")]
+#[case(Some("uk_UA"), 1, COUNT_NAME_UK)]
+#[case(Some("en"), 1, "Count")]
+#[case(Some("ru"), 1, "Количество")]
+#[case(Some("de"), 1, "Количество")]
+fn test_prop_name_for_language(
+    mut add_in: AliasesAddIn,
+    #[case] language: Option<&str>,
+    #[case] alias: usize,
+    #[case] expected: &str,
+) {
+    if let Some(language) = language {
+        add_in.set_user_interface_language_code(&os_string_nil(language));
+    }
+    assert_eq!(
+        add_in.get_prop_name(0, alias),
+        Some(os_string_nil(expected))
+    );
+}
+
+#[rstest]
+fn test_method_name_for_language(mut add_in: AliasesAddIn) {
+    assert_eq!(add_in.find_method(&os_string_nil("Орындау")), Some(0));
+
+    add_in.set_user_interface_language_code(&os_string("KK"));
+    assert!(add_in.ui_language().unwrap().is("kk"));
+    assert_eq!(add_in.get_method_name(0, 1), Some(os_string_nil("Орындау")));
+    assert_eq!(add_in.get_method_name(0, 0), Some(os_string_nil("Run")));
+
+    add_in.set_user_interface_language_code(&os_string("uk"));
+    assert_eq!(
+        add_in.get_method_name(0, 1),
+        Some(os_string_nil("Выполнить"))
+    );
+}
+
+#[rstest]
+fn test_method_name_for_locale(mut add_in: AliasesAddIn) {
+    add_in.set_locale(&os_string_nil("kk_KZ"));
+    assert_eq!(add_in.get_method_name(0, 1), Some(os_string_nil("Орындау")));
+
+    add_in.set_user_interface_language_code(&os_string_nil("ru"));
+    assert_eq!(
+        add_in.get_method_name(0, 1),
+        Some(os_string_nil("Выполнить"))
+    );
+}
+
+#[rstest]
+fn test_impl_method_name_for_language() {
+    let mut add_in = MethodsAddIn {
+        connection: Arc::new(None),
+        language: UiLanguage::new(),
+    };
+    assert_eq!(add_in.find_method(&os_string_nil("Зупинити")), Some(0));
+    assert_eq!(
+        add_in.get_method_name(0, 1),
+        Some(os_string_nil("Остановить"))
+    );
+
+    add_in.set_user_interface_language_code(&os_string_nil("uk"));
+    assert_eq!(
+        add_in.get_method_name(0, 1),
+        Some(os_string_nil("Зупинити"))
+    );
+}
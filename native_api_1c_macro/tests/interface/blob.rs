@@ -0,0 +1,122 @@
+use std::{cell::Cell, ptr, sync::Arc};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        provided_types::{TVariant, VariantType, VariantValue},
+    },
+    interface::{AddInWrapper, AllocationError, ParamValue, ParamValues, PlatformBlob},
+};
+use native_api_1c_macro::AddIn;
+use native_api_1c_test_utils::{allocated, free_variant, FakeHost};
+use rstest::{fixture, rstest};
+
+thread_local! {
+    /// Data of the last blob, filled by `Fill`
+    static FILLED: Cell<*const u8> = const { Cell::new(ptr::null()) };
+}
+
+#[derive(AddIn)]
+struct BlobAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Len", name_ru = "Длина")]
+    pub len: fn(&[u8]) -> i32,
+
+    #[add_in_func(name = "Reverse", name_ru = "Перевернуть")]
+    pub reverse: fn(Vec<u8>) -> Vec<u8>,
+
+    #[add_in_func(name = "Append", name_ru = "Дописать")]
+    pub append: fn(&mut Vec<u8>),
+
+    #[add_in_func(name = "Fill", name_ru = "Заполнить")]
+    pub fill: fn(i32) -> Result<PlatformBlob, AllocationError>,
+}
+
+#[fixture]
+fn add_in() -> BlobAddIn {
+    BlobAddIn {
+        connection: Arc::new(None),
+        len: |blob| blob.len() as i32,
+        reverse: |mut blob| {
+            blob.reverse();
+            blob
+        },
+        append: |blob| blob.push(0xff),
+        fill: |len| {
+            let mut blob = PlatformBlob::new(len as usize)?;
+            blob.fill(0xaa);
+            FILLED.set(blob.as_ptr());
+            Ok(blob)
+        },
+    }
+}
+
+#[rstest]
+#[case(vec![1, 2, 3], 3)]
+#[case(vec![], 0)]
+fn test_call_borrowed_arg(mut add_in: BlobAddIn, #[case] blob: Vec<u8>, #[case] expected: i32) {
+    let mut params = ParamValues::new(vec![ParamValue::Blob(blob)]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(expected)));
+}
+
+#[rstest]
+fn test_call_owned_arg_and_return(mut add_in: BlobAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Blob(vec![1, 2, 3])]);
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(ParamValue::Blob(vec![3, 2, 1])));
+}
+
+#[rstest]
+fn test_call_inout_arg(mut add_in: BlobAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Blob(vec![1])]);
+
+    let result = add_in.call_as_proc(2, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[0], ParamValue::Blob(vec![1, 0xff]));
+}
+
+#[rstest]
+fn test_call_wrong_type(mut add_in: BlobAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(1)]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert!(result.is_err());
+}
+
+/// `PlatformBlob` is allocated with MemoryManager of the call from 1C, so it
+/// fails outside of it
+#[rstest]
+fn test_platform_blob_outside_call(mut add_in: BlobAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(4)]);
+
+    let result = add_in.call_as_func(3, &mut params);
+    assert!(result.is_err());
+}
+
+/// `PlatformBlob` is returned to 1C as the buffer, the AddIn filled in,
+/// without copying
+#[rstest]
+fn test_platform_blob_returned_without_copy(add_in: BlobAddIn) {
+    let host = FakeHost::new(add_in, true);
+    let mut params = [TVariant {
+        value: VariantValue { i32: 4 },
+        elements: 0,
+        vt: VariantType::Int32,
+    }];
+
+    let result = host.call_as_func(3, &mut params).unwrap();
+    assert_eq!(result.vt, VariantType::Blob);
+    assert_eq!(result.as_blob(), Some([0xaa; 4].as_slice()));
+    assert_eq!(
+        unsafe { result.value.data_blob.ptr }.cast_const(),
+        FILLED.get()
+    );
+
+    free_variant(&result);
+    assert!(allocated().is_empty());
+}
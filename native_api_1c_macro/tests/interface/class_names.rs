@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::{
+    connection::Connection,
+    string_utils::{get_str, os_string},
+};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+#[derive(AddIn, Default)]
+struct Main {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[derive(AddIn, Default)]
+struct Other {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+extern_functions! {
+    #[add_in_component(name = "Main")]
+    Main::default(),
+    Other::default(),
+}
+
+#[test]
+fn test_names_without_factory_classes() {
+    // libraries without factory classes keep the aliases of classes
+    let names = unsafe { get_str(GetClassNames()) };
+    assert_eq!(names, os_string("0|1\0"));
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+use native_api_1c_test_utils::{connection, take_errors};
+
+const MAX_QUEUE_LENGTH: i32 = 10;
+
+#[derive(AddIn)]
+#[add_in_prop(ty = Bool, name = "Connected", name_ru = "Подключен", getter = Self::is_connected)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = "LastError", name_ru = "ПоследняяОшибка", getter = Self::last_error)]
+    last_error: Option<String>,
+
+    #[add_in_prop(
+        ty = Int,
+        name = "QueueLength",
+        name_ru = "ДлинаОчереди",
+        getter = Self::queue_length,
+        setter = Self::set_queue_length
+    )]
+    queue: Vec<i32>,
+
+    #[add_in_prop(ty = Str, name = "Address", name_ru = "Адрес", readable, setter = Self::connect)]
+    address: String,
+}
+
+impl TestAddIn {
+    fn is_connected(&self) -> bool {
+        !self.address.is_empty()
+    }
+
+    fn last_error(&self) -> String {
+        self.last_error.clone().unwrap_or_default()
+    }
+
+    fn queue_length(&self) -> i32 {
+        self.queue.len() as i32
+    }
+
+    fn set_queue_length(&mut self, length: i32) -> Result<(), String> {
+        if !(0..=MAX_QUEUE_LENGTH).contains(&length) {
+            let error = format!("queue length must be in 0..={MAX_QUEUE_LENGTH}");
+            self.last_error = Some(error.clone());
+            return Err(error);
+        }
+        self.queue.resize(length as usize, 0);
+        Ok(())
+    }
+
+    fn connect(&mut self, address: String) {
+        self.address = address;
+    }
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(Some(connection())),
+        last_error: None,
+        queue: vec![],
+        address: String::new(),
+    }
+}
+
+#[rstest]
+#[case(0, true, false)]
+#[case(1, true, false)]
+#[case(2, true, true)]
+#[case(3, true, true)]
+fn test_access(
+    add_in: TestAddIn,
+    #[case] prop_i: usize,
+    #[case] readable: bool,
+    #[case] writable: bool,
+) {
+    assert_eq!(add_in.is_prop_readable(prop_i), readable);
+    assert_eq!(add_in.is_prop_writable(prop_i), writable);
+}
+
+#[rstest]
+fn test_getter(mut add_in: TestAddIn) {
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::Bool(false)));
+    add_in.address = "localhost".to_string();
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::Bool(true)));
+
+    add_in.queue = vec![1, 2, 3];
+    assert_eq!(add_in.get_prop_val(2), Ok(ParamValue::I32(3)));
+}
+
+#[rstest]
+fn test_setter(mut add_in: TestAddIn) {
+    assert_eq!(add_in.set_prop_val(2, ParamValue::I32(5)), Ok(()));
+    assert_eq!(add_in.queue.len(), 5);
+
+    assert_eq!(
+        add_in.set_prop_val(3, ParamValue::String(os_string("localhost"))),
+        Ok(())
+    );
+    assert_eq!(add_in.address, "localhost");
+    assert_eq!(
+        add_in.get_prop_val(3),
+        Ok(ParamValue::String(os_string("localhost")))
+    );
+}
+
+#[rstest]
+fn test_setter_error(mut add_in: TestAddIn) {
+    assert_eq!(
+        add_in.set_prop_val(2, ParamValue::I32(MAX_QUEUE_LENGTH + 1)),
+        Err(())
+    );
+    assert!(add_in.queue.is_empty());
+    assert_ne!(add_in.get_prop_val(1), Ok(ParamValue::String(vec![])));
+    assert_eq!(
+        take_errors(),
+        [(
+            "QueueLength".to_string(),
+            format!("queue length must be in 0..={MAX_QUEUE_LENGTH}")
+        )]
+    );
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    description::{DescribeAddIn, MethodDescription, ParamDescription, ValueType},
+    ffi::{connection::Connection, string_utils::os_string_nil},
+    interface::ParamValue,
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::rstest;
+
+/// Reads and writes files
+#[derive(AddIn)]
+struct FilesAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    /// Number of open files
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Количество", readable)]
+    count: i32,
+
+    /// Reads the file
+    /// # Arguments
+    /// * `path` - path to the file
+    /// # Returns
+    /// Text of the file
+    #[add_in_func(name = "Read", name_ru = "Прочитать")]
+    #[arg(default = "data.txt")]
+    read: fn(path: String) -> String,
+
+    #[add_in_func(name = "Split", name_ru = "Разделить")]
+    #[arg(as_out)]
+    #[arg(variadic, max = 2)]
+    split: fn(&mut String, Vec<i32>),
+
+    #[add_in_func(name = "Dispatch", name_ru = "Вызвать", raw, n_params = 2)]
+    dispatch: fn(
+        &mut native_api_1c::native_api_1c_core::interface::ParamValues,
+    ) -> Result<ParamValue, ()>,
+}
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct MethodsAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[add_in_methods]
+impl MethodsAddIn {
+    /// Stops the work
+    #[add_in_func(name = "Stop", name_ru = "Остановить")]
+    fn stop(&mut self, force: bool) {
+        let _ = force;
+    }
+}
+
+fn param(
+    name: Option<&'static str>,
+    ty: ValueType,
+    default: Option<ParamValue>,
+) -> ParamDescription {
+    ParamDescription {
+        name,
+        ty,
+        default,
+        optional: false,
+        by_ref: false,
+    }
+}
+
+#[rstest]
+fn test_field_description() {
+    let description = FilesAddIn::description();
+    assert_eq!(description.name, "FilesAddIn");
+    assert_eq!(description.doc, "Reads and writes files");
+
+    let [count] = description.props.as_slice() else {
+        panic!("expected 1 property");
+    };
+    assert_eq!((count.name, count.name_ru), ("Count", "Количество"));
+    assert_eq!(count.doc, "Number of open files");
+    assert_eq!(count.ty, ValueType::Number);
+    assert!(count.readable && !count.writable);
+
+    let [read, split, dispatch] = description.methods.as_slice() else {
+        panic!("expected 3 methods");
+    };
+    assert_eq!(
+        read,
+        &MethodDescription {
+            name: "Read",
+            name_ru: "Прочитать",
+            doc: "Reads the file\n# Arguments\n* `path` - path to the file\n\
+                  # Returns\nText of the file",
+            params: vec![param(
+                Some("path"),
+                ValueType::String,
+                Some(ParamValue::String(os_string_nil("data.txt"))),
+            )],
+            returns: Some(ValueType::String),
+        }
+    );
+
+    let values = ParamDescription {
+        optional: true,
+        ..param(None, ValueType::Number, None)
+    };
+    let out = ParamDescription {
+        by_ref: true,
+        ..param(None, ValueType::String, None)
+    };
+    assert_eq!(split.params, vec![out, values.clone(), values]);
+    assert_eq!(split.returns, None);
+
+    assert_eq!(dispatch.params.len(), 2);
+    assert!(dispatch.params.iter().all(|p| p.ty == ValueType::Any));
+    assert_eq!(dispatch.returns, Some(ValueType::Any));
+}
+
+#[rstest]
+fn test_impl_methods_description() {
+    let description = MethodsAddIn::description();
+    let [stop] = description.methods.as_slice() else {
+        panic!("expected 1 method");
+    };
+    assert_eq!(stop.doc, "Stops the work");
+    assert_eq!(
+        stop.params,
+        vec![param(Some("force"), ValueType::Bool, None)]
+    );
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use native_api_1c::native_api_1c_core::{
     ffi::{
         connection::Connection,
+        provided_types::Tm,
         string_utils::{os_string, os_string_nil},
     },
     interface::{AddInWrapper, ParamValue, ParamValues},
@@ -43,7 +44,7 @@ struct TestAddIn {
     pub procedure: fn(&mut Self, i32, i32),
 
     #[add_in_func(name = OUT_FUNCTION_NAME_EN, name_ru = OUT_FUNCTION_NAME_RU)]
-    #[arg(ty = Str, as_out, default = OUT_STR)]
+    #[arg(ty = Str, as_inout, default = OUT_STR)]
     pub out_function: fn(&mut String),
 }
 
@@ -183,3 +184,171 @@ fn test_call_out_function(mut add_in: TestAddIn) {
     assert!(result.is_ok());
     assert_eq!(params[0], ParamValue::String(os_string("Hello, 1C!")));
 }
+
+#[derive(AddIn)]
+struct OutParamsAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "WriteDate", name_ru = "ЗаписатьДату")]
+    #[arg(as_out)]
+    pub write_date: fn(&mut chrono::NaiveDateTime),
+
+    #[add_in_func(name = "CountOut", name_ru = "СчетчикВывод")]
+    #[arg(as_out)]
+    pub count_out: fn(&mut i32) -> bool,
+
+    #[add_in_func(name = "NextDay", name_ru = "СледующийДень")]
+    #[arg(as_inout)]
+    pub next_day: fn(&mut chrono::NaiveDateTime),
+}
+
+fn date(y: i32, m: u32, d: u32) -> chrono::NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+#[fixture]
+fn out_add_in() -> OutParamsAddIn {
+    OutParamsAddIn {
+        connection: Arc::new(None),
+        write_date: |out_date| *out_date = date(2024, 2, 29),
+        count_out: |count| {
+            *count += 1;
+            true
+        },
+        next_day: |in_out_date| *in_out_date += chrono::Duration::days(1),
+    }
+}
+
+#[rstest]
+#[case(ParamValue::Empty)]
+#[case(ParamValue::I32(42))]
+#[case(ParamValue::Date(date(2000, 1, 1).into()))]
+fn test_call_out_date(mut out_add_in: OutParamsAddIn, #[case] incoming: ParamValue) {
+    let mut params = ParamValues::new(vec![incoming]);
+
+    let result = out_add_in.call_as_proc(0, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[0], ParamValue::Date(date(2024, 2, 29).into()));
+}
+
+#[rstest]
+#[case(ParamValue::Empty)]
+#[case(ParamValue::I32(42))]
+fn test_call_out_ignores_incoming(mut out_add_in: OutParamsAddIn, #[case] incoming: ParamValue) {
+    let mut params = ParamValues::new(vec![incoming]);
+
+    let result = out_add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(ParamValue::Bool(true)));
+    assert_eq!(params[0], ParamValue::I32(1));
+}
+
+#[rstest]
+fn test_call_inout_date(mut out_add_in: OutParamsAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Date(date(2024, 2, 28).into())]);
+
+    let result = out_add_in.call_as_proc(2, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[0], ParamValue::Date(date(2024, 2, 29).into()));
+}
+
+#[rstest]
+fn test_call_inout_requires_value(mut out_add_in: OutParamsAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Empty]);
+
+    let result = out_add_in.call_as_proc(2, &mut params);
+    assert!(result.is_err());
+    assert_eq!(params[0], ParamValue::Empty);
+}
+
+#[derive(AddIn)]
+struct DefaultsAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Defaults", name_ru = "ЗначенияПоУмолчанию")]
+    #[arg(default = "2024-02-29T12:30:15")]
+    #[arg(default = "2024-02-29")]
+    #[arg(default = b"bin")]
+    #[arg(default = "hex:0102ff")]
+    #[arg(default = "base64:AAECAw==")]
+    pub defaults: fn(chrono::NaiveDateTime, chrono::NaiveDateTime, Vec<u8>, Vec<u8>, Vec<u8>),
+}
+
+#[rstest]
+#[case(0, ParamValue::Date(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(12, 30, 15).unwrap().into()))]
+#[case(1, ParamValue::Date(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(0, 0, 0).unwrap().into()))]
+#[case(2, ParamValue::Blob(b"bin".to_vec()))]
+#[case(3, ParamValue::Blob(vec![0x01, 0x02, 0xff]))]
+#[case(4, ParamValue::Blob(vec![0, 1, 2, 3]))]
+fn test_date_and_blob_defaults(#[case] param_i: usize, #[case] expected: ParamValue) {
+    let add_in = DefaultsAddIn {
+        connection: Arc::new(None),
+        defaults: |_, _, _, _, _| {},
+    };
+    assert_eq!(add_in.get_param_def_value(0, param_i), Some(expected));
+}
+
+#[derive(AddIn)]
+struct DatesAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "NextDay", name_ru = "СледующийДень")]
+    pub next_day: fn(chrono::NaiveDateTime) -> chrono::NaiveDateTime,
+
+    #[add_in_func(name = "NextDayTime", name_ru = "СледующийДеньTime")]
+    pub next_day_time: fn(time::PrimitiveDateTime) -> time::PrimitiveDateTime,
+
+    #[add_in_func(name = "Offset", name_ru = "Смещение")]
+    pub offset: fn(chrono::DateTime<chrono::FixedOffset>) -> i32,
+}
+
+#[fixture]
+fn dates_add_in() -> DatesAddIn {
+    DatesAddIn {
+        connection: Arc::new(None),
+        next_day: |date| date + chrono::Duration::days(1),
+        next_day_time: |date| date + time::Duration::days(1),
+        offset: |date| date.offset().local_minus_utc(),
+    }
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+fn test_call_date_types(mut dates_add_in: DatesAddIn, #[case] method_i: usize) {
+    let mut params = ParamValues::new(vec![ParamValue::Date(date(2024, 2, 28).into())]);
+
+    let result = dates_add_in.call_as_func(method_i, &mut params);
+    assert_eq!(result, Ok(ParamValue::Date(date(2024, 2, 29).into())));
+}
+
+#[rstest]
+fn test_call_date_with_offset(mut dates_add_in: DatesAddIn) {
+    let mut tm: Tm = date(2024, 2, 29).into();
+    tm.set_utc_offset(3 * 3600);
+    let mut params = ParamValues::new(vec![ParamValue::Date(tm)]);
+
+    let result = dates_add_in.call_as_func(2, &mut params);
+    if cfg!(target_family = "unix") {
+        assert_eq!(result, Ok(ParamValue::I32(3 * 3600)));
+    } else {
+        assert_eq!(result, Ok(ParamValue::I32(0)));
+    }
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+#[case(2)]
+fn test_call_invalid_date(mut dates_add_in: DatesAddIn, #[case] method_i: usize) {
+    // zeroed `Tm` has day of the month 0
+    let mut params = ParamValues::new(vec![ParamValue::Date(Tm::default())]);
+
+    let result = dates_add_in.call_as_func(method_i, &mut params);
+    assert!(result.is_err());
+}
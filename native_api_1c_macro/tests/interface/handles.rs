@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection,
+    handles::{Handle, HandleRegistry},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use native_api_1c_test_utils::{connection, take_errors};
+use rstest::{fixture, rstest};
+
+struct Buffer(String);
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct HandlesAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_handles]
+    buffers: HandleRegistry<Buffer>,
+
+    #[add_in_prop(ty = Handle, name = "Current", name_ru = "Текущий", readable, writable)]
+    current: Handle<Buffer>,
+
+    appended: i32,
+}
+
+#[add_in_methods]
+impl HandlesAddIn {
+    #[add_in_func(name = "Open", name_ru = "Открыть")]
+    fn open(&mut self, text: String) -> Handle<Buffer> {
+        self.buffers.insert(Buffer(text))
+    }
+
+    #[add_in_func(name = "Append", name_ru = "Дописать")]
+    #[arg(handle = buffers)]
+    fn append(&mut self, buffer: &mut Buffer, text: String) {
+        buffer.0.push_str(&text);
+        self.appended += 1;
+    }
+
+    #[add_in_func(name = "Read", name_ru = "Прочитать")]
+    #[arg(handle = buffers)]
+    fn read(buffer: &Buffer) -> String {
+        buffer.0.clone()
+    }
+
+    #[add_in_func(name = "Close", name_ru = "Закрыть")]
+    fn close(&mut self, handle: Handle<Buffer>) -> Result<(), ()> {
+        self.buffers.remove(handle).map(drop).map_err(drop)
+    }
+}
+
+#[fixture]
+fn add_in() -> HandlesAddIn {
+    HandlesAddIn {
+        connection: Arc::new(Some(connection())),
+        buffers: HandleRegistry::new(),
+        current: Handle::default(),
+        appended: 0,
+    }
+}
+
+fn open(add_in: &mut HandlesAddIn, text: &str) -> ParamValue {
+    let mut params = ParamValues::new(vec![text.into()]);
+    add_in.call_as_func(0, &mut params).unwrap()
+}
+
+fn read(add_in: &mut HandlesAddIn, handle: &ParamValue) -> Result<ParamValue, ()> {
+    let mut params = ParamValues::new(vec![handle.clone()]);
+    add_in.call_as_func(2, &mut params)
+}
+
+#[rstest]
+fn test_resolve_handle(mut add_in: HandlesAddIn) {
+    let first = open(&mut add_in, "first");
+    let second = open(&mut add_in, "second");
+    assert_ne!(first, second);
+
+    let mut params = ParamValues::new(vec![first.clone(), "!".into()]);
+    assert_eq!(add_in.call_as_proc(1, &mut params), Ok(()));
+    assert_eq!(add_in.appended, 1);
+    // handle argument is not written back
+    assert_eq!(params[0], first);
+
+    assert_eq!(read(&mut add_in, &first), Ok("first!".into()));
+    assert_eq!(read(&mut add_in, &second), Ok("second".into()));
+}
+
+#[rstest]
+fn test_stale_handle(mut add_in: HandlesAddIn) {
+    let first = open(&mut add_in, "first");
+
+    let mut params = ParamValues::new(vec![first.clone()]);
+    assert_eq!(add_in.call_as_proc(3, &mut params), Ok(()));
+    assert_eq!(add_in.call_as_proc(3, &mut params), Err(()));
+    assert_eq!(read(&mut add_in, &first), Err(()));
+
+    // slot is reused, but the old handle stays stale
+    let second = open(&mut add_in, "second");
+    assert_ne!(first, second);
+    assert_eq!(read(&mut add_in, &first), Err(()));
+    assert_eq!(read(&mut add_in, &second), Ok("second".into()));
+    let stale = ("Read".to_string(), "handle is stale".to_string());
+    assert_eq!(take_errors(), [stale.clone(), stale]);
+}
+
+#[rstest]
+fn test_busy_value(mut add_in: HandlesAddIn) {
+    let first = open(&mut add_in, "first");
+    let handle = Handle::try_from(first.clone()).unwrap();
+
+    // value is locked, e.g. by a call from other thread
+    let value = add_in.buffers.resolve(handle).unwrap();
+    let guard = HandleRegistry::lock(&value).unwrap();
+    assert_eq!(read(&mut add_in, &first), Err(()));
+    assert_eq!(
+        take_errors(),
+        [(
+            "Read".to_string(),
+            "value of the handle is in use".to_string()
+        )]
+    );
+
+    drop(guard);
+    assert_eq!(read(&mut add_in, &first), Ok("first".into()));
+}
+
+#[rstest]
+#[case(ParamValue::I32(0))]
+#[case(ParamValue::F64(1.5))]
+#[case(ParamValue::String(vec![]))]
+fn test_invalid_handle(mut add_in: HandlesAddIn, #[case] handle: ParamValue) {
+    open(&mut add_in, "first");
+    assert_eq!(read(&mut add_in, &handle), Err(()));
+}
+
+#[rstest]
+fn test_handle_prop(mut add_in: HandlesAddIn) {
+    assert_eq!(add_in.get_prop_val(0), Ok(ParamValue::I32(0)));
+
+    let first = open(&mut add_in, "first");
+    assert_eq!(add_in.set_prop_val(0, first.clone()), Ok(()));
+    assert_eq!(add_in.get_prop_val(0), Ok(first));
+    assert!(add_in.buffers.contains(add_in.current));
+}
+
+#[rstest]
+fn test_cleanup_on_done(mut add_in: HandlesAddIn) {
+    let first = open(&mut add_in, "first");
+    open(&mut add_in, "second");
+    assert_eq!(add_in.buffers.len(), 2);
+
+    add_in.done();
+    assert!(add_in.buffers.is_empty());
+    assert_eq!(read(&mut add_in, &first), Err(()));
+}
@@ -0,0 +1,216 @@
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{get_str, os_string},
+    },
+    instances::{Children, PendingInstance},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{extern_functions, AddIn};
+
+use native_api_1c_test_utils::{free_variant, FakeHost};
+
+/// State, shared by parser and its documents
+#[derive(Default)]
+struct Shared {
+    open_documents: AtomicUsize,
+}
+
+#[derive(AddIn)]
+struct Parser {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    shared: Arc<Shared>,
+    children: Children,
+
+    #[add_in_func(name = "Open", name_ru = "Открыть")]
+    open: fn(&Self, String) -> PendingInstance,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self {
+            connection: Arc::new(None),
+            shared: Arc::default(),
+            children: Children::new(),
+            open: |parser, path| {
+                let document = Document {
+                    connection: Arc::new(None),
+                    shared: Some(parser.shared.clone()),
+                    path,
+                };
+                parser.shared.open_documents.fetch_add(1, Ordering::SeqCst);
+                parser.children.prepare(&DOCUMENTS, document)
+            },
+        }
+    }
+}
+
+#[derive(AddIn, Default)]
+struct Document {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    shared: Option<Arc<Shared>>,
+
+    #[add_in_prop(ty = Str, name = "Path", name_ru = "Путь", readable)]
+    path: String,
+}
+
+impl Drop for Document {
+    fn drop(&mut self) {
+        if let Some(shared) = &self.shared {
+            shared.open_documents.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+extern_functions! {
+    #[add_in_component(name = "Parser")]
+    Parser::default(),
+    #[add_in_component(name = "Document", factory = DOCUMENTS)]
+    Document,
+}
+
+fn class_object(name: &str) -> Option<*mut c_void> {
+    let name: Vec<u16> = os_string(name).into_iter().chain(Some(0)).collect();
+    let mut component = ptr::null_mut();
+    match unsafe { GetClassObject(name.as_ptr(), &mut component) } {
+        0 => None,
+        _ => Some(component),
+    }
+}
+
+/// Object of the class, created by 1C with `New`
+fn new_object(name: &str) -> FakeHost {
+    let component = class_object(name).unwrap();
+    unsafe { FakeHost::attach(component, true) }
+}
+
+fn open(parser: &mut Parser, path: &str) -> String {
+    let mut params = ParamValues::new(vec![path.into()]);
+    match parser.call_as_func(0, &mut params) {
+        Ok(ParamValue::String(class)) => String::from_utf16(&class).unwrap(),
+        result => panic!("unexpected result {result:?}"),
+    }
+}
+
+fn path(document: &FakeHost) -> String {
+    let path = document.get_prop_val(0).unwrap();
+    let text = String::from_utf16(path.as_str().unwrap()).unwrap();
+    free_variant(&path);
+    text
+}
+
+#[test]
+fn test_class_names() {
+    let names = unsafe { get_str(GetClassNames()) };
+    assert_eq!(names, os_string("Parser|Document\0"));
+    assert_eq!(DOCUMENTS.class(), "Document");
+}
+
+#[test]
+fn test_create_objects() {
+    let mut parser = class_object("Parser").unwrap();
+    unsafe { DestroyObject(&mut parser) };
+
+    // without prepared AddIn object is created from the default one
+    let document = new_object("Document");
+    assert_eq!(path(&document), "");
+
+    assert!(class_object("Unknown").is_none());
+}
+
+// Tests, that prepare documents, share `DOCUMENTS` and are run one by one
+#[test]
+fn test_instances() {
+    next_object_is_prepared();
+    parents_share_factory();
+    children_dropped_with_parent();
+    cancel();
+}
+
+fn next_object_is_prepared() {
+    let mut parser = Parser::default();
+    assert_eq!(open(&mut parser, "a.txt"), "Document");
+    assert_eq!(open(&mut parser, "b.txt"), "Document");
+    assert_eq!(parser.children.pending_count(), 2);
+
+    // objects are created from prepared AddIns in the order they are prepared
+    let first = new_object("Document");
+    assert_eq!(path(&first), "a.txt");
+    assert_eq!(parser.children.pending_count(), 1);
+    let second = new_object("Document");
+    assert_eq!(path(&second), "b.txt");
+    assert_eq!(DOCUMENTS.pending_count(), 0);
+
+    assert_eq!(parser.shared.open_documents.load(Ordering::SeqCst), 2);
+    drop(first);
+    drop(second);
+    assert_eq!(parser.shared.open_documents.load(Ordering::SeqCst), 0);
+}
+
+fn parents_share_factory() {
+    let mut first = Parser::default();
+    let mut second = Parser::default();
+    open(&mut second, "second.txt");
+    open(&mut first, "first.txt");
+
+    let document = new_object("Document");
+    assert_eq!(path(&document), "second.txt");
+    assert_eq!(first.children.pending_count(), 1);
+    assert_eq!(second.children.pending_count(), 0);
+
+    // objects, created by parent, don't depend on it
+    drop(second);
+    assert_eq!(path(&document), "second.txt");
+
+    let document = new_object("Document");
+    assert_eq!(path(&document), "first.txt");
+}
+
+fn children_dropped_with_parent() {
+    let mut parser = Parser::default();
+    open(&mut parser, "a.txt");
+    open(&mut parser, "b.txt");
+    let mut other = Parser::default();
+    open(&mut other, "c.txt");
+    let shared = parser.shared.clone();
+    assert_eq!(shared.open_documents.load(Ordering::SeqCst), 2);
+
+    drop(parser);
+    assert_eq!(shared.open_documents.load(Ordering::SeqCst), 0);
+    assert_eq!(DOCUMENTS.pending_count(), 1);
+
+    let document = new_object("Document");
+    assert_eq!(path(&document), "c.txt");
+}
+
+fn cancel() {
+    let shared = Arc::new(Shared::default());
+    let document = Document {
+        connection: Arc::new(None),
+        shared: Some(shared.clone()),
+        path: "d.txt".to_string(),
+    };
+    shared.open_documents.fetch_add(1, Ordering::SeqCst);
+
+    let children = Children::new();
+    let instance = children.prepare(&DOCUMENTS, document);
+    assert_eq!(instance.class(), "Document");
+    assert_eq!(DOCUMENTS.pending_count(), 1);
+    assert!(DOCUMENTS.cancel(&instance));
+    assert!(!DOCUMENTS.cancel(&instance));
+    assert_eq!(shared.open_documents.load(Ordering::SeqCst), 0);
+    assert_eq!(children.pending_count(), 0);
+}
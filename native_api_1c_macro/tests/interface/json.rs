@@ -0,0 +1,132 @@
+use std::{collections::HashMap, sync::Arc};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+    json::{from_param, to_param, JsonError},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Order {
+    id: i32,
+    items: Vec<Item>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Item {
+    name: String,
+    count: u32,
+}
+
+#[derive(AddIn)]
+struct JsonAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Total", name_ru = "Итого")]
+    #[arg]
+    #[arg(ty = Json)]
+    pub total: fn(i32, Order) -> i32,
+
+    #[add_in_func(name = "Group", name_ru = "Сгруппировать")]
+    #[arg(ty = Json)]
+    #[returns(ty = Json)]
+    pub group: fn(Vec<Item>) -> HashMap<String, u32>,
+
+    #[add_in_func(name = "Fill", name_ru = "Заполнить")]
+    #[arg(ty = Json, as_out)]
+    #[arg(ty = Json, as_inout)]
+    #[returns(ty = Json, result)]
+    pub fill: fn(&mut Order, &mut Vec<String>) -> Result<Vec<u8>, ()>,
+}
+
+#[fixture]
+fn add_in() -> JsonAddIn {
+    JsonAddIn {
+        connection: Arc::new(None),
+        total: |base, order| base + order.items.iter().map(|i| i.count as i32).sum::<i32>(),
+        group: |items| {
+            let mut groups = HashMap::new();
+            for item in items {
+                *groups.entry(item.name).or_default() += item.count;
+            }
+            groups
+        },
+        fill: |order, names| {
+            order.id = 1;
+            names.push("added".to_string());
+            Ok(vec![1, 2])
+        },
+    }
+}
+
+fn json(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+#[rstest]
+fn test_call_json_arg(mut add_in: JsonAddIn) {
+    let order = r#"{"id": 1, "items": [{"name": "a", "count": 2}, {"name": "b", "count": 3}]}"#;
+    let mut params = ParamValues::new(vec![ParamValue::I32(10), json(order)]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(15)));
+}
+
+#[rstest]
+#[case(json(r#"{"id": 1, "items": [{"name": "a", "count": -2}]}"#))]
+#[case(json(r#"{"id": 1}"#))]
+#[case(json("not a json"))]
+#[case(ParamValue::I32(1))]
+fn test_call_invalid_json_arg(mut add_in: JsonAddIn, #[case] order: ParamValue) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(10), order]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert!(result.is_err());
+}
+
+#[rstest]
+fn test_call_json_return(mut add_in: JsonAddIn) {
+    let items = r#"[{"name": "a", "count": 2}, {"name": "a", "count": 3}]"#;
+    let mut params = ParamValues::new(vec![json(items)]);
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(json(r#"{"a":5}"#)));
+}
+
+#[rstest]
+fn test_call_json_out_params(mut add_in: JsonAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::Empty, json(r#"["first"]"#)]);
+
+    let result = add_in.call_as_func(2, &mut params);
+    assert_eq!(result, Ok(json("[1,2]")));
+    assert_eq!(params[0], json(r#"{"id":1,"items":[]}"#));
+    assert_eq!(params[1], json(r#"["first","added"]"#));
+}
+
+#[rstest]
+#[case(
+    r#"{"id": 1, "items": [{"name": "a", "count": -2}]}"#,
+    "items[0].count"
+)]
+#[case(r#"{"id": "1", "items": []}"#, "id")]
+#[case(r#"{"id": 1, "items": []} trailing"#, ".")]
+fn test_from_param_error_path(#[case] order: &str, #[case] path: &str) {
+    let error = from_param::<Order>(&json(order), 3).unwrap_err();
+    assert_eq!(error.param_index, Some(3));
+    assert_eq!(error.path, path);
+}
+
+#[test]
+fn test_to_param_error_path() {
+    let mut map = HashMap::new();
+    map.insert(vec![1], 1);
+    let error: JsonError = to_param(&map, None).unwrap_err();
+    assert_eq!(error.param_index, None);
+    assert!(error
+        .to_string()
+        .starts_with("Unable to convert return value to JSON"));
+}
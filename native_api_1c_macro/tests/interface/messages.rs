@@ -0,0 +1,241 @@
+use std::sync::{Arc, LazyLock};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        provided_types::{DataStr, TVariant, VariantType, VariantValue},
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, ParamValue, ParamValues},
+    localization::{Message, MessageCatalog, UiLanguage},
+};
+use native_api_1c_macro::{add_in_methods, message_catalog, AddIn};
+use rstest::{fixture, rstest};
+use serde::Deserialize;
+
+use native_api_1c_test_utils::{connection, take_errors, FakeHost};
+
+static MESSAGES: LazyLock<MessageCatalog> = LazyLock::new(|| {
+    message_catalog!(
+        default = "ru",
+        ru = "messages/ru.txt",
+        en = "messages/en.txt",
+    )
+});
+
+#[derive(AddIn)]
+#[add_in(messages = MESSAGES)]
+struct MessagesAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_lang]
+    language: UiLanguage,
+
+    #[add_in_prop(ty = Int, name = "Limit", name_ru = "Лимит", readable, setter = Self::set_limit)]
+    limit: i32,
+
+    #[add_in_func(name = "Open", name_ru = "Открыть")]
+    open: fn(&Self, String) -> Result<String, Message>,
+
+    #[add_in_func(name = "Check", name_ru = "Проверить")]
+    check: fn(&Self) -> Result<i32, String>,
+
+    #[add_in_func(name = "Poll", name_ru = "Опросить")]
+    poll: fn(&Self) -> Result<i32, ()>,
+
+    #[add_in_func(name = "Dispatch", name_ru = "Выполнить", raw, n_params = 0)]
+    dispatch: fn(&mut Self, &mut ParamValues) -> Result<ParamValue, Message>,
+}
+
+impl MessagesAddIn {
+    fn set_limit(&mut self, limit: i32) -> Result<(), Message> {
+        if limit < 0 {
+            return Err(Message::new("busy"));
+        }
+        self.limit = limit;
+        Ok(())
+    }
+}
+
+/// AddIn without catalog, `Message` is reported as its key
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct UntranslatedAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[add_in_methods]
+impl UntranslatedAddIn {
+    #[add_in_func(name = "Open", name_ru = "Открыть")]
+    fn open(&self, path: String) -> Result<String, Message> {
+        Err(Message::new("not_found").arg("path", path))
+    }
+}
+
+#[derive(Deserialize)]
+struct Options {
+    #[allow(dead_code)]
+    depth: u32,
+}
+
+/// AddIn without `#[add_in_lang]` field, language is kept by the component
+#[derive(AddIn)]
+#[add_in(messages = MESSAGES)]
+struct WithoutLanguageAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Limit", name_ru = "Лимит", writable, setter = Self::set_limit)]
+    #[allow(dead_code)]
+    limit: i32,
+
+    #[add_in_func(name = "Configure", name_ru = "Настроить")]
+    #[arg(ty = Json)]
+    configure: fn(&Self, Options),
+}
+
+impl WithoutLanguageAddIn {
+    fn set_limit(&mut self, _: i32) -> Result<(), Message> {
+        Err(Message::new("busy"))
+    }
+}
+
+fn without_language() -> FakeHost {
+    let add_in = WithoutLanguageAddIn {
+        connection: Arc::new(None),
+        limit: 0,
+        configure: |_, _| {},
+    };
+    FakeHost::new(add_in, true)
+}
+
+fn str_variant(value: &mut [u16]) -> TVariant {
+    TVariant {
+        value: VariantValue {
+            data_str: DataStr {
+                ptr: value.as_mut_ptr(),
+                len: value.len() as u32,
+            },
+        },
+        elements: 0,
+        vt: VariantType::WStr,
+    }
+}
+
+#[fixture]
+fn add_in() -> MessagesAddIn {
+    let mut add_in = MessagesAddIn {
+        connection: Arc::new(None),
+        language: UiLanguage::new(),
+        limit: 0,
+        open: |_, path| Err(Message::new("not_found").arg("path", path)),
+        check: |_| Err("check failed".to_string()),
+        poll: |_| Err(()),
+        dispatch: |_, _| Err(Message::new("busy")),
+    };
+    assert!(add_in.init(connection()));
+    add_in
+}
+
+fn errors() -> Vec<String> {
+    take_errors()
+        .into_iter()
+        .map(|(_, description)| description)
+        .collect()
+}
+
+#[rstest]
+#[case(None, "Файл data.xml не найден")]
+#[case(Some("en"), "File data.xml not found")]
+#[case(Some("kk"), "Файл data.xml не найден")]
+fn test_function_error_in_language(
+    mut add_in: MessagesAddIn,
+    #[case] language: Option<&str>,
+    #[case] expected: &str,
+) {
+    if let Some(language) = language {
+        add_in.set_user_interface_language_code(&os_string_nil(language));
+    }
+    let mut params = ParamValues::new(vec!["data.xml".into()]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Err(()));
+    assert_eq!(take_errors(), [("Open".to_string(), expected.to_string())]);
+}
+
+#[rstest]
+fn test_raw_function_error(mut add_in: MessagesAddIn) {
+    add_in.set_locale(&os_string_nil("en_US"));
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(3, &mut params), Err(()));
+    assert_eq!(
+        take_errors(),
+        [("Dispatch".to_string(), "Busy".to_string())]
+    );
+}
+
+#[rstest]
+fn test_setter_error(mut add_in: MessagesAddIn) {
+    add_in.set_user_interface_language_code(&os_string_nil("en"));
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(-1)), Err(()));
+    assert_eq!(take_errors(), [("Limit".to_string(), "Busy".to_string())]);
+
+    assert_eq!(add_in.set_prop_val(0, ParamValue::I32(5)), Ok(()));
+    assert_eq!(add_in.limit, 5);
+    assert!(take_errors().is_empty());
+}
+
+#[rstest]
+fn test_other_errors(mut add_in: MessagesAddIn) {
+    let mut params = ParamValues::new(vec![]);
+    assert_eq!(add_in.call_as_func(1, &mut params), Err(()));
+    assert_eq!(errors(), ["check failed"]);
+
+    // error without text is not reported
+    assert_eq!(add_in.call_as_func(2, &mut params), Err(()));
+    assert!(take_errors().is_empty());
+}
+
+#[test]
+fn test_message_without_catalog() {
+    let mut add_in = UntranslatedAddIn {
+        connection: Arc::new(None),
+    };
+    assert!(add_in.init(connection()));
+    let mut params = ParamValues::new(vec!["data.xml".into()]);
+    assert_eq!(add_in.call_as_func(0, &mut params), Err(()));
+    assert_eq!(errors(), ["not_found"]);
+}
+
+#[rstest]
+#[case(None, "Занято")]
+#[case(Some("en"), "Busy")]
+fn test_language_without_field(#[case] language: Option<&str>, #[case] expected: &str) {
+    let host = without_language();
+    if let Some(language) = language {
+        host.set_user_interface_language_code(language);
+    }
+    let mut limit = TVariant::default();
+    limit.update_to_i32(5);
+    assert!(!host.set_prop_val(0, &limit));
+    assert_eq!(errors(), [expected]);
+}
+
+#[rstest]
+#[case(
+    "ru",
+    "Неверный JSON в параметре 0: invalid type: string \"deep\", expected u32"
+)]
+#[case(
+    "en",
+    "Invalid JSON in parameter 0 at depth: invalid type: string \"deep\", expected u32"
+)]
+fn test_json_error_in_language(#[case] language: &str, #[case] expected: &str) {
+    let host = without_language();
+    host.set_user_interface_language_code(language);
+    let mut options = os_string(r#"{"depth": "deep"}"#);
+    assert!(!host.call_as_proc(0, &mut [str_variant(&mut options)]));
+    let errors = errors();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with(expected), "{}", errors[0]);
+}
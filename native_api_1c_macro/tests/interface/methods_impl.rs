@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::{fixture, rstest};
+
+const DEFAULT_VALUE: i32 = 12;
+
+const PROP_NAME: &str = "Storage";
+const PROP_NAME_RU: &str = "Хранилище";
+
+const FUNCTION_NAME_EN: &str = "Function";
+const FUNCTION_NAME_RU: &str = "Функция";
+
+const PROCEDURE_NAME_EN: &str = "Procedure";
+const PROCEDURE_NAME_RU: &str = "Процедура";
+
+const GREET_NAME_EN: &str = "Greet";
+const GREET_NAME_RU: &str = "Приветствие";
+
+const INVALID_NAME: &str = "Invalid";
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct TestAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = PROP_NAME, name_ru = PROP_NAME_RU, readable)]
+    storage: i32,
+}
+
+#[add_in_methods]
+impl TestAddIn {
+    #[add_in_func(name = FUNCTION_NAME_EN, name_ru = FUNCTION_NAME_RU)]
+    #[arg]
+    #[arg(default = DEFAULT_VALUE)]
+    fn function(&self, a: i32, b: i32) -> Result<i32, ()> {
+        Ok(a + b + self.storage)
+    }
+
+    #[add_in_func(name = PROCEDURE_NAME_EN, name_ru = PROCEDURE_NAME_RU)]
+    fn procedure(&mut self, a: i32, b: i32) {
+        self.storage = a + b;
+    }
+
+    #[add_in_func(name = GREET_NAME_EN, name_ru = GREET_NAME_RU)]
+    fn greet(name: String) -> String {
+        format!("Hello, {name}!")
+    }
+
+    #[allow(dead_code)]
+    fn not_exposed(&self) -> i32 {
+        self.storage
+    }
+}
+
+#[fixture]
+fn add_in() -> TestAddIn {
+    TestAddIn {
+        connection: Arc::new(None),
+        storage: 0,
+    }
+}
+
+#[rstest]
+fn test_get_n_methods(add_in: TestAddIn) {
+    assert_eq!(add_in.get_n_methods(), 3)
+}
+
+#[rstest]
+#[case(FUNCTION_NAME_EN, Some(0))]
+#[case(FUNCTION_NAME_RU, Some(0))]
+#[case(PROCEDURE_NAME_EN, Some(1))]
+#[case(PROCEDURE_NAME_RU, Some(1))]
+#[case(GREET_NAME_EN, Some(2))]
+#[case(GREET_NAME_RU, Some(2))]
+#[case(INVALID_NAME, None)]
+fn test_find_method(add_in: TestAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
+    assert_eq!(add_in.find_method(&os_string_nil(name)), expected);
+}
+
+#[rstest]
+#[case(0, 0, Some(FUNCTION_NAME_EN))]
+#[case(0, 1, Some(FUNCTION_NAME_RU))]
+#[case(2, 0, Some(GREET_NAME_EN))]
+#[case(3, 0, None)]
+fn test_get_method_name(
+    add_in: TestAddIn,
+    #[case] method_i: usize,
+    #[case] alias_i: usize,
+    #[case] expected: Option<&str>,
+) {
+    assert_eq!(
+        add_in.get_method_name(method_i, alias_i),
+        expected.map(os_string_nil)
+    );
+}
+
+#[rstest]
+#[case(0, 2)]
+#[case(1, 2)]
+#[case(2, 1)]
+#[case(3, 0)]
+fn test_get_n_params(add_in: TestAddIn, #[case] method_i: usize, #[case] n_params: usize) {
+    assert_eq!(add_in.get_n_params(method_i), n_params);
+}
+
+#[rstest]
+#[case(0, 1, Some(ParamValue::I32(DEFAULT_VALUE)))]
+#[case(1, 1, None)]
+fn test_get_param_def_value(
+    add_in: TestAddIn,
+    #[case] method_i: usize,
+    #[case] param_i: usize,
+    #[case] expected: Option<ParamValue>,
+) {
+    assert_eq!(add_in.get_param_def_value(method_i, param_i), expected);
+}
+
+#[rstest]
+#[case(0, true)]
+#[case(1, false)]
+#[case(2, true)]
+fn test_has_ret_val(add_in: TestAddIn, #[case] method_i: usize, #[case] has_ret_val: bool) {
+    assert_eq!(add_in.has_ret_val(method_i), has_ret_val);
+}
+
+#[rstest]
+fn test_call_methods(mut add_in: TestAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(1), ParamValue::I32(2)]);
+    assert!(add_in.call_as_proc(1, &mut params).is_ok());
+    assert_eq!(add_in.storage, 3);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(1 + 2 + 3)));
+
+    let mut params = ParamValues::new(vec![ParamValue::String(os_string("1C"))]);
+    let result = add_in.call_as_func(2, &mut params);
+    assert_eq!(result, Ok(ParamValue::String(os_string_nil("Hello, 1C!"))));
+}
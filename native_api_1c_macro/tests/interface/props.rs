@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use native_api_1c::native_api_1c_core::{
     ffi::connection::Connection,
-    interface::{AddInWrapper, ParamValue},
+    interface::{AddInWrapper, ParamValue, ProtocolMode},
 };
 use native_api_1c_macro::AddIn;
 use rstest::{fixture, rstest};
@@ -139,3 +139,97 @@ fn test_set_prop_val(
     );
     assert_eq!(value_getter(&add_in), new_value);
 }
+
+const DATE_PROP_NAME: &str = "LastSyncTime";
+const DATE_PROP_NAME_RU: &str = "ВремяПоследнейСинхронизации";
+
+const BLOB_PROP_NAME: &str = "Certificate";
+const BLOB_PROP_NAME_RU: &str = "Сертификат";
+
+#[derive(AddIn)]
+struct TypedAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Date, name = DATE_PROP_NAME, name_ru = DATE_PROP_NAME_RU, readable, writable)]
+    last_sync_time: chrono::NaiveDateTime,
+
+    #[add_in_prop(ty = Blob, name = BLOB_PROP_NAME, name_ru = BLOB_PROP_NAME_RU, readable, writable)]
+    certificate: Vec<u8>,
+}
+
+#[fixture]
+fn typed_add_in() -> TypedAddIn {
+    TypedAddIn {
+        connection: Arc::new(None),
+        last_sync_time: chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(12, 30, 15)
+            .unwrap(),
+        certificate: vec![1, 2, 3],
+    }
+}
+
+#[rstest]
+fn test_date_prop(mut typed_add_in: TypedAddIn) {
+    let start_value = typed_add_in.last_sync_time;
+    assert_eq!(
+        typed_add_in.get_prop_val(0),
+        Ok(ParamValue::Date(start_value.into()))
+    );
+
+    let new_value = chrono::NaiveDate::from_ymd_opt(1999, 12, 31)
+        .unwrap()
+        .and_hms_opt(23, 59, 59)
+        .unwrap();
+    assert_eq!(
+        typed_add_in.set_prop_val(0, ParamValue::Date(new_value.into())),
+        Ok(())
+    );
+    assert_eq!(typed_add_in.last_sync_time, new_value);
+    assert_eq!(
+        typed_add_in.set_prop_val(0, ParamValue::I32(NEW_VALUE)),
+        Err(())
+    );
+}
+
+#[rstest]
+fn test_blob_prop(mut typed_add_in: TypedAddIn) {
+    assert_eq!(
+        typed_add_in.get_prop_val(1),
+        Ok(ParamValue::Blob(vec![1, 2, 3]))
+    );
+
+    assert_eq!(
+        typed_add_in.set_prop_val(1, ParamValue::Blob(vec![4, 5])),
+        Ok(())
+    );
+    assert_eq!(typed_add_in.certificate, vec![4, 5]);
+    assert_eq!(
+        typed_add_in.set_prop_val(1, ParamValue::Bool(true)),
+        Err(())
+    );
+}
+
+#[derive(AddIn)]
+#[add_in(protocol_v1)]
+struct LegacyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Str, name = RW_PROP_NAME, name_ru = RW_PROP_NAME_RU, readable, writable)]
+    property: String,
+}
+
+#[rstest]
+fn test_protocol_mode(add_in: TestAddIn) {
+    assert_eq!(add_in.get_info(), 2000);
+    assert_eq!(add_in.protocol_mode(), ProtocolMode::V2);
+
+    let legacy_add_in = LegacyAddIn {
+        connection: Arc::new(None),
+        property: String::new(),
+    };
+    assert_eq!(legacy_add_in.get_info(), 1000);
+    assert_eq!(legacy_add_in.protocol_mode(), ProtocolMode::V1);
+}
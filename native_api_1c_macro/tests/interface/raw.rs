@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        string_utils::{os_string, os_string_nil},
+    },
+    interface::{AddInWrapper, AddInWrapperResult, ParamValue, ParamValues},
+};
+use native_api_1c_macro::{add_in_methods, AddIn};
+use rstest::{fixture, rstest};
+
+const DISPATCH_NAME_EN: &str = "Dispatch";
+const DISPATCH_NAME_RU: &str = "Вызвать";
+
+const COUNT_NAME_EN: &str = "Count";
+const COUNT_NAME_RU: &str = "Количество";
+
+const RESET_NAME_EN: &str = "Reset";
+const RESET_NAME_RU: &str = "Сбросить";
+
+#[derive(AddIn)]
+struct RawAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    calls: i32,
+
+    #[add_in_func(name = DISPATCH_NAME_EN, name_ru = DISPATCH_NAME_RU, raw, n_params = 3)]
+    pub dispatch: fn(&mut Self, &mut ParamValues) -> Result<ParamValue, ()>,
+
+    #[add_in_func(name = COUNT_NAME_EN, name_ru = COUNT_NAME_RU, raw, n_params = 0)]
+    pub count: fn(&mut ParamValues) -> Result<ParamValue, String>,
+
+    #[add_in_func(name = RESET_NAME_EN, name_ru = RESET_NAME_RU, raw, n_params = 1)]
+    pub reset: fn(&mut Self, &mut ParamValues) -> AddInWrapperResult<()>,
+}
+
+/// Sets number of calls to the first param
+fn reset(add_in: &mut RawAddIn, params: &mut ParamValues) -> AddInWrapperResult<()> {
+    add_in.calls = params[0].as_i32().ok_or(())?;
+    Ok(())
+}
+
+/// Returns type name of the first param and swaps the other two
+fn dispatch(add_in: &mut RawAddIn, params: &mut ParamValues) -> Result<ParamValue, ()> {
+    add_in.calls += 1;
+
+    let type_name = match params[0] {
+        ParamValue::Empty => "Empty",
+        ParamValue::Bool(_) => "Bool",
+        ParamValue::I32(_) => "I32",
+        ParamValue::F64(_) => "F64",
+        ParamValue::Date(_) => "Date",
+        ParamValue::String(_) => "String",
+        ParamValue::Blob(_) | ParamValue::PlatformBlob(_) => "Blob",
+    };
+    if type_name == "Blob" {
+        return Err(());
+    }
+
+    let second = params[1].clone();
+    params[1] = params[2].clone();
+    params[2] = second;
+
+    Ok(ParamValue::String(os_string(type_name)))
+}
+
+#[fixture]
+fn add_in() -> RawAddIn {
+    RawAddIn {
+        connection: Arc::new(None),
+        calls: 0,
+        dispatch,
+        count: |params| Ok(ParamValue::I32(params.len() as i32)),
+        reset,
+    }
+}
+
+#[rstest]
+#[case(DISPATCH_NAME_EN, Some(0))]
+#[case(DISPATCH_NAME_RU, Some(0))]
+#[case(COUNT_NAME_EN, Some(1))]
+#[case(COUNT_NAME_RU, Some(1))]
+fn test_find_method(add_in: RawAddIn, #[case] name: &str, #[case] expected: Option<usize>) {
+    assert_eq!(add_in.find_method(&os_string_nil(name)), expected);
+}
+
+#[rstest]
+#[case(0, 3)]
+#[case(1, 0)]
+fn test_get_n_params(add_in: RawAddIn, #[case] method_i: usize, #[case] n_params: usize) {
+    assert_eq!(add_in.get_n_params(method_i), n_params);
+}
+
+#[rstest]
+#[case(0, 0)]
+#[case(0, 2)]
+#[case(1, 0)]
+fn test_get_param_def_value(add_in: RawAddIn, #[case] method_i: usize, #[case] param_i: usize) {
+    assert_eq!(add_in.get_param_def_value(method_i, param_i), None);
+}
+
+#[rstest]
+#[case(0, true)]
+#[case(1, true)]
+#[case(2, false)]
+fn test_has_ret_val(add_in: RawAddIn, #[case] method_i: usize, #[case] has_ret_val: bool) {
+    assert_eq!(add_in.has_ret_val(method_i), has_ret_val);
+}
+
+#[rstest]
+#[case(ParamValue::Empty, "Empty")]
+#[case(ParamValue::I32(1), "I32")]
+#[case(ParamValue::F64(1.0), "F64")]
+#[case(ParamValue::String(os_string("1")), "String")]
+fn test_call_raw_function(
+    mut add_in: RawAddIn,
+    #[case] first: ParamValue,
+    #[case] type_name: &str,
+) {
+    let mut params = ParamValues::new(vec![first, ParamValue::I32(2), ParamValue::Bool(true)]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result, Ok(ParamValue::String(os_string(type_name))));
+    assert_eq!(params[1], ParamValue::Bool(true));
+    assert_eq!(params[2], ParamValue::I32(2));
+    assert_eq!(add_in.calls, 1);
+}
+
+#[rstest]
+fn test_call_raw_procedure(mut add_in: RawAddIn) {
+    let mut params = ParamValues::new(vec![
+        ParamValue::Empty,
+        ParamValue::I32(2),
+        ParamValue::Empty,
+    ]);
+
+    let result = add_in.call_as_proc(0, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[2], ParamValue::I32(2));
+    assert_eq!(add_in.calls, 1);
+}
+
+#[rstest]
+fn test_call_raw_procedure_without_value(mut add_in: RawAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(5)]);
+
+    assert!(add_in.call_as_func(2, &mut params).is_err());
+    assert_eq!(add_in.call_as_proc(2, &mut params), Ok(()));
+    assert_eq!(add_in.calls, 5);
+
+    let mut params = ParamValues::new(vec![ParamValue::Empty]);
+    assert!(add_in.call_as_proc(2, &mut params).is_err());
+}
+
+#[rstest]
+fn test_call_raw_error(mut add_in: RawAddIn) {
+    let mut params = ParamValues::new(vec![
+        ParamValue::Blob(vec![]),
+        ParamValue::Empty,
+        ParamValue::Empty,
+    ]);
+
+    assert!(add_in.call_as_func(0, &mut params).is_err());
+    assert!(add_in.call_as_proc(0, &mut params).is_err());
+}
+
+#[rstest]
+fn test_call_raw_without_self(mut add_in: RawAddIn) {
+    let mut params = ParamValues::new(vec![]);
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(0)));
+}
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+struct RawMethodsAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[add_in_methods]
+impl RawMethodsAddIn {
+    #[add_in_func(name = "First", name_ru = "Первый", raw, n_params = 2)]
+    fn first(&self, params: &mut ParamValues) -> Result<ParamValue, ()> {
+        params.iter().next().cloned().ok_or(())
+    }
+}
+
+#[test]
+fn test_call_raw_method() {
+    let mut add_in = RawMethodsAddIn {
+        connection: Arc::new(None),
+    };
+    let mut params = ParamValues::new(vec![ParamValue::F64(1.5), ParamValue::Empty]);
+
+    assert_eq!(add_in.get_n_params(0), 2);
+    assert!(add_in.has_ret_val(0));
+    assert_eq!(
+        add_in.call_as_func(0, &mut params),
+        Ok(ParamValue::F64(1.5))
+    );
+}
@@ -0,0 +1,128 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection,
+    interface::{AddInWrapper, ParamValue},
+    shared::{self, Shared, SharedKey},
+};
+use native_api_1c_macro::AddIn;
+
+use native_api_1c_test_utils::connection;
+
+#[derive(Default)]
+struct Counter {
+    calls: AtomicUsize,
+}
+
+struct Cache {
+    name: &'static str,
+}
+
+fn open_cache() -> Cache {
+    Cache { name: "cache" }
+}
+
+#[derive(AddIn)]
+struct SharedAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_shared]
+    counter: Shared<Counter>,
+
+    #[add_in_shared(init = open_cache, key = "cache")]
+    cache: Shared<Cache>,
+
+    #[add_in_prop(ty = Int, name = "Calls", name_ru = "Вызовы", readable)]
+    calls: i32,
+}
+
+impl SharedAddIn {
+    fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            counter: Shared::new(),
+            cache: Shared::new(),
+            calls: 0,
+        }
+    }
+}
+
+/// Other AddIn of the library, that has its own counter, but shares the cache
+#[derive(AddIn)]
+struct OtherAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_shared]
+    counter: Shared<Counter>,
+
+    #[add_in_shared(key = "cache")]
+    cache: Shared<Cache>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache { name: "other" }
+    }
+}
+
+// Tests, that share values by the same keys and are run one by one
+#[test]
+fn test_shared() {
+    shared_between_instances();
+    shared_between_types();
+}
+
+fn shared_between_instances() {
+    let key = SharedKey::owner::<SharedAddIn>();
+    let mut first = SharedAddIn::new();
+    let mut second = SharedAddIn::new();
+    assert!(first.counter.get().is_none());
+
+    assert!(first.init(connection()));
+    assert!(second.init(connection()));
+    first.counter.calls.fetch_add(1, Ordering::Relaxed);
+    second.counter.calls.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(first.counter.calls.load(Ordering::Relaxed), 2);
+    assert!(Arc::ptr_eq(
+        first.counter.arc().unwrap(),
+        second.counter.arc().unwrap()
+    ));
+    assert_eq!(second.cache.name, "cache");
+    assert!(matches!(first.get_prop_val(0), Ok(ParamValue::I32(0))));
+
+    // value lives while any instance does
+    drop(first);
+    assert!(shared::get::<Counter>(key).is_some());
+    drop(second);
+    assert!(shared::get::<Counter>(key).is_none());
+    assert!(shared::get::<Cache>(SharedKey::named("cache")).is_none());
+
+    let mut third = SharedAddIn::new();
+    assert!(third.init(connection()));
+    assert_eq!(third.counter.calls.load(Ordering::Relaxed), 0);
+}
+
+fn shared_between_types() {
+    let mut first = SharedAddIn::new();
+    let mut other = OtherAddIn {
+        connection: Arc::new(None),
+        counter: Shared::new(),
+        cache: Shared::new(),
+    };
+    assert!(first.init(connection()));
+    assert!(other.init(connection()));
+
+    // values of the same type don't collide, unless they have the same key
+    first.counter.calls.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(other.counter.calls.load(Ordering::Relaxed), 0);
+    assert!(Arc::ptr_eq(
+        first.cache.arc().unwrap(),
+        other.cache.arc().unwrap()
+    ));
+    assert_eq!(other.cache.name, "cache");
+}
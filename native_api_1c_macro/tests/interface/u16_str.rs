@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue, ParamValues, U16Str, U16String},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+#[derive(AddIn)]
+struct U16StrAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = U16Str, name = "Text", name_ru = "Текст", readable, writable)]
+    pub text: U16String,
+
+    #[add_in_func(name = "Len", name_ru = "Длина")]
+    pub len: fn(&U16Str) -> i32,
+
+    #[add_in_func(name = "Concat", name_ru = "Соединить")]
+    #[arg]
+    #[arg(default = "!")]
+    pub concat: fn(&Self, &U16Str, U16String) -> U16String,
+
+    #[add_in_func(name = "Append", name_ru = "Дописать")]
+    pub append: fn(&mut U16String),
+
+    #[add_in_func(name = "Write", name_ru = "Записать")]
+    #[arg(as_out)]
+    pub write: fn(&mut U16String),
+}
+
+#[fixture]
+fn add_in() -> U16StrAddIn {
+    U16StrAddIn {
+        connection: Arc::new(None),
+        text: U16String::from("text"),
+        len: |str| str.len() as i32,
+        concat: |add_in, str, suffix| {
+            let mut result = add_in.text.clone();
+            result.push_u16_str(str);
+            result.push_u16_str(&suffix);
+            result
+        },
+        append: |str| str.push_str(", world"),
+        write: |str| str.push_str("written"),
+    }
+}
+
+fn string(value: &str) -> ParamValue {
+    ParamValue::String(os_string(value))
+}
+
+#[rstest]
+fn test_call_borrowed_arg(mut add_in: U16StrAddIn) {
+    let mut params = ParamValues::new(vec![string("Привет")]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(6)));
+}
+
+#[rstest]
+#[case(vec![string(": "), string("?")], ParamValue::String(os_string("text: ?")))]
+#[case(vec![string(": ")], ParamValue::String(os_string("text: !")))]
+fn test_call_owned_arg_and_return(
+    mut add_in: U16StrAddIn,
+    #[case] params: Vec<ParamValue>,
+    #[case] expected: ParamValue,
+) {
+    let n_params = add_in.get_n_params(1);
+    let mut params = ParamValues::new(
+        (0..n_params)
+            .map(|i| match params.get(i) {
+                Some(param) => param.clone(),
+                None => add_in.get_param_def_value(1, i).unwrap(),
+            })
+            .collect(),
+    );
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(expected));
+}
+
+#[rstest]
+#[case(2, string("Hello"), string("Hello, world"))]
+#[case(3, ParamValue::Empty, string("written"))]
+#[case(3, string("ignored"), string("written"))]
+fn test_call_out_args(
+    mut add_in: U16StrAddIn,
+    #[case] method_i: usize,
+    #[case] incoming: ParamValue,
+    #[case] expected: ParamValue,
+) {
+    let mut params = ParamValues::new(vec![incoming]);
+
+    let result = add_in.call_as_proc(method_i, &mut params);
+    assert!(result.is_ok());
+    assert_eq!(params[0], expected);
+}
+
+#[rstest]
+fn test_call_wrong_type(mut add_in: U16StrAddIn) {
+    let mut params = ParamValues::new(vec![ParamValue::I32(1)]);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert!(result.is_err());
+}
+
+#[rstest]
+fn test_prop(mut add_in: U16StrAddIn) {
+    assert_eq!(add_in.get_prop_val(0), Ok(string("text")));
+
+    let result = add_in.set_prop_val(0, string("новый"));
+    assert!(result.is_ok());
+    assert_eq!(add_in.text, U16String::from("новый"));
+}
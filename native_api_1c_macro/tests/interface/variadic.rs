@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::{connection::Connection, string_utils::os_string},
+    interface::{AddInWrapper, ParamValue, ParamValues},
+};
+use native_api_1c_macro::AddIn;
+use rstest::{fixture, rstest};
+
+const FORMAT_MAX: usize = 3;
+const SUM_MAX: usize = 4;
+
+#[derive(AddIn)]
+struct VariadicAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Format", name_ru = "Форматировать")]
+    #[arg]
+    #[arg(variadic, max = 3)]
+    pub format: fn(String, Vec<ParamValue>) -> String,
+
+    #[add_in_func(name = "Sum", name_ru = "Сумма")]
+    #[arg(variadic, max = 4)]
+    pub sum: fn(Vec<i32>) -> i32,
+}
+
+#[fixture]
+fn add_in() -> VariadicAddIn {
+    VariadicAddIn {
+        connection: Arc::new(None),
+        format: |template, values| format!("{template}: {}", values.len()),
+        sum: |values| values.iter().sum(),
+    }
+}
+
+#[rstest]
+#[case(0, 1 + FORMAT_MAX)]
+#[case(1, SUM_MAX)]
+fn test_get_n_params(add_in: VariadicAddIn, #[case] method_i: usize, #[case] n_params: usize) {
+    assert_eq!(add_in.get_n_params(method_i), n_params);
+}
+
+#[rstest]
+#[case(0, 0, None)]
+#[case(0, 1, Some(ParamValue::Empty))]
+#[case(0, 3, Some(ParamValue::Empty))]
+#[case(0, 4, None)]
+#[case(1, 0, Some(ParamValue::Empty))]
+#[case(1, 3, Some(ParamValue::Empty))]
+#[case(1, 4, None)]
+fn test_get_param_def_value(
+    add_in: VariadicAddIn,
+    #[case] method_i: usize,
+    #[case] param_i: usize,
+    #[case] expected: Option<ParamValue>,
+) {
+    assert_eq!(add_in.get_param_def_value(method_i, param_i), expected);
+}
+
+#[rstest]
+#[case(vec![ParamValue::Empty, ParamValue::Empty, ParamValue::Empty], 0)]
+#[case(vec![ParamValue::I32(1), ParamValue::Empty, ParamValue::Empty], 1)]
+#[case(vec![ParamValue::Empty, ParamValue::Bool(true), ParamValue::Empty], 2)]
+#[case(vec![ParamValue::I32(1), ParamValue::F64(2.0), ParamValue::Bool(true)], 3)]
+fn test_call_raw_variadic(
+    mut add_in: VariadicAddIn,
+    #[case] values: Vec<ParamValue>,
+    #[case] expected_len: usize,
+) {
+    let mut params = vec![ParamValue::String(os_string("Values"))];
+    params.extend(values);
+    let mut params = ParamValues::new(params);
+
+    let result = add_in.call_as_func(0, &mut params);
+    assert_eq!(
+        result,
+        Ok(ParamValue::String(os_string(&format!(
+            "Values: {expected_len}"
+        ))))
+    );
+}
+
+#[rstest]
+#[case(vec![], 0)]
+#[case(vec![ParamValue::I32(1)], 1)]
+#[case(vec![ParamValue::I32(1), ParamValue::I32(2), ParamValue::Empty], 3)]
+#[case(vec![ParamValue::I32(1), ParamValue::I32(2), ParamValue::I32(3), ParamValue::I32(4)], 10)]
+fn test_call_typed_variadic(
+    mut add_in: VariadicAddIn,
+    #[case] mut values: Vec<ParamValue>,
+    #[case] expected: i32,
+) {
+    values.resize(SUM_MAX, ParamValue::Empty);
+    let mut params = ParamValues::new(values);
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert_eq!(result, Ok(ParamValue::I32(expected)));
+}
+
+#[rstest]
+#[case(vec![ParamValue::I32(1), ParamValue::Empty, ParamValue::I32(3), ParamValue::Empty])]
+#[case(vec![ParamValue::I32(1), ParamValue::Bool(true), ParamValue::Empty, ParamValue::Empty])]
+fn test_call_typed_variadic_invalid(mut add_in: VariadicAddIn, #[case] values: Vec<ParamValue>) {
+    let mut params = ParamValues::new(values);
+
+    let result = add_in.call_as_func(1, &mut params);
+    assert!(result.is_err());
+}
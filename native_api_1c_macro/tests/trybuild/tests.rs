@@ -3,6 +3,7 @@ fn trybuild_props() {
     let t = trybuild::TestCases::new();
 
     t.pass("tests/trybuild/to_build/props.rs");
+    t.compile_fail("tests/trybuild/to_build/computed_props/without_getter.rs");
 }
 
 #[test]
@@ -28,11 +29,55 @@ fn trybuild_functions() {
     t.pass("tests/trybuild/to_build/functions/out_params/float_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/str_type.rs");
     t.pass("tests/trybuild/to_build/functions/out_params/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/out_params/date_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/out_params/conflicting_modes.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/out_params/out_default.rs");
 
     t.pass("tests/trybuild/to_build/functions/defaults/bool_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/int_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/float_type.rs");
     t.pass("tests/trybuild/to_build/functions/defaults/str_type.rs");
-    t.compile_fail("tests/trybuild/to_build/functions/defaults/date_type.rs");
-    t.compile_fail("tests/trybuild/to_build/functions/defaults/blob_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/date_type.rs");
+    t.pass("tests/trybuild/to_build/functions/defaults/blob_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/invalid_date_type.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/defaults/invalid_blob_type.rs");
+
+    t.pass("tests/trybuild/to_build/functions/inferred/all_types.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/conflicting_arg.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/conflicting_return.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/unknown_arg.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/inferred/qualified_arg.rs");
+
+    t.pass("tests/trybuild/to_build/functions/variadic/all_types.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/variadic/not_last.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/variadic/no_max.rs");
+
+    t.compile_fail("tests/trybuild/to_build/functions/raw/invalid_signature.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/raw/no_n_params.rs");
+
+    t.compile_fail("tests/trybuild/to_build/functions/json/default.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/json/prop_type.rs");
+
+    t.compile_fail("tests/trybuild/to_build/functions/handles/not_reference.rs");
+    t.compile_fail("tests/trybuild/to_build/functions/handles/with_default.rs");
+}
+
+#[test]
+fn trybuild_names() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/trybuild/to_build/names/invalid_syntax.rs");
+    t.compile_fail("tests/trybuild/to_build/names/duplicate_method.rs");
+    t.compile_fail("tests/trybuild/to_build/names/prop_and_method.rs");
+    t.compile_fail("tests/trybuild/to_build/names/impl_methods.rs");
+    t.compile_fail("tests/trybuild/to_build/names/impl_methods_prop.rs");
+}
+
+#[test]
+fn trybuild_messages() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/trybuild/to_build/messages/missing_key.rs");
+    t.compile_fail("tests/trybuild/to_build/messages/invalid_line.rs");
+    t.compile_fail("tests/trybuild/to_build/messages/without_default.rs");
 }
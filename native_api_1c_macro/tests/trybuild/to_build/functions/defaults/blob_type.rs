@@ -12,6 +12,12 @@ pub struct MyAddIn {
     #[arg(ty = Blob, default = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9])]
     #[returns(ty = Blob)]
     pub my_function: fn(&Self, Vec<u8>) -> Vec<u8>,
+
+    #[add_in_func(name = "MyFunctionLiterals", name_ru = "МояФункцияЛитералы")]
+    #[arg(default = b"\x00\x01bytes")]
+    #[arg(default = "hex:DEAD beef")]
+    #[arg(default = "base64:AAECAw==")]
+    pub my_function_literals: fn(&Self, Vec<u8>, Vec<u8>, Vec<u8>),
 }
 
 impl MyAddIn {
@@ -19,6 +25,7 @@ impl MyAddIn {
         Self {
             connection: Arc::new(None),
             my_function: Self::my_function_inner,
+            my_function_literals: |_, _, _, _| {},
         }
     }
 
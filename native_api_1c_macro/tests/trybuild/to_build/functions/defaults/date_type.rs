@@ -12,6 +12,19 @@ pub struct MyAddIn {
     #[arg(ty = Date, default = "2021-01-01T00:00:00+00:00")]
     #[returns(ty = Date)]
     pub my_function: fn(&Self, chrono::NaiveDateTime) -> chrono::NaiveDateTime,
+
+    #[add_in_func(name = "MyFunctionFormats", name_ru = "МояФункцияФорматы")]
+    #[arg(default = "2021-01-01")]
+    #[arg(default = "2021-01-01T12:30:00")]
+    #[arg(default = "2021-01-01 12:30:00")]
+    #[arg(default = chrono::NaiveDateTime::UNIX_EPOCH)]
+    pub my_function_formats: fn(
+        &Self,
+        chrono::NaiveDateTime,
+        chrono::NaiveDateTime,
+        chrono::NaiveDateTime,
+        chrono::NaiveDateTime,
+    ),
 }
 
 impl MyAddIn {
@@ -19,6 +32,7 @@ impl MyAddIn {
         Self {
             connection: Arc::new(None),
             my_function: Self::my_function_inner,
+            my_function_formats: |_, _, _, _, _| {},
         }
     }
 
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, handles::HandleRegistry};
+use native_api_1c_macro::AddIn;
+
+pub struct Buffer(String);
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_handles]
+    buffers: HandleRegistry<Buffer>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    #[arg(handle = buffers)]
+    pub my_function: fn(Buffer),
+}
+
+fn main() {}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
+    pub my_function: fn(&Self, bool, i32, f64, String, chrono::NaiveDateTime, Vec<u8>) -> String,
+
+    #[add_in_func(name = "MyResultFunction", name_ru = "МояФункцияРезультат")]
+    #[arg]
+    #[arg(default = 12)]
+    pub my_result_function: fn(&mut Self, i32, i32) -> Result<i32, ()>,
+
+    #[add_in_func(name = "MyOutProcedure", name_ru = "МояПроцедураВывод")]
+    pub my_out_procedure: fn(&mut Self, &mut String) -> Result<(), ()>,
+
+    #[add_in_func(name = "MyQualifiedFunction", name_ru = "МояФункцияСПутями")]
+    pub my_qualified_function: fn(
+        &Self,
+        std::string::String,
+        chrono::DateTime<chrono::FixedOffset>,
+        std::vec::Vec<u8>,
+    ) -> native_api_1c::native_api_1c_core::handles::Handle<String>,
+
+    #[add_in_func(name = "MyExplicitFunction", name_ru = "МояЯвнаяФункция")]
+    #[arg(ty = Int)]
+    #[returns(ty = Int)]
+    pub my_explicit_function: fn(&Self, i64) -> i32,
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            my_function: Self::my_function_inner,
+            my_result_function: Self::my_result_function_inner,
+            my_out_procedure: Self::my_out_procedure_inner,
+            my_qualified_function: Self::my_qualified_function_inner,
+            my_explicit_function: Self::my_explicit_function_inner,
+        }
+    }
+
+    fn my_function_inner(
+        &self,
+        _: bool,
+        _: i32,
+        _: f64,
+        s: String,
+        _: chrono::NaiveDateTime,
+        _: Vec<u8>,
+    ) -> String {
+        s
+    }
+
+    fn my_result_function_inner(&mut self, a: i32, b: i32) -> Result<i32, ()> {
+        Ok(a + b)
+    }
+
+    fn my_out_procedure_inner(&mut self, out: &mut String) -> Result<(), ()> {
+        *out = "out".to_string();
+        Ok(())
+    }
+
+    fn my_qualified_function_inner(
+        &self,
+        _: std::string::String,
+        _: chrono::DateTime<chrono::FixedOffset>,
+        _: std::vec::Vec<u8>,
+    ) -> native_api_1c::native_api_1c_core::handles::Handle<String> {
+        unimplemented!()
+    }
+
+    fn my_explicit_function_inner(&self, arg: i64) -> i32 {
+        arg as i32
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
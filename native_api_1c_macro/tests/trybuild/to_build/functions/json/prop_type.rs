@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Json, name = "MyProp", name_ru = "МоеСвойство", readable)]
+    pub my_prop: Vec<String>,
+}
+
+fn main() {}
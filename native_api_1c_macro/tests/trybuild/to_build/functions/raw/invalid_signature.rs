@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, interface::ParamValue};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция", raw, n_params = 2)]
+    pub my_function: fn(&mut Self, i32, i32) -> Result<ParamValue, ()>,
+}
+
+fn main() {}
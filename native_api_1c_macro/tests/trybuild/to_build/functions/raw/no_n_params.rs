@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::{
+    ffi::connection::Connection,
+    interface::{ParamValue, ParamValues},
+};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "MyFunction", name_ru = "МояФункция", raw)]
+    pub my_function: fn(&mut Self, &mut ParamValues) -> Result<ParamValue, ()>,
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use native_api_1c::native_api_1c_core::{ffi::connection::Connection, interface::ParamValue};
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Log", name_ru = "Лог")]
+    #[arg(ty = Str)]
+    #[arg(variadic, max = 10)]
+    pub log: fn(&Self, String, Vec<ParamValue>),
+
+    #[add_in_func(name = "Strings", name_ru = "Строки")]
+    #[arg(variadic, max = 2)]
+    pub strings: fn(&mut Self, Vec<String>) -> Result<String, ()>,
+
+    #[add_in_func(name = "Dates", name_ru = "Даты")]
+    #[arg(variadic, max = 2)]
+    pub dates: fn(Vec<NaiveDateTime>) -> bool,
+
+    #[add_in_func(name = "Blobs", name_ru = "Двоичные")]
+    #[arg(variadic, max = 2)]
+    pub blobs: fn(Vec<Vec<u8>>) -> Vec<u8>,
+
+    #[add_in_func(name = "Longs", name_ru = "Длинные")]
+    #[arg(ty = Int, variadic, max = 2)]
+    pub longs: fn(Vec<i64>),
+}
+
+impl MyAddIn {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(None),
+            log: |_, _, _| {},
+            strings: |_, values| Ok(values.concat()),
+            dates: |values| values.is_empty(),
+            blobs: |values| values.concat(),
+            longs: |_| {},
+        }
+    }
+}
+
+fn main() {
+    let _add_in = MyAddIn::new();
+}
@@ -0,0 +1,10 @@
+use native_api_1c::native_api_1c_core::localization::MessageCatalog;
+use native_api_1c_macro::message_catalog;
+
+fn main() {
+    let _: MessageCatalog = message_catalog!(
+        default = "ru",
+        ru = "catalog/ru.txt",
+        en = "catalog/en.txt",
+    );
+}
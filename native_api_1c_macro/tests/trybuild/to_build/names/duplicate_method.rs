@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_func(name = "Run", name_ru = "Выполнить")]
+    pub run: fn(),
+
+    #[add_in_func(name = "run", name_ru = "ВЫПОЛНИТЬ")]
+    pub run_again: fn(),
+}
+
+fn main() {}
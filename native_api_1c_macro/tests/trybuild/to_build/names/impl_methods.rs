@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::{add_in_methods, AddIn};
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+}
+
+#[add_in_methods]
+impl MyAddIn {
+    #[add_in_func(name = "Start", name_ru = "Старт")]
+    fn start(&mut self) {}
+
+    #[add_in_func(name = "Begin", name_ru = "старт")]
+    fn begin(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::{add_in_methods, AddIn};
+
+#[derive(AddIn)]
+#[add_in(impl_methods)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Количество", readable)]
+    pub count: i32,
+}
+
+#[add_in_methods]
+impl MyAddIn {
+    #[add_in_func(name = "count", name_ru = "Посчитать")]
+    fn count_items(&self) -> i32 {
+        self.count
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use native_api_1c::native_api_1c_core::ffi::connection::Connection;
+use native_api_1c_macro::AddIn;
+
+#[derive(AddIn)]
+pub struct MyAddIn {
+    #[add_in_con]
+    connection: Arc<Option<&'static Connection>>,
+
+    #[add_in_prop(ty = Int, name = "Count", name_ru = "Количество", readable)]
+    pub count: i32,
+
+    #[add_in_func(name = "Count", name_ru = "Посчитать")]
+    pub count_items: fn() -> i32,
+}
+
+fn main() {}
@@ -0,0 +1,399 @@
+//! Fake host of Native API, implementing interfaces, provided by 1C, used by
+//! tests of the workspace crates
+
+use std::{
+    alloc::{alloc, dealloc, Layout},
+    cell::RefCell,
+    collections::HashMap,
+    ffi::{c_long, c_ulong, c_ushort, c_void},
+    ptr,
+};
+
+use native_api_1c_core::{
+    ffi::{
+        connection::Connection,
+        create_component, destroy_component,
+        memory_manager::MemoryManager,
+        provided_types::{TVariant, VariantType},
+        string_utils::{from_os_string, os_string},
+    },
+    interface::AddInWrapper,
+};
+
+/// Allocations of larger size fail
+pub const MAX_ALLOCATION: usize = 1024;
+
+thread_local! {
+    /// Sizes of allocated blocks by address
+    static ALLOCATED: RefCell<HashMap<usize, usize>> = RefCell::default();
+}
+
+/// Layout of `IMemoryManager` vtable, as provided by 1C
+#[repr(C)]
+struct FakeVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    alloc_memory: unsafe extern "system" fn(&FakeManager, *mut *mut c_void, c_ulong) -> bool,
+    free_memory: unsafe extern "system" fn(&FakeManager, *mut *mut c_void),
+}
+
+#[repr(C)]
+struct FakeManager {
+    vptr: &'static FakeVTable,
+}
+
+unsafe extern "system" fn alloc_memory(
+    _: &FakeManager,
+    ptr: *mut *mut c_void,
+    size: c_ulong,
+) -> bool {
+    let size = size as usize;
+    if size > MAX_ALLOCATION {
+        return false;
+    }
+    let block = alloc(Layout::from_size_align(size.max(1), 8).unwrap());
+    ALLOCATED.with(|allocated| allocated.borrow_mut().insert(block as usize, size));
+    *ptr = block.cast();
+    true
+}
+
+unsafe extern "system" fn free_memory(_: &FakeManager, ptr: *mut *mut c_void) {
+    let block = *ptr as usize;
+    let size = ALLOCATED
+        .with(|allocated| allocated.borrow_mut().remove(&block))
+        .expect("freed memory that was not allocated");
+    dealloc(
+        block as *mut u8,
+        Layout::from_size_align(size.max(1), 8).unwrap(),
+    );
+    *ptr = std::ptr::null_mut();
+}
+
+static VTABLE: FakeVTable = FakeVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    alloc_memory,
+    free_memory,
+};
+
+static MANAGER: FakeManager = FakeManager { vptr: &VTABLE };
+
+pub fn memory_manager() -> &'static MemoryManager {
+    unsafe { &*(&MANAGER as *const FakeManager).cast::<MemoryManager>() }
+}
+
+pub fn allocated() -> Vec<usize> {
+    ALLOCATED.with(|allocated| allocated.borrow().values().copied().collect())
+}
+
+/// Frees string or blob of the variant, returned by the AddIn
+pub fn free_variant(variant: &TVariant) {
+    let mut block: *mut c_void = match variant.vt {
+        VariantType::WStr => unsafe { variant.value.data_str.ptr.cast() },
+        VariantType::Blob | VariantType::PStr => unsafe { variant.value.data_blob.ptr.cast() },
+        _ => return,
+    };
+    if !block.is_null() {
+        unsafe { free_memory(&MANAGER, &mut block) };
+    }
+}
+
+/// Layout of `IInitDoneBase` vtable, as called by 1C
+#[repr(C)]
+struct InitDoneVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    init: unsafe extern "system" fn(*mut c_void, *const c_void) -> bool,
+    set_mem_manager: unsafe extern "system" fn(*mut c_void, *const MemoryManager) -> bool,
+    get_info: unsafe extern "system" fn(*mut c_void) -> c_long,
+    done: unsafe extern "system" fn(*mut c_void),
+}
+
+/// Layout of `UserLanguageBase` vtable, as called by 1C
+#[repr(C)]
+struct UserLanguageVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    set_user_interface_language_code: unsafe extern "system" fn(*mut c_void, *const u16),
+}
+
+/// Layout of `ILanguageExtenderBase` vtable, as called by 1C
+#[repr(C)]
+struct LanguageExtenderVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    register_extension_as: unsafe extern "system" fn(*mut c_void, *mut *mut u16) -> bool,
+    get_n_props: unsafe extern "system" fn(*mut c_void) -> c_long,
+    find_prop: unsafe extern "system" fn(*mut c_void, *const u16) -> c_long,
+    get_prop_name: unsafe extern "system" fn(*mut c_void, c_long, c_long) -> *const u16,
+    get_prop_val: unsafe extern "system" fn(*mut c_void, c_long, *mut TVariant) -> bool,
+    set_prop_val: unsafe extern "system" fn(*mut c_void, c_long, *const TVariant) -> bool,
+    is_prop_readable: unsafe extern "system" fn(*mut c_void, c_long) -> bool,
+    is_prop_writable: unsafe extern "system" fn(*mut c_void, c_long) -> bool,
+    get_n_methods: unsafe extern "system" fn(*mut c_void) -> c_long,
+    find_method: unsafe extern "system" fn(*mut c_void, *const u16) -> c_long,
+    get_method_name: unsafe extern "system" fn(*mut c_void, c_long, c_long) -> *const u16,
+    get_n_params: unsafe extern "system" fn(*mut c_void, c_long) -> c_long,
+    get_param_def_value:
+        unsafe extern "system" fn(*mut c_void, c_long, c_long, *mut TVariant) -> bool,
+    has_ret_val: unsafe extern "system" fn(*mut c_void, c_long) -> bool,
+    call_as_proc: unsafe extern "system" fn(*mut c_void, c_long, *mut TVariant, c_long) -> bool,
+    call_as_func: unsafe extern "system" fn(
+        *mut c_void,
+        c_long,
+        *mut TVariant,
+        *mut TVariant,
+        c_long,
+    ) -> bool,
+}
+
+thread_local! {
+    /// Errors, passed to `AddError`, with their sources
+    static ERRORS: RefCell<Vec<(String, String)>> = RefCell::default();
+}
+
+/// Layout of `IAddInDefBase` vtable, as provided by 1C
+#[repr(C)]
+struct FakeConnectionVTable {
+    dtor: usize,
+    #[cfg(target_family = "unix")]
+    dtor2: usize,
+    add_error: unsafe extern "system" fn(
+        &FakeConnection,
+        c_ushort,
+        *const u16,
+        *const u16,
+        c_long,
+    ) -> bool,
+    read: unsafe extern "system" fn(
+        &FakeConnection,
+        *mut u16,
+        &mut TVariant,
+        c_long,
+        *mut *mut u16,
+    ) -> bool,
+    write: unsafe extern "system" fn(&FakeConnection, *mut u16, &mut TVariant) -> bool,
+    register_profile_as: unsafe extern "system" fn(&FakeConnection, *mut u16) -> bool,
+    set_event_buffer_depth: unsafe extern "system" fn(&FakeConnection, c_long) -> bool,
+    get_event_buffer_depth: unsafe extern "system" fn(&FakeConnection) -> c_long,
+    external_event:
+        unsafe extern "system" fn(&FakeConnection, *mut u16, *mut u16, *mut u16) -> bool,
+    clean_event_buffer: unsafe extern "system" fn(&FakeConnection),
+    set_status_line: unsafe extern "system" fn(&FakeConnection, *mut u16) -> bool,
+    reset_status_line: unsafe extern "system" fn(&FakeConnection),
+}
+
+#[repr(C)]
+struct FakeConnection {
+    vptr: &'static FakeConnectionVTable,
+}
+
+/// Null-terminated string, passed by the AddIn
+unsafe fn read_wstr(ptr: *const u16) -> String {
+    let len = (0..).take_while(|&i| *ptr.add(i) != 0).count();
+    from_os_string(std::slice::from_raw_parts(ptr, len))
+}
+
+unsafe extern "system" fn add_error(
+    _: &FakeConnection,
+    _: c_ushort,
+    source: *const u16,
+    description: *const u16,
+    _: c_long,
+) -> bool {
+    let error = (read_wstr(source), read_wstr(description));
+    ERRORS.with(|errors| errors.borrow_mut().push(error));
+    true
+}
+
+unsafe extern "system" fn read(
+    _: &FakeConnection,
+    _: *mut u16,
+    _: &mut TVariant,
+    _: c_long,
+    _: *mut *mut u16,
+) -> bool {
+    false
+}
+
+unsafe extern "system" fn write(_: &FakeConnection, _: *mut u16, _: &mut TVariant) -> bool {
+    false
+}
+
+unsafe extern "system" fn with_string(_: &FakeConnection, _: *mut u16) -> bool {
+    false
+}
+
+unsafe extern "system" fn set_event_buffer_depth(_: &FakeConnection, _: c_long) -> bool {
+    false
+}
+
+unsafe extern "system" fn get_event_buffer_depth(_: &FakeConnection) -> c_long {
+    0
+}
+
+unsafe extern "system" fn external_event(
+    _: &FakeConnection,
+    _: *mut u16,
+    _: *mut u16,
+    _: *mut u16,
+) -> bool {
+    false
+}
+
+unsafe extern "system" fn no_op(_: &FakeConnection) {}
+
+static CONNECTION_VTABLE: FakeConnectionVTable = FakeConnectionVTable {
+    dtor: 0,
+    #[cfg(target_family = "unix")]
+    dtor2: 0,
+    add_error,
+    read,
+    write,
+    register_profile_as: with_string,
+    set_event_buffer_depth,
+    get_event_buffer_depth,
+    external_event,
+    clean_event_buffer: no_op,
+    set_status_line: with_string,
+    reset_status_line: no_op,
+};
+
+static CONNECTION: FakeConnection = FakeConnection {
+    vptr: &CONNECTION_VTABLE,
+};
+
+/// Connection, that records errors, reported by the AddIn
+pub fn connection() -> &'static Connection {
+    unsafe { &*(&CONNECTION as *const FakeConnection).cast::<Connection>() }
+}
+
+/// Takes errors, reported to the connection by this thread, as sources and
+/// descriptions
+pub fn take_errors() -> Vec<(String, String)> {
+    ERRORS.with(|errors| errors.take())
+}
+
+/// Host, that loads the component the way 1C does: through the vtables of its
+/// interfaces, which follow each other at the start of the component
+pub struct FakeHost {
+    component: *mut c_void,
+}
+
+impl FakeHost {
+    /// Creates and initializes the component, hosts of Native API 1.0 may
+    /// not provide MemoryManager
+    pub fn new<T: AddInWrapper>(addin: T, with_memory_manager: bool) -> Self {
+        let mut component = ptr::null_mut();
+        unsafe {
+            create_component(&mut component, addin);
+            Self::attach(component, with_memory_manager)
+        }
+    }
+
+    /// Initializes the component, created by the library, e.g. with
+    /// `GetClassObject`
+    /// # Safety
+    /// `component` must be created by `create_component` and not destroyed
+    pub unsafe fn attach(component: *mut c_void, with_memory_manager: bool) -> Self {
+        let host = Self { component };
+
+        let (this, vtable) = host.init_done();
+        unsafe {
+            assert!((vtable.init)(this, ptr::addr_of!(CONNECTION).cast()));
+            if with_memory_manager {
+                assert!((vtable.set_mem_manager)(this, memory_manager()));
+            }
+        }
+        host
+    }
+
+    /// Calls `SetMemManager`, hosts of Native API 1.0 may pass null as `None`
+    pub fn set_mem_manager(&self, mem: Option<&MemoryManager>) -> bool {
+        let (this, vtable) = self.init_done();
+        let mem = mem.map_or(ptr::null(), |mem| mem as *const MemoryManager);
+        unsafe { (vtable.set_mem_manager)(this, mem) }
+    }
+
+    fn interface<V>(&self, index: usize) -> (*mut c_void, &V) {
+        unsafe {
+            let this = self.component.cast::<*const V>().add(index);
+            (this.cast(), &**this)
+        }
+    }
+
+    fn init_done(&self) -> (*mut c_void, &InitDoneVTable) {
+        self.interface(0)
+    }
+
+    fn lang_extender(&self) -> (*mut c_void, &LanguageExtenderVTable) {
+        self.interface(1)
+    }
+
+    fn user_lang(&self) -> (*mut c_void, &UserLanguageVTable) {
+        self.interface(3)
+    }
+
+    pub fn set_user_interface_language_code(&self, code: &str) {
+        let code: Vec<u16> = os_string(code).into_iter().chain(Some(0)).collect();
+        let (this, vtable) = self.user_lang();
+        unsafe { (vtable.set_user_interface_language_code)(this, code.as_ptr()) }
+    }
+
+    pub fn set_prop_val(&self, num: c_long, value: &TVariant) -> bool {
+        let (this, vtable) = self.lang_extender();
+        unsafe { (vtable.set_prop_val)(this, num, value) }
+    }
+
+    pub fn get_info(&self) -> c_long {
+        let (this, vtable) = self.init_done();
+        unsafe { (vtable.get_info)(this) }
+    }
+
+    pub fn get_prop_val(&self, num: c_long) -> Option<TVariant> {
+        let (this, vtable) = self.lang_extender();
+        let mut value = TVariant::default();
+        unsafe { (vtable.get_prop_val)(this, num, &mut value) }.then_some(value)
+    }
+
+    pub fn get_param_def_value(&self, method: c_long, param: c_long) -> Option<TVariant> {
+        let (this, vtable) = self.lang_extender();
+        let mut value = TVariant::default();
+        unsafe { (vtable.get_param_def_value)(this, method, param, &mut value) }.then_some(value)
+    }
+
+    pub fn call_as_proc(&self, method: c_long, params: &mut [TVariant]) -> bool {
+        let (this, vtable) = self.lang_extender();
+        unsafe { (vtable.call_as_proc)(this, method, params.as_mut_ptr(), params.len() as c_long) }
+    }
+
+    pub fn call_as_func(&self, method: c_long, params: &mut [TVariant]) -> Option<TVariant> {
+        let (this, vtable) = self.lang_extender();
+        let mut value = TVariant::default();
+        unsafe {
+            (vtable.call_as_func)(
+                this,
+                method,
+                &mut value,
+                params.as_mut_ptr(),
+                params.len() as c_long,
+            )
+        }
+        .then_some(value)
+    }
+}
+
+impl Drop for FakeHost {
+    fn drop(&mut self) {
+        let (this, vtable) = self.init_done();
+        unsafe {
+            (vtable.done)(this);
+            destroy_component(&mut self.component);
+        }
+    }
+}
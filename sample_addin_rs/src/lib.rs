@@ -27,11 +27,11 @@ pub struct SampleAddIn {
     ///  CompObj.MyFunction(10);     // 2nd arg = 12 (default value)
     /// ```
     /// If function returns an error, but does not panic, then 1C will throw an exception
+    /// Types of arguments and return value are inferred from the signature
     #[add_in_func(name = "MyFunction", name_ru = "МояФункция")]
-    #[arg(ty = Int)]
-    #[arg(ty = Int, default = 12)] // default value for the second argument
-    #[returns(ty = Int, result)]
-    pub my_function: fn(&Self, i32, i64) -> Result<i32, ()>,
+    #[arg]
+    #[arg(default = 12)] // default value for the second argument
+    pub my_function: fn(&Self, i32, i32) -> Result<i32, ()>,
 
     /// Function, taking no arguments and returning nothing
     #[add_in_func(name = "MyProcedure", name_ru = "МояПроцедура")]
@@ -55,12 +55,8 @@ impl Default for SampleAddIn {
 }
 
 impl SampleAddIn {
-    fn my_function_inner(&self, arg: i32, arg_maybe_default: i64) -> Result<i32, ()> {
-        Ok(self.protected_prop
-            + self.some_prop
-            + arg
-            + self.private_field
-            + arg_maybe_default as i32)
+    fn my_function_inner(&self, arg: i32, arg_maybe_default: i32) -> Result<i32, ()> {
+        Ok(self.protected_prop + self.some_prop + arg + self.private_field + arg_maybe_default)
     }
 
     fn my_procedure_inner(&mut self) {